@@ -0,0 +1,721 @@
+//
+// Libraries - native
+//
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+use std::time::Duration;
+//
+// Libraries - downloaded
+//
+use clap::{Args, ValueEnum};
+use sha2::{Digest, Sha256};
+//
+// Libraries - local
+//
+use crate::output::Output;
+use easy_paths::{
+    find, get_absolute_path, get_absolute_path_or_error, get_common_prefix, get_path_normalized,
+    get_relative_path, get_relative_path_between, get_string_from_type_osstr_lossy,
+    raise_error_if_path_does_not_exist, Find, PathNormalization,
+};
+//
+// Public - enums
+//
+/// Broad kinds of failure, each with its own exit code
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorCategory {
+    NotFound,
+    InvalidPath,
+    Io,
+    Encoding,
+}
+
+impl ErrorCategory {
+    /// Returns the process exit code for the category
+    pub fn get_exit_code(&self) -> u8 {
+        match self {
+            ErrorCategory::NotFound => 3,
+            ErrorCategory::InvalidPath => 4,
+            ErrorCategory::Io => 5,
+            ErrorCategory::Encoding => 6,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum WalkType {
+    /// Regular files
+    F,
+    /// Directories
+    D,
+    /// Symlinks
+    L,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum WalkEncoding {
+    /// Fail on paths that aren't valid UTF-8
+    Strict,
+    /// Replace invalid bytes with U+FFFD
+    Lossy,
+    /// Replace invalid bytes with '\xNN' escapes
+    #[cfg(unix)]
+    Escaped,
+}
+//
+// Public - structs
+//
+/// A failure, with the message printed to stderr and the category deciding the exit code
+#[derive(Clone, Debug)]
+pub struct CliError {
+    pub error_category: ErrorCategory,
+    pub string_message: String,
+}
+
+#[derive(Debug, Args)]
+pub struct WalkArgs {
+    /// Directory to walk
+    pub root: String,
+    /// Only return entries whose name matches this glob ( repeatable, all must match, like find(1) )
+    #[arg(long = "name")]
+    pub names: Vec<String>,
+    /// Like --name, ignoring case
+    #[arg(long = "iname")]
+    pub inames: Vec<String>,
+    /// Only return entries of this type
+    #[arg(long = "type", value_enum)]
+    pub walk_type: Option<WalkType>,
+    /// Don't descend below this depth ( the root's children are depth 1 )
+    #[arg(long)]
+    pub max_depth: Option<usize>,
+    /// Don't return entries above this depth
+    #[arg(long)]
+    pub min_depth: Option<usize>,
+    /// Only return files larger than this many bytes
+    #[arg(long)]
+    pub size_gt: Option<u64>,
+    /// Only return files smaller than this many bytes
+    #[arg(long)]
+    pub size_lt: Option<u64>,
+    /// Only return entries modified less than this many seconds ago
+    #[arg(long)]
+    pub newer_than: Option<u64>,
+    /// Only return entries modified more than this many seconds ago
+    #[arg(long)]
+    pub older_than: Option<u64>,
+    /// Descend into symlinked directories
+    #[arg(long)]
+    pub follow_symlinks: bool,
+    /// How to print paths that aren't valid UTF-8
+    #[arg(long, value_enum, default_value = "strict")]
+    pub encoding: WalkEncoding,
+}
+//
+// Public - run
+//
+pub fn run_abs(arg_vec_of_paths: &[String], arg_bool_must_exist: bool) -> Result<Output, CliError> {
+    let mut vec_to_return = vec![];
+    for item_string_path in arg_vec_of_paths {
+        if !arg_bool_must_exist {
+            vec_to_return.push(get_absolute_path(item_string_path));
+            continue;
+        }
+        raise_error_if_missing(item_string_path)?;
+        vec_to_return.push(
+            get_absolute_path_or_error(item_string_path)
+                .map_err(|err| get_cli_error(ErrorCategory::Io, err))?,
+        );
+    }
+    Ok(Output::Paths(vec_to_return))
+}
+
+pub fn run_rel(
+    arg_vec_of_paths: &[String],
+    arg_string_path_from: &str,
+    arg_bool_canonical: bool,
+) -> Result<Output, CliError> {
+    let path_normalization = if arg_bool_canonical {
+        raise_error_if_missing(arg_string_path_from)?;
+        PathNormalization::Canonical
+    } else {
+        PathNormalization::Lexical
+    };
+    let mut vec_to_return = vec![];
+    for item_string_path in arg_vec_of_paths {
+        if arg_bool_canonical {
+            raise_error_if_missing(item_string_path)?;
+        }
+        vec_to_return.push(
+            get_relative_path_between(&arg_string_path_from, item_string_path, path_normalization)
+                .map_err(|err| get_cli_error(ErrorCategory::InvalidPath, err))?,
+        );
+    }
+    Ok(Output::Paths(vec_to_return))
+}
+
+pub fn run_common(arg_vec_of_paths: &[String]) -> Result<Output, CliError> {
+    match get_common_prefix(arg_vec_of_paths) {
+        Ok(string_result) => Ok(Output::Path(string_result)),
+        Err(err) => Err(get_cli_error(ErrorCategory::InvalidPath, err)),
+    }
+}
+
+pub fn run_normalize(arg_vec_of_paths: &[String]) -> Result<Output, CliError> {
+    let mut vec_to_return = vec![];
+    for item_string_path in arg_vec_of_paths {
+        match get_path_normalized(item_string_path) {
+            Some(string_result) => vec_to_return.push(string_result),
+            None => {
+                return Err(get_cli_error(
+                    ErrorCategory::Encoding,
+                    [
+                        "Error: failed to normalize path.".to_string(),
+                        format!("path = {}", item_string_path,),
+                    ]
+                    .join("\n"),
+                ))
+            }
+        }
+    }
+    Ok(Output::Paths(vec_to_return))
+}
+
+pub fn run_walk(arg_walk_args: &WalkArgs) -> Result<Output, CliError> {
+    raise_error_if_missing(&arg_walk_args.root)?;
+    let mut find_query = find(&arg_walk_args.root);
+    for item_string_glob in &arg_walk_args.names {
+        find_query = find_query.name(item_string_glob);
+    }
+    for item_string_glob in &arg_walk_args.inames {
+        find_query = find_query.iname(item_string_glob);
+    }
+    find_query = match arg_walk_args.walk_type {
+        Some(WalkType::F) => find_query.type_file(),
+        Some(WalkType::D) => find_query.type_dir(),
+        Some(WalkType::L) => find_query.type_symlink(),
+        None => find_query,
+    };
+    if let Some(int_depth) = arg_walk_args.max_depth {
+        find_query = find_query.max_depth(int_depth);
+    }
+    if let Some(int_depth) = arg_walk_args.min_depth {
+        find_query = find_query.min_depth(int_depth);
+    }
+    if let Some(int_bytes) = arg_walk_args.size_gt {
+        find_query = find_query.size_gt(int_bytes);
+    }
+    if let Some(int_bytes) = arg_walk_args.size_lt {
+        find_query = find_query.size_lt(int_bytes);
+    }
+    if let Some(int_seconds) = arg_walk_args.newer_than {
+        find_query = find_query.newer_than(Duration::from_secs(int_seconds));
+    }
+    if let Some(int_seconds) = arg_walk_args.older_than {
+        find_query = find_query.older_than(Duration::from_secs(int_seconds));
+    }
+    if arg_walk_args.follow_symlinks {
+        find_query = find_query.follow_symlinks();
+    }
+    Ok(Output::Paths(get_vec_of_paths_from_walk(
+        find_query,
+        arg_walk_args.encoding,
+    )?))
+}
+
+pub fn run_find_root(
+    arg_string_path_start: &str,
+    arg_vec_of_markers: &[String],
+) -> Result<Output, CliError> {
+    raise_error_if_missing(arg_string_path_start)?;
+    let string_path_start = get_absolute_path(&arg_string_path_start);
+    for item_path_ancestor in Path::new(&string_path_start).ancestors() {
+        if arg_vec_of_markers
+            .iter()
+            .any(|item_string_marker| item_path_ancestor.join(item_string_marker).exists())
+        {
+            return Ok(Output::Path(
+                item_path_ancestor.to_string_lossy().into_owned(),
+            ));
+        }
+    }
+    Err(get_cli_error(
+        ErrorCategory::NotFound,
+        [
+            "Error: no ancestor contains any of the markers.".to_string(),
+            format!("start = {}", string_path_start,),
+            format!("markers = {:?}", arg_vec_of_markers,),
+        ]
+        .join("\n"),
+    ))
+}
+
+pub fn run_diff(
+    arg_string_path_left: &str,
+    arg_string_path_right: &str,
+) -> Result<Output, CliError> {
+    let map_left = get_map_of_relative_paths_to_kinds(arg_string_path_left)?;
+    let map_right = get_map_of_relative_paths_to_kinds(arg_string_path_right)?;
+    let mut vec_of_only_left = vec![];
+    let mut vec_of_different = vec![];
+    for (item_string_path_relative, item_entry_kind_left) in &map_left {
+        let entry_kind_right = match map_right.get(item_string_path_relative) {
+            Some(entry_kind_result) => entry_kind_result,
+            None => {
+                vec_of_only_left.push(item_string_path_relative.clone());
+                continue;
+            }
+        };
+        let bool_is_same = match (item_entry_kind_left, entry_kind_right) {
+            (EntryKind::Dir, EntryKind::Dir) => true,
+            (EntryKind::File, EntryKind::File) => are_files_equal(
+                &Path::new(arg_string_path_left).join(item_string_path_relative),
+                &Path::new(arg_string_path_right).join(item_string_path_relative),
+            )?,
+            (EntryKind::Symlink(path_buf_left), EntryKind::Symlink(path_buf_right)) => {
+                path_buf_left == path_buf_right
+            }
+            _ => false,
+        };
+        if !bool_is_same {
+            vec_of_different.push(item_string_path_relative.clone());
+        }
+    }
+    let vec_of_only_right = map_right
+        .keys()
+        .filter(|item_string_path_relative| !map_left.contains_key(*item_string_path_relative))
+        .cloned()
+        .collect::<Vec<String>>();
+    Ok(Output::Diff {
+        vec_of_only_left,
+        vec_of_only_right,
+        vec_of_different,
+    })
+}
+
+pub fn run_hash(arg_vec_of_paths: &[String]) -> Result<Output, CliError> {
+    let mut vec_to_return = vec![];
+    for item_string_path in arg_vec_of_paths {
+        for item_string_path_file in get_vec_of_files(item_string_path)? {
+            let string_hash = get_hash_of_file(&item_string_path_file)?;
+            vec_to_return.push((item_string_path_file, string_hash));
+        }
+    }
+    Ok(Output::Hashes(vec_to_return))
+}
+
+/// Files are grouped by size first, so only files sharing a size are hashed
+/// Empty files are skipped
+pub fn run_dupes(arg_vec_of_roots: &[String]) -> Result<Output, CliError> {
+    let mut set_of_paths = BTreeSet::new();
+    for item_string_path_root in arg_vec_of_roots {
+        set_of_paths.extend(get_vec_of_files(item_string_path_root)?);
+    }
+    let mut map_of_sizes_to_paths: BTreeMap<u64, Vec<String>> = BTreeMap::new();
+    for item_string_path in set_of_paths {
+        let int_bytes = get_metadata(&item_string_path)?.len();
+        if int_bytes > 0 {
+            map_of_sizes_to_paths
+                .entry(int_bytes)
+                .or_default()
+                .push(item_string_path);
+        }
+    }
+    let mut vec_to_return = vec![];
+    for item_vec_of_paths in map_of_sizes_to_paths.into_values() {
+        if item_vec_of_paths.len() < 2 {
+            continue;
+        }
+        let mut map_of_hashes_to_paths: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for item_string_path in item_vec_of_paths {
+            map_of_hashes_to_paths
+                .entry(get_hash_of_file(&item_string_path)?)
+                .or_default()
+                .push(item_string_path);
+        }
+        vec_to_return.extend(
+            map_of_hashes_to_paths
+                .into_values()
+                .filter(|item_vec_of_paths| item_vec_of_paths.len() > 1),
+        );
+    }
+    vec_to_return.sort();
+    Ok(Output::Dupes(vec_to_return))
+}
+
+/// Sizes are apparent sizes ( the sum of file lengths ), not blocks allocated on disk
+/// Symlinks are not followed
+pub fn run_du(arg_vec_of_paths: &[String]) -> Result<Output, CliError> {
+    let mut vec_to_return = vec![];
+    for item_string_path in arg_vec_of_paths {
+        let mut int_bytes_total = 0;
+        for item_string_path_file in get_vec_of_files(item_string_path)? {
+            int_bytes_total += get_metadata(&item_string_path_file)?.len();
+        }
+        vec_to_return.push((item_string_path.clone(), int_bytes_total));
+    }
+    Ok(Output::Sizes(vec_to_return))
+}
+//
+// Private
+//
+/// Number of bytes compared at a time by are_files_equal()
+const INT_BYTES_CHUNK: usize = 64 * 1024;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum EntryKind {
+    Dir,
+    File,
+    Symlink(std::path::PathBuf),
+}
+
+fn are_files_equal(arg_path_left: &Path, arg_path_right: &Path) -> Result<bool, CliError> {
+    if get_metadata(arg_path_left)?.len() != get_metadata(arg_path_right)?.len() {
+        return Ok(false);
+    }
+    let mut buf_reader_left = BufReader::new(get_file(arg_path_left)?);
+    let mut buf_reader_right = BufReader::new(get_file(arg_path_right)?);
+    let mut vec_of_bytes_left = vec![0; INT_BYTES_CHUNK];
+    let mut vec_of_bytes_right = vec![0; INT_BYTES_CHUNK];
+    loop {
+        let int_bytes_read =
+            read_chunk(&mut buf_reader_left, &mut vec_of_bytes_left, arg_path_left)?;
+        if read_chunk(
+            &mut buf_reader_right,
+            &mut vec_of_bytes_right,
+            arg_path_right,
+        )? != int_bytes_read
+            || vec_of_bytes_left[..int_bytes_read] != vec_of_bytes_right[..int_bytes_read]
+        {
+            return Ok(false);
+        }
+        if int_bytes_read == 0 {
+            return Ok(true);
+        }
+    }
+}
+
+fn get_cli_error(arg_error_category: ErrorCategory, arg_string_message: String) -> CliError {
+    CliError {
+        error_category: arg_error_category,
+        string_message: arg_string_message,
+    }
+}
+
+fn get_cli_error_from_io<T: AsRef<Path>>(
+    arg_str_summary: &str,
+    arg_err: std::io::Error,
+    arg_path: T,
+) -> CliError {
+    get_cli_error(
+        if arg_err.kind() == std::io::ErrorKind::NotFound {
+            ErrorCategory::NotFound
+        } else {
+            ErrorCategory::Io
+        },
+        [
+            arg_str_summary.to_string(),
+            format!("err = {}", arg_err,),
+            format!("path = {}", arg_path.as_ref().display(),),
+        ]
+        .join("\n"),
+    )
+}
+
+fn get_file<T: AsRef<Path>>(arg_path: T) -> Result<File, CliError> {
+    File::open(arg_path.as_ref())
+        .map_err(|err| get_cli_error_from_io("Error: failed to open file.", err, arg_path))
+}
+
+fn get_hash_of_file(arg_string_path: &str) -> Result<String, CliError> {
+    let mut sha256 = Sha256::new();
+    if let Err(err) = std::io::copy(&mut get_file(arg_string_path)?, &mut sha256) {
+        return Err(get_cli_error_from_io(
+            "Error: failed to read file.",
+            err,
+            arg_string_path,
+        ));
+    }
+    Ok(format!("{:x}", sha256.finalize()))
+}
+
+/// Returns every entry under arg_string_path_root, keyed by its path relative to the root
+fn get_map_of_relative_paths_to_kinds(
+    arg_string_path_root: &str,
+) -> Result<BTreeMap<String, EntryKind>, CliError> {
+    raise_error_if_missing(arg_string_path_root)?;
+    let mut map_to_return = BTreeMap::new();
+    for string_path in
+        get_vec_of_paths_from_walk(find(&arg_string_path_root), WalkEncoding::Strict)?
+    {
+        let file_type = get_metadata(&string_path)?.file_type();
+        let entry_kind = if file_type.is_symlink() {
+            EntryKind::Symlink(std::fs::read_link(&string_path).map_err(|err| {
+                get_cli_error_from_io("Error: failed to read symlink.", err, &string_path)
+            })?)
+        } else if file_type.is_dir() {
+            EntryKind::Dir
+        } else {
+            EntryKind::File
+        };
+        let string_path_relative = get_relative_path(&string_path, &arg_string_path_root)
+            .map_err(|err| get_cli_error(ErrorCategory::InvalidPath, err))?;
+        map_to_return.insert(string_path_relative, entry_kind);
+    }
+    Ok(map_to_return)
+}
+
+/// Returns the metadata of the path itself, without following symlinks
+fn get_metadata<T: AsRef<Path>>(arg_path: T) -> Result<std::fs::Metadata, CliError> {
+    std::fs::symlink_metadata(arg_path.as_ref()).map_err(|err| {
+        get_cli_error_from_io("Error: failed to get meta data from path.", err, arg_path)
+    })
+}
+
+/// Returns every match of arg_find, converted with arg_walk_encoding
+/// The walk yields OsStrings, so a failure there is always I/O, and a failure here is always encoding.
+fn get_vec_of_paths_from_walk(
+    arg_find: Find,
+    arg_walk_encoding: WalkEncoding,
+) -> Result<Vec<String>, CliError> {
+    let mut vec_to_return = vec![];
+    for item_result in arg_find.into_iter_os() {
+        let os_string = item_result.map_err(|err| get_cli_error(ErrorCategory::Io, err))?;
+        vec_to_return.push(get_string_from_os_string(os_string, arg_walk_encoding)?);
+    }
+    Ok(vec_to_return)
+}
+
+fn get_string_from_os_string(
+    arg_os_string: OsString,
+    arg_walk_encoding: WalkEncoding,
+) -> Result<String, CliError> {
+    match arg_walk_encoding {
+        WalkEncoding::Strict => arg_os_string.into_string().map_err(|os_string| {
+            get_cli_error(
+                ErrorCategory::Encoding,
+                [
+                    "Error: path is not valid UTF-8.".to_string(),
+                    format!("path = {:?}", os_string,),
+                ]
+                .join("\n"),
+            )
+        }),
+        WalkEncoding::Lossy => Ok(get_string_from_type_osstr_lossy(&arg_os_string).0),
+        #[cfg(unix)]
+        WalkEncoding::Escaped => Ok(easy_paths::get_string_from_type_osstr_escaped(
+            &arg_os_string,
+        )),
+    }
+}
+
+/// Returns arg_string_path if it's a file, and otherwise the sorted files beneath it
+fn get_vec_of_files(arg_string_path: &str) -> Result<Vec<String>, CliError> {
+    raise_error_if_missing(arg_string_path)?;
+    if get_metadata(arg_string_path)?.is_file() {
+        return Ok(vec![arg_string_path.to_string()]);
+    }
+    let mut vec_to_return =
+        get_vec_of_paths_from_walk(find(&arg_string_path).type_file(), WalkEncoding::Strict)?;
+    vec_to_return.sort();
+    Ok(vec_to_return)
+}
+
+fn raise_error_if_missing(arg_string_path: &str) -> Result<(), CliError> {
+    raise_error_if_path_does_not_exist(&arg_string_path)
+        .map_err(|err| get_cli_error(ErrorCategory::NotFound, err))
+}
+
+/// Fills as much of arg_vec_of_bytes as the file allows, so chunks from two readers line up
+fn read_chunk<R: Read>(
+    arg_reader: &mut R,
+    arg_vec_of_bytes: &mut [u8],
+    arg_path: &Path,
+) -> Result<usize, CliError> {
+    let mut int_bytes_read = 0;
+    while int_bytes_read < arg_vec_of_bytes.len() {
+        match arg_reader.read(&mut arg_vec_of_bytes[int_bytes_read..]) {
+            Ok(0) => break,
+            Ok(int_result) => int_bytes_read += int_result,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => {
+                return Err(get_cli_error_from_io(
+                    "Error: failed to read file.",
+                    err,
+                    arg_path,
+                ))
+            }
+        }
+    }
+    Ok(int_bytes_read)
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::get_dir_temp_for_tests;
+
+    fn write_files(arg_string_path_root: &str, arg_slice_of_files: &[(&str, &str)]) {
+        for (item_str_path, item_str_contents) in arg_slice_of_files {
+            let path_buf = Path::new(arg_string_path_root).join(item_str_path);
+            std::fs::create_dir_all(path_buf.parent().unwrap()).unwrap();
+            std::fs::write(path_buf, item_str_contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_run_diff() {
+        let string_path_dir = get_dir_temp_for_tests("run_diff");
+        write_files(
+            &format!("{}/left", string_path_dir,),
+            &[
+                ("same.txt", "a"),
+                ("changed.txt", "b"),
+                ("sub/only_left.txt", ""),
+            ],
+        );
+        write_files(
+            &format!("{}/right", string_path_dir,),
+            &[
+                ("same.txt", "a"),
+                ("changed.txt", "c"),
+                ("only_right.txt", ""),
+            ],
+        );
+        let output = match run_diff(
+            &format!("{}/left", string_path_dir,),
+            &format!("{}/right", string_path_dir,),
+        ) {
+            Ok(output_result) => output_result,
+            Err(err) => panic!("{}", err.string_message,),
+        };
+        assert_eq!(
+            output,
+            Output::Diff {
+                vec_of_only_left: vec!["sub".to_string(), "sub/only_left.txt".to_string()],
+                vec_of_only_right: vec!["only_right.txt".to_string()],
+                vec_of_different: vec!["changed.txt".to_string()],
+            }
+        );
+        assert_eq!(output.get_exit_code(), 1);
+        let err = run_diff(&format!("{}/missing", string_path_dir,), &string_path_dir).unwrap_err();
+        assert_eq!(err.error_category, ErrorCategory::NotFound);
+    }
+
+    #[test]
+    fn test_run_dupes_and_du() {
+        let string_path_dir = get_dir_temp_for_tests("run_dupes_and_du");
+        write_files(
+            &string_path_dir,
+            &[
+                ("a.txt", "same"),
+                ("sub/b.txt", "same"),
+                ("c.txt", "diff"),
+                ("empty_1.txt", ""),
+                ("empty_2.txt", ""),
+            ],
+        );
+        assert_eq!(
+            run_dupes(std::slice::from_ref(&string_path_dir)).unwrap(),
+            Output::Dupes(vec![vec![
+                format!("{}/a.txt", string_path_dir,),
+                format!("{}/sub/b.txt", string_path_dir,),
+            ]])
+        );
+        assert_eq!(
+            run_du(std::slice::from_ref(&string_path_dir)).unwrap(),
+            Output::Sizes(vec![(string_path_dir.clone(), 12)])
+        );
+        assert_eq!(
+            run_hash(&[format!("{}/a.txt", string_path_dir,)]).unwrap(),
+            Output::Hashes(vec![(
+                format!("{}/a.txt", string_path_dir,),
+                "0967115f2813a3541eaef77de9d9d5773f1c0c04314b0bbfe4ff3b3b1c55b5d5".to_string(),
+            )])
+        );
+    }
+
+    #[test]
+    fn test_run_find_root() {
+        let string_path_dir = get_dir_temp_for_tests("run_find_root");
+        write_files(
+            &string_path_dir,
+            &[("marker.toml", ""), ("A/B/file.txt", "")],
+        );
+        assert_eq!(
+            run_find_root(
+                &format!("{}/A/B", string_path_dir,),
+                &["marker.toml".to_string()]
+            )
+            .unwrap(),
+            Output::Path(get_absolute_path(&string_path_dir))
+        );
+        let err =
+            run_find_root(&string_path_dir, &["not_a_marker_anywhere".to_string()]).unwrap_err();
+        assert_eq!(err.error_category, ErrorCategory::NotFound);
+    }
+
+    #[test]
+    fn test_run_walk() {
+        #[derive(clap::Parser)]
+        struct WalkCli {
+            #[command(flatten)]
+            walk_args: WalkArgs,
+        }
+        let string_path_dir = get_dir_temp_for_tests("run_walk");
+        write_files(
+            &string_path_dir,
+            &[
+                ("Cargo.toml", ""),
+                ("README.md", ""),
+                ("sub/Cargo.lock", ""),
+            ],
+        );
+        let get_output = |arg_slice_of_args: &[&str]| {
+            let walk_cli = <WalkCli as clap::Parser>::try_parse_from(
+                ["walk", string_path_dir.as_str()]
+                    .iter()
+                    .chain(arg_slice_of_args),
+            )
+            .unwrap();
+            run_walk(&walk_cli.walk_args).unwrap()
+        };
+        // Every --name must match, like find(1)
+        assert_eq!(
+            get_output(&[
+                "--max-depth",
+                "1",
+                "--name",
+                "Cargo.toml",
+                "--name",
+                "README.md"
+            ]),
+            Output::Paths(vec![])
+        );
+        assert_eq!(
+            get_output(&["--name", "Cargo.*", "--name", "*.toml"]),
+            Output::Paths(vec![format!("{}/Cargo.toml", string_path_dir,)])
+        );
+        let err = run_walk(
+            &<WalkCli as clap::Parser>::try_parse_from(["walk", "does/not/exist"])
+                .unwrap()
+                .walk_args,
+        )
+        .unwrap_err();
+        assert_eq!(err.error_category, ErrorCategory::NotFound);
+    }
+
+    #[test]
+    fn test_run_rel_and_abs() {
+        assert_eq!(
+            run_rel(&["/A/B/C".to_string()], "/A/X", false).unwrap(),
+            Output::Paths(vec!["../B/C".to_string()])
+        );
+        let err = run_abs(&["does/not/exist".to_string()], true).unwrap_err();
+        assert_eq!(err.error_category.get_exit_code(), 3);
+    }
+}
@@ -0,0 +1,159 @@
+//! easy-paths: the easy_paths library as a command-line tool
+//!
+//! Every subcommand can print newline-separated ( default ), NUL-separated ( --null ) or JSON ( --json )
+//! output, so shell scripts and CI jobs get the same path semantics as Rust code using the library.
+//!
+//! Exit codes:
+//! * 0: success
+//! * 1: diff ran and found differences
+//! * 2: bad command-line arguments
+//! * 3: a path doesn't exist
+//! * 4: a path is invalid for the operation, ie no relative path exists between two drives
+//! * 5: an I/O error occurred while reading the disk
+//! * 6: a path isn't valid UTF-8
+//
+// Libraries - native
+//
+use std::process::ExitCode;
+//
+// Libraries - downloaded
+//
+use clap::{Args, Parser, Subcommand};
+//
+// Libraries - local
+//
+mod commands;
+mod output;
+#[cfg(test)]
+#[path = "../../test_utils.rs"]
+mod test_utils;
+
+use output::OutputFormat;
+//
+// Structs
+//
+#[derive(Debug, Parser)]
+#[command(
+    name = "easy-paths",
+    version,
+    about = "Path utilities from the easy_paths crate"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    output_args: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+struct OutputArgs {
+    /// Separate results with NUL instead of newline
+    #[arg(
+        short = '0',
+        long = "null",
+        global = true,
+        conflicts_with = "bool_json"
+    )]
+    bool_null: bool,
+    /// Print results as JSON
+    #[arg(long = "json", global = true)]
+    bool_json: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the absolute form of each path
+    Abs {
+        paths: Vec<String>,
+        /// Fail if a path doesn't exist, instead of resolving it lexically
+        #[arg(long)]
+        must_exist: bool,
+    },
+    /// Print each path relative to a directory, climbing with '..' where needed
+    Rel {
+        paths: Vec<String>,
+        /// Directory the results are relative to
+        #[arg(long, default_value = ".")]
+        from: String,
+        /// Resolve symlinks first ( every path must exist )
+        #[arg(long)]
+        canonical: bool,
+    },
+    /// Print the longest path shared by all arguments
+    Common {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Print each path with '.' and '..' resolved lexically
+    Normalize { paths: Vec<String> },
+    /// Print the paths under a directory, filtered like find(1)
+    Walk(commands::WalkArgs),
+    /// Print the nearest ancestor directory holding one of the marker names
+    FindRoot {
+        /// Directory to start from
+        #[arg(long, default_value = ".")]
+        start: String,
+        /// File or directory names that mark a root
+        #[arg(long = "marker", default_values_t = [".git".to_string()])]
+        markers: Vec<String>,
+    },
+    /// Compare two directory trees by relative path and file contents
+    Diff { left: String, right: String },
+    /// Print the SHA-256 of each file, recursing into directories
+    Hash {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+    /// Print groups of files with identical contents
+    Dupes {
+        #[arg(required = true)]
+        roots: Vec<String>,
+    },
+    /// Print the total size in bytes of each path, recursing into directories
+    Du {
+        #[arg(required = true)]
+        paths: Vec<String>,
+    },
+}
+//
+// Main
+//
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let output_format = if cli.output_args.bool_json {
+        OutputFormat::Json
+    } else if cli.output_args.bool_null {
+        OutputFormat::Nul
+    } else {
+        OutputFormat::Lines
+    };
+    let result = match cli.command {
+        Command::Abs { paths, must_exist } => commands::run_abs(&paths, must_exist),
+        Command::Rel {
+            paths,
+            from,
+            canonical,
+        } => commands::run_rel(&paths, &from, canonical),
+        Command::Common { paths } => commands::run_common(&paths),
+        Command::Normalize { paths } => commands::run_normalize(&paths),
+        Command::Walk(walk_args) => commands::run_walk(&walk_args),
+        Command::FindRoot { start, markers } => commands::run_find_root(&start, &markers),
+        Command::Diff { left, right } => commands::run_diff(&left, &right),
+        Command::Hash { paths } => commands::run_hash(&paths),
+        Command::Dupes { roots } => commands::run_dupes(&roots),
+        Command::Du { paths } => commands::run_du(&paths),
+    };
+    match result {
+        Ok(output) => {
+            if let Err(err) = output.print(output_format) {
+                eprintln!("{}", err,);
+                return ExitCode::from(commands::ErrorCategory::Io.get_exit_code());
+            }
+            ExitCode::from(output.get_exit_code())
+        }
+        Err(cli_error) => {
+            eprintln!("{}", cli_error.string_message,);
+            ExitCode::from(cli_error.error_category.get_exit_code())
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//
+// Libraries - native
+//
+use std::io::{BufWriter, Write};
+//
+// Libraries - downloaded
+//
+use serde_json::{json, Value};
+//
+// Public - enums
+//
+/// How results are separated on stdout
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Lines,
+    Nul,
+    Json,
+}
+
+/// The result of a subcommand, before it's formatted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Output {
+    /// A single path, ie from common or find-root
+    Path(String),
+    /// One path per input, or per match
+    Paths(Vec<String>),
+    /// Relative paths that only exist on one side, or whose contents differ
+    Diff {
+        vec_of_only_left: Vec<String>,
+        vec_of_only_right: Vec<String>,
+        vec_of_different: Vec<String>,
+    },
+    /// ( path, hex SHA-256 ) pairs
+    Hashes(Vec<(String, String)>),
+    /// Groups of paths with identical contents
+    Dupes(Vec<Vec<String>>),
+    /// ( path, size in bytes ) pairs
+    Sizes(Vec<(String, u64)>),
+}
+
+impl Output {
+    /// Returns 1 if diff found differences, and 0 otherwise
+    pub fn get_exit_code(&self) -> u8 {
+        match self {
+            Output::Diff {
+                vec_of_only_left,
+                vec_of_only_right,
+                vec_of_different,
+            } => {
+                if vec_of_only_left.is_empty()
+                    && vec_of_only_right.is_empty()
+                    && vec_of_different.is_empty()
+                {
+                    0
+                } else {
+                    1
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    /// Writes the output to stdout
+    /// # Arguments
+    /// * arg_output_format: OutputFormat
+    pub fn print(&self, arg_output_format: OutputFormat) -> Result<(), String> {
+        let stdout = std::io::stdout();
+        let mut buf_writer = BufWriter::new(stdout.lock());
+        match self
+            .write(&mut buf_writer, arg_output_format)
+            .and_then(|_| buf_writer.flush())
+        {
+            Ok(()) => Ok(()),
+            Err(err) => Err([
+                "Error: failed to write to stdout.".to_string(),
+                format!("err = {}", err,),
+            ]
+            .join("\n")),
+        }
+    }
+
+    /// Writes the output in arg_output_format
+    /// Lines and Nul put each record on its own line, or after its own NUL. Records are:
+    /// * Path / Paths: the path
+    /// * Diff: '-', '+' or 'M', a tab, then the relative path
+    /// * Hashes: the hash, two spaces, then the path ( the sha256sum format )
+    /// * Dupes: each path, with an empty record between groups
+    /// * Sizes: the size, a tab, then the path ( the du format )
+    pub fn write<W: Write>(
+        &self,
+        arg_writer: &mut W,
+        arg_output_format: OutputFormat,
+    ) -> std::io::Result<()> {
+        if arg_output_format == OutputFormat::Json {
+            serde_json::to_writer(&mut *arg_writer, &self.get_json_value())?;
+            return writeln!(arg_writer);
+        }
+        let str_terminator = if arg_output_format == OutputFormat::Nul {
+            "\0"
+        } else {
+            "\n"
+        };
+        for item_string_record in self.get_vec_of_records() {
+            write!(arg_writer, "{}{}", item_string_record, str_terminator,)?;
+        }
+        Ok(())
+    }
+}
+//
+// Private
+//
+impl Output {
+    fn get_json_value(&self) -> Value {
+        match self {
+            Output::Path(string_path) => json!(string_path),
+            Output::Paths(vec_of_paths) => json!(vec_of_paths),
+            Output::Diff {
+                vec_of_only_left,
+                vec_of_only_right,
+                vec_of_different,
+            } => json!({
+                "only_left": vec_of_only_left,
+                "only_right": vec_of_only_right,
+                "different": vec_of_different,
+            }),
+            Output::Hashes(vec_of_hashes) => Value::Array(
+                vec_of_hashes
+                    .iter()
+                    .map(|(item_string_path, item_string_hash)| {
+                        json!({ "path": item_string_path, "sha256": item_string_hash })
+                    })
+                    .collect(),
+            ),
+            Output::Dupes(vec_of_groups) => json!(vec_of_groups),
+            Output::Sizes(vec_of_sizes) => Value::Array(
+                vec_of_sizes
+                    .iter()
+                    .map(|(item_string_path, item_int_bytes)| {
+                        json!({ "path": item_string_path, "bytes": item_int_bytes })
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn get_vec_of_records(&self) -> Vec<String> {
+        match self {
+            Output::Path(string_path) => vec![string_path.clone()],
+            Output::Paths(vec_of_paths) => vec_of_paths.clone(),
+            Output::Diff {
+                vec_of_only_left,
+                vec_of_only_right,
+                vec_of_different,
+            } => [
+                ("-", vec_of_only_left),
+                ("+", vec_of_only_right),
+                ("M", vec_of_different),
+            ]
+            .iter()
+            .flat_map(|(item_str_marker, item_vec_of_paths)| {
+                item_vec_of_paths.iter().map(move |item_string_path| {
+                    format!("{}\t{}", item_str_marker, item_string_path,)
+                })
+            })
+            .collect(),
+            Output::Hashes(vec_of_hashes) => vec_of_hashes
+                .iter()
+                .map(|(item_string_path, item_string_hash)| {
+                    format!("{}  {}", item_string_hash, item_string_path,)
+                })
+                .collect(),
+            Output::Dupes(vec_of_groups) => {
+                let mut vec_to_return = vec![];
+                for (item_int_index, item_vec_of_paths) in vec_of_groups.iter().enumerate() {
+                    if item_int_index > 0 {
+                        vec_to_return.push(String::new());
+                    }
+                    vec_to_return.extend(item_vec_of_paths.iter().cloned());
+                }
+                vec_to_return
+            }
+            Output::Sizes(vec_of_sizes) => vec_of_sizes
+                .iter()
+                .map(|(item_string_path, item_int_bytes)| {
+                    format!("{}\t{}", item_int_bytes, item_string_path,)
+                })
+                .collect(),
+        }
+    }
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_string_written(arg_output: &Output, arg_output_format: OutputFormat) -> String {
+        let mut vec_of_bytes = vec![];
+        arg_output
+            .write(&mut vec_of_bytes, arg_output_format)
+            .unwrap();
+        String::from_utf8(vec_of_bytes).unwrap()
+    }
+
+    #[test]
+    fn test_write() {
+        let output = Output::Paths(vec!["a".to_string(), "b c".to_string()]);
+        assert_eq!(get_string_written(&output, OutputFormat::Lines), "a\nb c\n");
+        assert_eq!(get_string_written(&output, OutputFormat::Nul), "a\0b c\0");
+        assert_eq!(
+            get_string_written(&output, OutputFormat::Json),
+            "[\"a\",\"b c\"]\n"
+        );
+        let output = Output::Dupes(vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["c".to_string(), "d".to_string()],
+        ]);
+        assert_eq!(
+            get_string_written(&output, OutputFormat::Lines),
+            "a\nb\n\nc\nd\n"
+        );
+        let output = Output::Diff {
+            vec_of_only_left: vec!["x".to_string()],
+            vec_of_only_right: vec![],
+            vec_of_different: vec!["y".to_string()],
+        };
+        assert_eq!(
+            get_string_written(&output, OutputFormat::Lines),
+            "-\tx\nM\ty\n"
+        );
+        assert_eq!(output.get_exit_code(), 1);
+    }
+}
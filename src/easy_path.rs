@@ -0,0 +1,610 @@
+//
+// Libraries - native
+//
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+//
+// Libraries - local
+//
+use crate::{
+    are_paths_the_same_assume_cwd, get_absolute_path, get_absolute_path_or_error, get_common_path,
+    get_dir_ancestor_that_exists, get_path_normalized, get_paths_in_dir,
+    get_paths_in_dir_and_sub_dirs, get_paths_to_only_dirs_in_dir_and_sub_dirs,
+    get_paths_to_only_files_in_dir_and_sub_dirs, get_relative_path, get_relative_path_between,
+    has_parent, raise_error_if_path_does_not_exist, raise_error_if_path_is_not_in_project,
+    PathComparison, PathNormalization,
+};
+//
+// Public - structs
+//
+/// An owned string path with the crate's operations available as chainable methods
+/// It derefs to str, so it can be passed anywhere a string-like path is expected.
+/// Left out on purpose: the _with_fs variants, functions over slices of paths ( ie
+/// get_only_dirs_from_slice(), get_paths_sorted_by_size_starting_with_shortest() ), the
+/// raise_error_if_path_points_to_*() project guards, and functions that take no path ( ie get_dir_cwd() ).
+/// # Examples
+/// let easy_path = EasyPath::from( "~/A/B/C" ).absolute().parent_n( 2 ).unwrap().join( "D" );
+/// println!( "{}", easy_path, );
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EasyPath {
+    string_path: String,
+}
+
+/// A borrowed string path with the same methods as EasyPath
+/// Methods that only look at part of the path ( base_name(), parent_n(), ... ) return borrows
+/// instead of allocating.
+/// # Examples
+/// let easy_path_ref = EasyPathRef::from( "/A/B/file.txt" );
+/// assert_eq!( easy_path_ref.base_name(), Some( "file.txt" ) );
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EasyPathRef<'a> {
+    str_path: &'a str,
+}
+
+impl<'a> EasyPathRef<'a> {
+    /// Returns the path as a str
+    pub fn as_str(&self) -> &'a str {
+        self.str_path
+    }
+
+    /// Returns the path as a std Path
+    pub fn as_path(&self) -> &'a Path {
+        Path::new(self.str_path)
+    }
+
+    /// Returns an owned copy of the path
+    pub fn to_easy_path(&self) -> EasyPath {
+        EasyPath::from(self.str_path)
+    }
+
+    /// Returns the absolute path, see get_absolute_path()
+    pub fn absolute(&self) -> EasyPath {
+        EasyPath::from(get_absolute_path(&self.str_path))
+    }
+
+    /// Returns the absolute path, or an error if it can't be canonicalized, see get_absolute_path_or_error()
+    pub fn absolute_or_error(&self) -> Result<EasyPath, String> {
+        Ok(EasyPath::from(get_absolute_path_or_error(&self.str_path)?))
+    }
+
+    /// Returns the closest ancestor that exists, or the path itself, see get_dir_ancestor_that_exists()
+    pub fn ancestor_that_exists(&self) -> Option<EasyPath> {
+        get_dir_ancestor_that_exists(&self.str_path).map(EasyPath::from)
+    }
+
+    /// Returns the last component of the path, see get_base_name()
+    pub fn base_name(&self) -> Option<&'a str> {
+        self.as_path().file_name()?.to_str()
+    }
+
+    /// Returns the deepest existing directory both paths share, see get_common_path()
+    /// # Arguments
+    /// * arg_string_path: string-like path to compare with
+    pub fn common_path_with<T: Display + ?Sized>(
+        &self,
+        arg_string_path: &T,
+    ) -> Result<EasyPath, String> {
+        Ok(EasyPath::from(get_common_path(&[
+            self.str_path.to_string(),
+            format!("{}", arg_string_path,),
+        ])?))
+    }
+
+    /// Returns the parent of the path, see get_dir_name()
+    pub fn dir_name(&self) -> Option<EasyPathRef<'a>> {
+        self.parent_n(1)
+    }
+
+    /// Returns the directories inside the path and all sub directories,
+    /// see get_paths_to_only_dirs_in_dir_and_sub_dirs()
+    pub fn dirs_in_dir_and_sub_dirs(&self) -> Result<Vec<EasyPath>, String> {
+        get_vec_of_easy_paths(get_paths_to_only_dirs_in_dir_and_sub_dirs(&self.str_path))
+    }
+
+    /// Returns the extension without the period, see get_extension()
+    pub fn extension(&self) -> Option<&'a str> {
+        self.as_path().extension()?.to_str()
+    }
+
+    /// Returns the files inside the path and all sub directories,
+    /// see get_paths_to_only_files_in_dir_and_sub_dirs()
+    pub fn files_in_dir_and_sub_dirs(&self) -> Result<Vec<EasyPath>, String> {
+        get_vec_of_easy_paths(get_paths_to_only_files_in_dir_and_sub_dirs(&self.str_path))
+    }
+
+    /// Returns true if the path has a parent, see has_parent()
+    pub fn has_parent(&self) -> bool {
+        has_parent(&self.str_path)
+    }
+
+    /// Returns true if the path is absolute
+    pub fn is_absolute(&self) -> bool {
+        self.as_path().is_absolute()
+    }
+
+    /// Returns true if something exists at the path
+    pub fn exists(&self) -> bool {
+        self.as_path().exists()
+    }
+
+    /// Returns true if the path points at a directory
+    pub fn is_dir(&self) -> bool {
+        self.as_path().is_dir()
+    }
+
+    /// Returns true if the path points at a file
+    pub fn is_file(&self) -> bool {
+        self.as_path().is_file()
+    }
+
+    /// Returns true if both paths point to the same place, relative paths being taken from the cwd,
+    /// see are_paths_the_same_assume_cwd()
+    /// # Arguments
+    /// * arg_string_path: string-like path to compare with
+    /// * arg_path_comparison: PathComparison deciding what counts as the same path
+    pub fn is_same_as<T: Display + ?Sized>(
+        &self,
+        arg_string_path: &T,
+        arg_path_comparison: PathComparison,
+    ) -> Result<bool, String> {
+        are_paths_the_same_assume_cwd(
+            &self.str_path,
+            &format!("{}", arg_string_path,),
+            arg_path_comparison,
+        )
+    }
+
+    /// Returns the path with arg_string_path appended
+    /// As with Path::join(), an absolute argument replaces the path entirely.
+    /// # Arguments
+    /// * arg_string_path: string-like path to append
+    pub fn join<T: Display + ?Sized>(&self, arg_string_path: &T) -> EasyPath {
+        EasyPath::from(
+            self.as_path()
+                .join(format!("{}", arg_string_path,))
+                // Both halves are str, so the result is always valid UTF-8
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// Returns the path with '.' and '..' resolved lexically, see get_path_normalized()
+    pub fn normalized(&self) -> EasyPath {
+        match get_path_normalized(&self.str_path) {
+            Some(string_result) => EasyPath::from(string_result),
+            None => self.to_easy_path(),
+        }
+    }
+
+    /// Returns the ancestor arg_int_levels_up levels up, see get_dir_ancestor_n_levels_up()
+    /// # Arguments
+    /// * arg_int_levels_up: number of parents to walk up
+    pub fn parent_n(&self, arg_int_levels_up: usize) -> Option<EasyPathRef<'a>> {
+        let mut path = self.as_path();
+        for _ in 0..arg_int_levels_up {
+            path = path.parent()?;
+        }
+        Some(EasyPathRef::from(path.to_str()?))
+    }
+
+    /// Returns the paths inside the directory, see get_paths_in_dir()
+    pub fn paths_in_dir(&self) -> Result<Vec<EasyPath>, String> {
+        get_vec_of_easy_paths(get_paths_in_dir(&self.str_path))
+    }
+
+    /// Returns the paths inside the directory and all sub directories, see get_paths_in_dir_and_sub_dirs()
+    pub fn paths_in_dir_and_sub_dirs(&self) -> Result<Vec<EasyPath>, String> {
+        get_vec_of_easy_paths(get_paths_in_dir_and_sub_dirs(&self.str_path))
+    }
+
+    /// Returns an error if nothing exists at the path, see raise_error_if_path_does_not_exist()
+    pub fn raise_error_if_does_not_exist(&self) -> Result<(), String> {
+        raise_error_if_path_does_not_exist(&self.str_path)
+    }
+
+    /// Returns an error if the path is outside the project, see raise_error_if_path_is_not_in_project()
+    pub fn raise_error_if_not_in_project(&self) -> Result<(), String> {
+        raise_error_if_path_is_not_in_project(&self.str_path)
+    }
+
+    /// Returns the path relative to the directory arg_string_path_dir_from, climbing with '..' as needed,
+    /// see get_relative_path_between()
+    /// # Arguments
+    /// * arg_string_path_dir_from: string-like path to the starting directory
+    /// * arg_path_normalization: PathNormalization
+    pub fn relative_from<T: Display + ?Sized>(
+        &self,
+        arg_string_path_dir_from: &T,
+        arg_path_normalization: PathNormalization,
+    ) -> Result<EasyPath, String> {
+        Ok(EasyPath::from(get_relative_path_between(
+            &format!("{}", arg_string_path_dir_from,),
+            &self.str_path,
+            arg_path_normalization,
+        )?))
+    }
+
+    /// Returns the path with arg_string_path_root_prefix removed from the front, see get_relative_path()
+    /// # Arguments
+    /// * arg_string_path_root_prefix: string-like path that the path starts with
+    pub fn relative_to<T: Display + ?Sized>(
+        &self,
+        arg_string_path_root_prefix: &T,
+    ) -> Result<EasyPath, String> {
+        Ok(EasyPath::from(get_relative_path(
+            &self.str_path,
+            &format!("{}", arg_string_path_root_prefix,),
+        )?))
+    }
+}
+
+impl EasyPath {
+    /// Returns an EasyPath from anything string-like
+    /// # Arguments
+    /// * arg_string_path: string-like
+    pub fn new<T: Display + ?Sized>(arg_string_path: &T) -> Self {
+        EasyPath {
+            string_path: format!("{}", arg_string_path,),
+        }
+    }
+
+    /// Returns a borrowed view of the path
+    pub fn as_easy_path_ref(&self) -> EasyPathRef<'_> {
+        EasyPathRef::from(self.string_path.as_str())
+    }
+
+    /// Returns the path as a str
+    pub fn as_str(&self) -> &str {
+        &self.string_path
+    }
+
+    /// Returns the path as a std Path
+    pub fn as_path(&self) -> &Path {
+        Path::new(&self.string_path)
+    }
+
+    /// Returns the inner String
+    pub fn into_string(self) -> String {
+        self.string_path
+    }
+
+    /// See EasyPathRef::absolute()
+    pub fn absolute(&self) -> EasyPath {
+        self.as_easy_path_ref().absolute()
+    }
+
+    /// See EasyPathRef::absolute_or_error()
+    pub fn absolute_or_error(&self) -> Result<EasyPath, String> {
+        self.as_easy_path_ref().absolute_or_error()
+    }
+
+    /// See EasyPathRef::ancestor_that_exists()
+    pub fn ancestor_that_exists(&self) -> Option<EasyPath> {
+        self.as_easy_path_ref().ancestor_that_exists()
+    }
+
+    /// See EasyPathRef::base_name()
+    pub fn base_name(&self) -> Option<&str> {
+        self.as_easy_path_ref().base_name()
+    }
+
+    /// See EasyPathRef::common_path_with()
+    pub fn common_path_with<T: Display + ?Sized>(
+        &self,
+        arg_string_path: &T,
+    ) -> Result<EasyPath, String> {
+        self.as_easy_path_ref().common_path_with(arg_string_path)
+    }
+
+    /// See EasyPathRef::dir_name()
+    pub fn dir_name(&self) -> Option<EasyPathRef<'_>> {
+        self.as_easy_path_ref().dir_name()
+    }
+
+    /// See EasyPathRef::dirs_in_dir_and_sub_dirs()
+    pub fn dirs_in_dir_and_sub_dirs(&self) -> Result<Vec<EasyPath>, String> {
+        self.as_easy_path_ref().dirs_in_dir_and_sub_dirs()
+    }
+
+    /// See EasyPathRef::extension()
+    pub fn extension(&self) -> Option<&str> {
+        self.as_easy_path_ref().extension()
+    }
+
+    /// See EasyPathRef::files_in_dir_and_sub_dirs()
+    pub fn files_in_dir_and_sub_dirs(&self) -> Result<Vec<EasyPath>, String> {
+        self.as_easy_path_ref().files_in_dir_and_sub_dirs()
+    }
+
+    /// See EasyPathRef::has_parent()
+    pub fn has_parent(&self) -> bool {
+        self.as_easy_path_ref().has_parent()
+    }
+
+    /// See EasyPathRef::is_absolute()
+    pub fn is_absolute(&self) -> bool {
+        self.as_easy_path_ref().is_absolute()
+    }
+
+    /// See EasyPathRef::exists()
+    pub fn exists(&self) -> bool {
+        self.as_easy_path_ref().exists()
+    }
+
+    /// See EasyPathRef::is_dir()
+    pub fn is_dir(&self) -> bool {
+        self.as_easy_path_ref().is_dir()
+    }
+
+    /// See EasyPathRef::is_file()
+    pub fn is_file(&self) -> bool {
+        self.as_easy_path_ref().is_file()
+    }
+
+    /// See EasyPathRef::is_same_as()
+    pub fn is_same_as<T: Display + ?Sized>(
+        &self,
+        arg_string_path: &T,
+        arg_path_comparison: PathComparison,
+    ) -> Result<bool, String> {
+        self.as_easy_path_ref()
+            .is_same_as(arg_string_path, arg_path_comparison)
+    }
+
+    /// See EasyPathRef::join()
+    pub fn join<T: Display + ?Sized>(&self, arg_string_path: &T) -> EasyPath {
+        self.as_easy_path_ref().join(arg_string_path)
+    }
+
+    /// See EasyPathRef::normalized()
+    pub fn normalized(&self) -> EasyPath {
+        self.as_easy_path_ref().normalized()
+    }
+
+    /// See EasyPathRef::parent_n()
+    pub fn parent_n(&self, arg_int_levels_up: usize) -> Option<EasyPathRef<'_>> {
+        self.as_easy_path_ref().parent_n(arg_int_levels_up)
+    }
+
+    /// See EasyPathRef::paths_in_dir()
+    pub fn paths_in_dir(&self) -> Result<Vec<EasyPath>, String> {
+        self.as_easy_path_ref().paths_in_dir()
+    }
+
+    /// See EasyPathRef::paths_in_dir_and_sub_dirs()
+    pub fn paths_in_dir_and_sub_dirs(&self) -> Result<Vec<EasyPath>, String> {
+        self.as_easy_path_ref().paths_in_dir_and_sub_dirs()
+    }
+
+    /// See EasyPathRef::raise_error_if_does_not_exist()
+    pub fn raise_error_if_does_not_exist(&self) -> Result<(), String> {
+        self.as_easy_path_ref().raise_error_if_does_not_exist()
+    }
+
+    /// See EasyPathRef::raise_error_if_not_in_project()
+    pub fn raise_error_if_not_in_project(&self) -> Result<(), String> {
+        self.as_easy_path_ref().raise_error_if_not_in_project()
+    }
+
+    /// See EasyPathRef::relative_from()
+    pub fn relative_from<T: Display + ?Sized>(
+        &self,
+        arg_string_path_dir_from: &T,
+        arg_path_normalization: PathNormalization,
+    ) -> Result<EasyPath, String> {
+        self.as_easy_path_ref()
+            .relative_from(arg_string_path_dir_from, arg_path_normalization)
+    }
+
+    /// See EasyPathRef::relative_to()
+    pub fn relative_to<T: Display + ?Sized>(
+        &self,
+        arg_string_path_root_prefix: &T,
+    ) -> Result<EasyPath, String> {
+        self.as_easy_path_ref()
+            .relative_to(arg_string_path_root_prefix)
+    }
+}
+
+impl Display for EasyPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.string_path,)
+    }
+}
+
+impl Display for EasyPathRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.str_path,)
+    }
+}
+
+impl Deref for EasyPath {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.string_path
+    }
+}
+
+impl Deref for EasyPathRef<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.str_path
+    }
+}
+
+impl AsRef<Path> for EasyPath {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl AsRef<Path> for EasyPathRef<'_> {
+    fn as_ref(&self) -> &Path {
+        self.as_path()
+    }
+}
+
+impl AsRef<str> for EasyPath {
+    fn as_ref(&self) -> &str {
+        &self.string_path
+    }
+}
+
+impl AsRef<str> for EasyPathRef<'_> {
+    fn as_ref(&self) -> &str {
+        self.str_path
+    }
+}
+
+impl From<&str> for EasyPath {
+    fn from(arg_str_path: &str) -> Self {
+        EasyPath {
+            string_path: arg_str_path.to_string(),
+        }
+    }
+}
+
+impl From<String> for EasyPath {
+    fn from(arg_string_path: String) -> Self {
+        EasyPath {
+            string_path: arg_string_path,
+        }
+    }
+}
+
+impl From<EasyPathRef<'_>> for EasyPath {
+    fn from(arg_easy_path_ref: EasyPathRef<'_>) -> Self {
+        arg_easy_path_ref.to_easy_path()
+    }
+}
+
+impl From<EasyPath> for String {
+    fn from(arg_easy_path: EasyPath) -> Self {
+        arg_easy_path.string_path
+    }
+}
+
+impl From<EasyPath> for PathBuf {
+    fn from(arg_easy_path: EasyPath) -> Self {
+        PathBuf::from(arg_easy_path.string_path)
+    }
+}
+
+impl<'a> From<&'a str> for EasyPathRef<'a> {
+    fn from(arg_str_path: &'a str) -> Self {
+        EasyPathRef {
+            str_path: arg_str_path,
+        }
+    }
+}
+
+impl<'a> From<&'a EasyPath> for EasyPathRef<'a> {
+    fn from(arg_easy_path: &'a EasyPath) -> Self {
+        arg_easy_path.as_easy_path_ref()
+    }
+}
+//
+// Private
+//
+fn get_vec_of_easy_paths(arg_result: Result<Vec<String>, String>) -> Result<Vec<EasyPath>, String> {
+    Ok(arg_result?.into_iter().map(EasyPath::from).collect())
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_base_name;
+
+    #[test]
+    fn test_easy_path_methods() {
+        let easy_path = EasyPath::from("/A/B/C/file.txt");
+        assert_eq!(easy_path.base_name(), Some("file.txt"));
+        assert_eq!(easy_path.extension(), Some("txt"));
+        assert_eq!(
+            easy_path.dir_name().map(|item| item.as_str()),
+            Some("/A/B/C")
+        );
+        assert_eq!(
+            easy_path.parent_n(2).map(|item| item.as_str()),
+            Some("/A/B")
+        );
+        assert_eq!(easy_path.parent_n(5), None);
+        assert_eq!(
+            easy_path.parent_n(2).unwrap().join("D").join("../E"),
+            EasyPath::from("/A/B/D/../E"),
+        );
+        assert_eq!(
+            easy_path.join("../../X").normalized(),
+            EasyPath::from("/A/B/X")
+        );
+        assert_eq!(
+            easy_path.relative_to("/A/B"),
+            Ok(EasyPath::from("C/file.txt"))
+        );
+        assert!(easy_path.relative_to("/X").is_err());
+        assert!(easy_path.is_absolute());
+        assert_eq!(
+            easy_path.relative_from("/A/X", PathNormalization::Lexical),
+            Ok(EasyPath::from("../B/C/file.txt"))
+        );
+        assert_eq!(
+            easy_path.is_same_as("/A/B/../B/C/file.txt", PathComparison::Lexical),
+            Ok(true)
+        );
+        assert!(easy_path.has_parent());
+    }
+
+    #[test]
+    fn test_easy_path_listing_and_guards() {
+        let easy_path = EasyPath::from("test/test_b");
+        let mut vec_of_easy_paths = easy_path.files_in_dir_and_sub_dirs().unwrap();
+        vec_of_easy_paths.sort();
+        assert_eq!(
+            vec_of_easy_paths,
+            vec![
+                EasyPath::from("test/test_b/test_c/text_b_c_a.txt"),
+                EasyPath::from("test/test_b/test_c/text_b_c_b.txt"),
+            ]
+        );
+        assert_eq!(
+            easy_path.dirs_in_dir_and_sub_dirs(),
+            Ok(vec![EasyPath::from("test/test_b/test_c")])
+        );
+        assert_eq!(
+            easy_path
+                .common_path_with("test/test_a")
+                .map(|item| item.absolute()),
+            Ok(EasyPath::from("test").absolute())
+        );
+        assert_eq!(easy_path.raise_error_if_does_not_exist(), Ok(()));
+        assert_eq!(easy_path.raise_error_if_not_in_project(), Ok(()));
+        assert!(EasyPath::from("test/missing")
+            .raise_error_if_does_not_exist()
+            .is_err());
+        assert_eq!(
+            EasyPath::from("test/test_b/missing").ancestor_that_exists(),
+            Some(EasyPath::from("test/test_b"))
+        );
+    }
+
+    #[test]
+    fn test_easy_path_string_like() {
+        let easy_path = EasyPath::from("test/test_a");
+        assert_eq!(format!("{}", easy_path,), "test/test_a");
+        assert!(easy_path.ends_with("test_a"));
+        assert_eq!(get_base_name(&easy_path), Some("test_a".to_string()));
+        assert!(std::fs::metadata(&easy_path).is_ok());
+        assert_eq!(
+            easy_path.absolute(),
+            EasyPath::new(&format!("{}/test/test_a", env!("CARGO_MANIFEST_DIR"),)),
+        );
+        let easy_path_ref = EasyPathRef::from(&easy_path);
+        assert_eq!(easy_path_ref.to_easy_path(), easy_path);
+        assert_eq!(String::from(easy_path), "test/test_a".to_string());
+    }
+}
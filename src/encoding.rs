@@ -0,0 +1,469 @@
+//
+// Libraries - native
+//
+use std::ffi::{OsStr, OsString};
+use std::fmt::{Debug, Display};
+use std::path::Path;
+//
+// Libraries - local
+//
+use crate::find::{find, find_in_path};
+//
+// Public - structs
+//
+/// How paths that aren't valid UTF-8 should be turned into Strings
+/// Strict is the crate's normal behavior: the path is reported as an error.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathEncoding {
+    #[default]
+    Strict,
+    /// Invalid sequences become U+FFFD; this can't be reversed
+    Lossy,
+    /// Invalid bytes become '\xNN' and '\' becomes '\\'; reverse with get_os_string_from_escaped_string()
+    #[cfg(unix)]
+    Escaped,
+}
+
+/// Paths converted with U+FFFD substitution, along with which ones were changed
+/// indices_lossy holds the positions in paths of every entry that wasn't valid UTF-8.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PathsLossy {
+    pub paths: Vec<String>,
+    pub indices_lossy: Vec<usize>,
+}
+
+impl PathsLossy {
+    /// Returns true if no path needed substitution
+    pub fn is_lossless(&self) -> bool {
+        self.indices_lossy.is_empty()
+    }
+
+    fn push(&mut self, arg_path: &Path) {
+        let (string_path, bool_lossy) = get_string_and_bool_lossy(arg_path.as_os_str());
+        if bool_lossy {
+            self.indices_lossy.push(self.paths.len());
+        }
+        self.paths.push(string_path);
+    }
+}
+//
+// Public - get - os strings
+//
+/// Returns the filename as an OsString, without requiring it to be valid UTF-8
+/// Returns None if the path has no filename ( ie '/' or ends in '..' )
+/// # Arguments
+/// * arg_path: anything that can be viewed as a Path ( str, String, OsStr, PathBuf )
+/// # Examples
+/// let os_string_name = match get_base_name_as_os_string( &path_buf ) {
+///     Some( os_string_result ) => { os_string_result }
+///     None => { panic!( "No file name." ) }
+/// };
+pub fn get_base_name_as_os_string<P: AsRef<Path> + ?Sized>(arg_path: &P) -> Option<OsString> {
+    arg_path
+        .as_ref()
+        .file_name()
+        .map(|os_str_result| os_str_result.to_os_string())
+}
+
+/// Returns a vec of OsStrings resulting from splitting the path
+/// Unlike get_vec_by_splitting_path(), this can't fail on badly encoded components
+/// # Arguments
+/// * arg_path: anything that can be viewed as a Path
+/// # Examples
+/// let vec_of_os_strings = get_vec_by_splitting_path_as_os_strings( &path_buf );
+pub fn get_vec_by_splitting_path_as_os_strings<P: AsRef<Path> + ?Sized>(
+    arg_path: &P,
+) -> Vec<OsString> {
+    arg_path
+        .as_ref()
+        .iter()
+        .map(|item_os_str| item_os_str.to_os_string())
+        .collect::<Vec<OsString>>()
+}
+
+/// Returns a vec of OsString paths inside directory
+/// Badly encoded names are returned as-is instead of causing a failure.
+/// In case of a failure reading the directory, this returns an error explaining what happened
+/// # Arguments
+/// * arg_path: anything that can be viewed as a Path
+/// # Examples
+/// let vec_of_os_strings = match get_paths_in_dir_as_os_strings( &"test" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_paths_in_dir_as_os_strings<P: AsRef<Path> + ?Sized>(
+    arg_path: &P,
+) -> Result<Vec<OsString>, String> {
+    let mut vec_to_return = vec![];
+    for_each_path_in_dir(arg_path.as_ref(), |arg_path_item| {
+        vec_to_return.push(arg_path_item.as_os_str().to_os_string())
+    })?;
+    Ok(vec_to_return)
+}
+
+/// Returns a vec of OsString paths inside directory and all sub directories
+/// Badly encoded names, including in the top directory, are returned as-is instead of causing a failure.
+/// Symlinks to directories are followed like get_paths_in_dir_and_sub_dirs() follows them, except each
+/// directory is only entered once, so a second link to it, or a link loop, isn't walked again.
+/// In case of a failure reading a directory, this returns an error explaining what happened
+/// # Arguments
+/// * arg_path: anything that can be viewed as a Path
+/// # Examples
+/// let vec_of_os_strings = match get_paths_in_dir_and_sub_dirs_as_os_strings( &"test" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_paths_in_dir_and_sub_dirs_as_os_strings<P: AsRef<Path> + ?Sized>(
+    arg_path: &P,
+) -> Result<Vec<OsString>, String> {
+    find_in_path(arg_path.as_ref())
+        .follow_symlinks()
+        .into_iter_os()
+        .collect::<Result<Vec<OsString>, String>>()
+}
+//
+// Public - get - lossy
+//
+/// Returns the paths inside directory, substituting U+FFFD for anything that isn't valid UTF-8
+/// The returned PathsLossy records which entries were changed.
+/// In case of a failure reading the directory, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let paths_lossy = match get_paths_in_dir_lossy( &"test" ) {
+///     Ok( paths_lossy_result ) => { paths_lossy_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// for item_index in &paths_lossy.indices_lossy {
+///     println!( "Badly encoded: {}", paths_lossy.paths[ *item_index ], );
+/// }
+pub fn get_paths_in_dir_lossy<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<PathsLossy, String> {
+    let mut paths_lossy_to_return = PathsLossy::default();
+    for_each_path_in_dir(
+        Path::new(&format!("{}", arg_string_path,)),
+        |arg_path_item| paths_lossy_to_return.push(arg_path_item),
+    )?;
+    Ok(paths_lossy_to_return)
+}
+
+/// Returns the paths inside directory and all sub directories, substituting U+FFFD for anything
+/// that isn't valid UTF-8
+/// Symlinks to directories are followed as in get_paths_in_dir_and_sub_dirs_as_os_strings().
+/// In case of a failure reading a directory, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path to the top directory
+/// # Examples
+/// let paths_lossy = match get_paths_in_dir_and_sub_dirs_lossy( &"test" ) {
+///     Ok( paths_lossy_result ) => { paths_lossy_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_paths_in_dir_and_sub_dirs_lossy<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<PathsLossy, String> {
+    let mut paths_lossy_to_return = PathsLossy::default();
+    for item_result in find(arg_string_path).follow_symlinks().into_iter_os() {
+        paths_lossy_to_return.push(Path::new(&item_result?));
+    }
+    Ok(paths_lossy_to_return)
+}
+
+/// Returns a String extracted from OsStr, substituting U+FFFD for invalid sequences
+/// The bool is true if any substitution happened
+/// # Arguments
+/// * arg_osstr: argument of type: &OsStr
+/// # Examples
+/// let ( string_result, bool_lossy ) = get_string_from_type_osstr_lossy( &os_str );
+pub fn get_string_from_type_osstr_lossy(arg_osstr: &OsStr) -> (String, bool) {
+    get_string_and_bool_lossy(arg_osstr)
+}
+//
+// Public - get - escaped
+//
+/// Returns a String extracted from OsStr, with bytes that aren't valid UTF-8 written as '\xNN'
+/// Backslashes are doubled so the result can always be reversed with get_os_string_from_escaped_string().
+/// Valid UTF-8 without backslashes comes back unchanged.
+/// # Arguments
+/// * arg_osstr: argument of type: &OsStr
+/// # Examples
+/// let string_escaped = get_string_from_type_osstr_escaped( &os_str );
+/// // b"caf\xE9" -> "caf\\xE9"
+#[cfg(unix)]
+pub fn get_string_from_type_osstr_escaped(arg_osstr: &OsStr) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    let mut string_to_return = String::new();
+    for item_chunk in arg_osstr.as_bytes().utf8_chunks() {
+        string_to_return.push_str(&item_chunk.valid().replace('\\', "\\\\"));
+        for item_byte in item_chunk.invalid() {
+            string_to_return.push_str(&format!("\\x{:02X}", item_byte,));
+        }
+    }
+    string_to_return
+}
+
+/// Returns the OsString encoded by get_string_from_type_osstr_escaped()
+/// In case of a failure ( a '\' that isn't followed by '\' or 'xNN' ), this returns an error
+/// # Arguments
+/// * arg_string_escaped: string-like
+/// # Examples
+/// let os_string = match get_os_string_from_escaped_string( &"caf\\xE9" ) {
+///     Ok( os_string_result ) => { os_string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+#[cfg(unix)]
+pub fn get_os_string_from_escaped_string<T: Debug + Display>(
+    arg_string_escaped: &T,
+) -> Result<OsString, String> {
+    use std::os::unix::ffi::OsStringExt;
+    let string_escaped = format!("{}", arg_string_escaped,);
+    let vec_of_bytes_escaped = string_escaped.as_bytes();
+    let mut vec_of_bytes = Vec::with_capacity(vec_of_bytes_escaped.len());
+    let mut int_index = 0;
+    while int_index < vec_of_bytes_escaped.len() {
+        let byte_current = vec_of_bytes_escaped[int_index];
+        if byte_current != b'\\' {
+            vec_of_bytes.push(byte_current);
+            int_index += 1;
+            continue;
+        }
+        match vec_of_bytes_escaped.get(int_index + 1) {
+            Some(b'\\') => {
+                vec_of_bytes.push(b'\\');
+                int_index += 2;
+            }
+            Some(b'x') => {
+                let option_byte = string_escaped
+                    .get(int_index + 2..int_index + 4)
+                    .and_then(|str_hex| u8::from_str_radix(str_hex, 16).ok());
+                match option_byte {
+                    Some(byte_decoded) => vec_of_bytes.push(byte_decoded),
+                    None => {
+                        return Err([
+                            "Error: '\\x' must be followed by two hex digits.".to_string(),
+                            format!("position = {}", int_index,),
+                            format!("arg_string_escaped = {}", arg_string_escaped,),
+                        ]
+                        .join("\n"))
+                    }
+                }
+                int_index += 4;
+            }
+            _ => {
+                return Err([
+                    "Error: unrecognized escape sequence.".to_string(),
+                    format!("position = {}", int_index,),
+                    format!("arg_string_escaped = {}", arg_string_escaped,),
+                ]
+                .join("\n"))
+            }
+        }
+    }
+    Ok(OsString::from_vec(vec_of_bytes))
+}
+//
+// Crate
+//
+/// Converts the path with the chosen encoding
+/// Only PathEncoding::Strict can fail
+pub(crate) fn get_string_from_path_with_encoding(
+    arg_path: &Path,
+    arg_path_encoding: PathEncoding,
+) -> Result<String, String> {
+    match arg_path_encoding {
+        PathEncoding::Strict => match arg_path.to_str() {
+            Some(str_result) => Ok(str_result.to_string()),
+            None => Err([
+                "Error: failed to extract str from PathBuf".to_string(),
+                format!("arg_path = {:?}", arg_path,),
+            ]
+            .join("\n")),
+        },
+        PathEncoding::Lossy => Ok(arg_path.to_string_lossy().to_string()),
+        #[cfg(unix)]
+        PathEncoding::Escaped => Ok(get_string_from_type_osstr_escaped(arg_path.as_os_str())),
+    }
+}
+//
+// Private
+//
+fn get_string_and_bool_lossy(arg_osstr: &OsStr) -> (String, bool) {
+    match arg_osstr.to_str() {
+        Some(str_result) => (str_result.to_string(), false),
+        None => (arg_osstr.to_string_lossy().to_string(), true),
+    }
+}
+
+fn for_each_path_in_dir<F: FnMut(&Path)>(
+    arg_path: &Path,
+    mut arg_closure: F,
+) -> Result<(), String> {
+    let read_dir = match std::fs::read_dir(arg_path) {
+        Ok(read_dir_result) => read_dir_result,
+        Err(err) => {
+            return Err([
+                "Error: failed to read directory.".to_string(),
+                format!("err = {}", err,),
+                format!("arg_path = {:?}", arg_path,),
+            ]
+            .join("\n"))
+        }
+    };
+    for item_dir_entry_result in read_dir {
+        match item_dir_entry_result {
+            Ok(item_dir_entry) => arg_closure(&item_dir_entry.path()),
+            Err(err) => {
+                return Err([
+                    "Error: failed to extract item_dir_entry".to_string(),
+                    format!("err = {}", err,),
+                ]
+                .join("\n"))
+            }
+        }
+    }
+    Ok(())
+}
+//
+// Tests
+//
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+    use std::os::unix::ffi::OsStrExt;
+
+    /// Creates a directory holding one valid and one badly encoded file name
+    /// Returns None if the filesystem refuses non-UTF-8 names ( ie APFS )
+    fn get_dir_with_bad_name(arg_str_name: &str) -> Option<String> {
+        let string_path_dir = get_dir_temp_for_tests(arg_str_name);
+        std::fs::write(format!("{}/good.txt", string_path_dir,), "").unwrap();
+        let path_buf_bad = Path::new(&string_path_dir).join(OsStr::from_bytes(b"bad\xFF.txt"));
+        match std::fs::write(&path_buf_bad, "") {
+            Ok(()) => Some(string_path_dir),
+            Err(_err) => None,
+        }
+    }
+
+    #[test]
+    fn test_get_string_from_type_osstr_escaped() {
+        let os_str = OsStr::from_bytes(b"a\\b/caf\xE9");
+        let string_escaped = get_string_from_type_osstr_escaped(os_str);
+        assert_eq!(string_escaped, "a\\\\b/caf\\xE9".to_string());
+        assert_eq!(
+            get_os_string_from_escaped_string(&string_escaped),
+            Ok(os_str.to_os_string())
+        );
+        match get_os_string_from_escaped_string(&"bad\\q") {
+            Ok(os_string_result) => panic!("Accepted bad escape: {:?}", os_string_result),
+            Err(_err) => {}
+        }
+    }
+
+    #[test]
+    fn test_get_paths_in_dir_lossy() {
+        let string_path_dir = match get_dir_with_bad_name("paths_in_dir_lossy") {
+            Some(string_result) => string_result,
+            None => return,
+        };
+        let paths_lossy = match get_paths_in_dir_lossy(&string_path_dir) {
+            Ok(paths_lossy_result) => paths_lossy_result,
+            Err(err) => panic!("{}", err,),
+        };
+        assert_eq!(paths_lossy.paths.len(), 2);
+        assert_eq!(paths_lossy.indices_lossy.len(), 1);
+        assert!(paths_lossy.paths[paths_lossy.indices_lossy[0]].ends_with("bad\u{FFFD}.txt"));
+        assert!(crate::get_paths_in_dir(&string_path_dir).is_err());
+    }
+
+    #[test]
+    fn test_get_paths_in_dir_and_sub_dirs_as_os_strings() {
+        let string_path_dir = match get_dir_with_bad_name("paths_in_dir_and_sub_dirs_os") {
+            Some(string_result) => string_result,
+            None => return,
+        };
+        let mut result = match get_paths_in_dir_and_sub_dirs_as_os_strings(&string_path_dir) {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        result.sort();
+        let expected = vec![
+            Path::new(&string_path_dir)
+                .join(OsStr::from_bytes(b"bad\xFF.txt"))
+                .into_os_string(),
+            Path::new(&string_path_dir)
+                .join("good.txt")
+                .into_os_string(),
+        ];
+        assert_eq!(result, expected);
+        let path_buf_dir_bad = Path::new(&string_path_dir).join(OsStr::from_bytes(b"dir\xFF"));
+        std::fs::create_dir(&path_buf_dir_bad).unwrap();
+        std::fs::write(path_buf_dir_bad.join("a.txt"), "").unwrap();
+        assert_eq!(
+            get_paths_in_dir_and_sub_dirs_as_os_strings(&path_buf_dir_bad),
+            Ok(vec![path_buf_dir_bad.join("a.txt").into_os_string()])
+        )
+    }
+
+    #[test]
+    fn test_get_paths_in_dir_and_sub_dirs_follows_symlinks_like_the_original() {
+        let string_path_dir = get_dir_temp_for_tests("paths_in_dir_and_sub_dirs_symlinks");
+        let string_path_dir_walked = format!("{}/walked", string_path_dir,);
+        std::fs::create_dir_all(format!("{}/outside", string_path_dir,)).unwrap();
+        std::fs::create_dir(&string_path_dir_walked).unwrap();
+        std::fs::write(format!("{}/outside/a.txt", string_path_dir,), "").unwrap();
+        std::os::unix::fs::symlink(
+            format!("{}/outside", string_path_dir,),
+            format!("{}/link", string_path_dir_walked,),
+        )
+        .unwrap();
+        let mut expected = crate::get_paths_in_dir_and_sub_dirs(&string_path_dir_walked).unwrap();
+        expected.sort();
+        assert_eq!(
+            expected,
+            vec![
+                format!("{}/link", string_path_dir_walked,),
+                format!("{}/link/a.txt", string_path_dir_walked,),
+            ]
+        );
+        let mut result = get_paths_in_dir_and_sub_dirs_as_os_strings(&string_path_dir_walked)
+            .unwrap()
+            .into_iter()
+            .map(|item_os_string| item_os_string.into_string().unwrap())
+            .collect::<Vec<String>>();
+        result.sort();
+        assert_eq!(result, expected);
+        let mut paths_lossy = get_paths_in_dir_and_sub_dirs_lossy(&string_path_dir_walked).unwrap();
+        paths_lossy.paths.sort();
+        assert_eq!(paths_lossy.paths, expected);
+    }
+
+    #[test]
+    fn test_find_reports_bad_names_and_continues() {
+        let string_path_dir = match get_dir_with_bad_name("find_reports_bad_names") {
+            Some(string_result) => string_result,
+            None => return,
+        };
+        let vec_of_results = find(&string_path_dir).into_iter().collect::<Vec<_>>();
+        assert_eq!(vec_of_results.len(), 2);
+        assert_eq!(
+            vec_of_results
+                .iter()
+                .filter(|item_result| item_result.is_err())
+                .count(),
+            1
+        );
+        let mut vec_of_strings_escaped = find(&string_path_dir)
+            .encoding(PathEncoding::Escaped)
+            .into_iter()
+            .collect::<Result<Vec<String>, String>>()
+            .unwrap();
+        vec_of_strings_escaped.sort();
+        assert_eq!(
+            vec_of_strings_escaped,
+            vec![
+                format!("{}/bad\\xFF.txt", string_path_dir,),
+                format!("{}/good.txt", string_path_dir,),
+            ]
+        )
+    }
+}
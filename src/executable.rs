@@ -0,0 +1,249 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+//
+// Libraries - local
+//
+use crate::{get_dir_cwd, get_path_normalized};
+//
+// Public - get - executables
+//
+/// Returns the absolute path of the first executable named arg_string_name on PATH, like which(1)
+/// Relative PATH entries ( and empty ones, which mean '.' ) are resolved against the cwd. On unix a file
+/// only counts if one of its execute bits is set; on Windows each extension in PATHEXT is tried.
+/// A name containing a separator ( ie "./run.sh" ) is checked directly instead of searching PATH.
+/// Symlinks are not resolved, so the result is the path that would be spawned.
+/// If nothing is found, this returns an error listing every directory searched
+/// # Arguments
+/// * arg_string_name: string-like executable name, ie "git"
+/// # Examples
+/// let string_path_git = match get_path_of_executable( &"git" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path_git = "/usr/bin/git"
+pub fn get_path_of_executable<T: Debug + Display>(arg_string_name: &T) -> Result<String, String> {
+    get_path_of_executable_in_dirs(arg_string_name, &get_vec_of_dirs_on_path())
+}
+
+/// Returns the absolute paths of every executable named arg_string_name on PATH, in PATH order
+/// Directories listed more than once only contribute once.
+/// If nothing is found, this returns an error listing every directory searched
+/// # Arguments
+/// * arg_string_name: string-like executable name
+/// # Examples
+/// let vec_of_paths = match get_all_paths_of_executable( &"python3" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_all_paths_of_executable<T: Debug + Display>(
+    arg_string_name: &T,
+) -> Result<Vec<String>, String> {
+    get_all_paths_of_executable_in_dirs(arg_string_name, &get_vec_of_dirs_on_path())
+}
+
+/// Same as get_path_of_executable(), except arg_slice_of_dirs is searched instead of PATH
+/// # Arguments
+/// * arg_string_name: string-like executable name
+/// * arg_slice_of_dirs: slice of string-like directories, searched in order
+/// # Examples
+/// let string_path = match get_path_of_executable_in_dirs( &"clang", &[ "/opt/llvm/bin", "/usr/bin" ] ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_path_of_executable_in_dirs<T1: Debug + Display, T2: Display>(
+    arg_string_name: &T1,
+    arg_slice_of_dirs: &[T2],
+) -> Result<String, String> {
+    let vec_of_paths = get_vec_of_executables(arg_string_name, arg_slice_of_dirs, true)?;
+    Ok(vec_of_paths.into_iter().next().unwrap_or_default())
+}
+
+/// Same as get_all_paths_of_executable(), except arg_slice_of_dirs is searched instead of PATH
+/// # Arguments
+/// * arg_string_name: string-like executable name
+/// * arg_slice_of_dirs: slice of string-like directories, searched in order
+pub fn get_all_paths_of_executable_in_dirs<T1: Debug + Display, T2: Display>(
+    arg_string_name: &T1,
+    arg_slice_of_dirs: &[T2],
+) -> Result<Vec<String>, String> {
+    get_vec_of_executables(arg_string_name, arg_slice_of_dirs, false)
+}
+//
+// Private
+//
+/// Returns the executables found, stopping after the first if arg_bool_first_only is set
+/// Returns an error if nothing is found, so the result is never empty
+fn get_vec_of_executables<T1: Debug + Display, T2: Display>(
+    arg_string_name: &T1,
+    arg_slice_of_dirs: &[T2],
+    arg_bool_first_only: bool,
+) -> Result<Vec<String>, String> {
+    let string_name = format!("{}", arg_string_name,);
+    if string_name.is_empty() {
+        return Err("Error: arg_string_name is empty.".to_string());
+    }
+    let string_path_dir_cwd = get_dir_cwd()?;
+    let vec_of_names = get_vec_of_names_with_extensions(&string_name);
+    // A name with a separator is a path, so it's only checked where it points
+    let vec_of_dirs =
+        if string_name.contains('/') || string_name.contains(std::path::MAIN_SEPARATOR) {
+            vec![string_path_dir_cwd.clone()]
+        } else {
+            arg_slice_of_dirs
+                .iter()
+                .map(|item| format!("{}", item,))
+                .collect::<Vec<String>>()
+        };
+    let mut vec_of_dirs_searched: Vec<String> = vec![];
+    let mut vec_to_return: Vec<String> = vec![];
+    for item_string_dir in &vec_of_dirs {
+        let string_path_dir = get_string_path_absolute(item_string_dir, &string_path_dir_cwd);
+        if vec_of_dirs_searched.contains(&string_path_dir) {
+            continue;
+        }
+        vec_of_dirs_searched.push(string_path_dir.clone());
+        for item_string_name in &vec_of_names {
+            let path_buf_candidate = Path::new(&string_path_dir).join(item_string_name);
+            if !is_executable(&path_buf_candidate) {
+                continue;
+            }
+            let string_path_candidate = get_string_path_absolute(
+                &path_buf_candidate.to_string_lossy(),
+                &string_path_dir_cwd,
+            );
+            if !vec_to_return.contains(&string_path_candidate) {
+                vec_to_return.push(string_path_candidate);
+            }
+            if arg_bool_first_only {
+                return Ok(vec_to_return);
+            }
+        }
+    }
+    if vec_to_return.is_empty() {
+        return Err([
+            "Error: executable not found.".to_string(),
+            format!("arg_string_name = {}", arg_string_name,),
+            format!("directories searched = {:?}", vec_of_dirs_searched,),
+        ]
+        .join("\n"));
+    }
+    Ok(vec_to_return)
+}
+
+/// Returns arg_str_path joined onto the cwd if it's relative, with '.' and '..' resolved
+/// An empty path is the cwd, as an empty PATH entry means '.'
+fn get_string_path_absolute(arg_str_path: &str, arg_str_path_dir_cwd: &str) -> String {
+    let path_buf = if Path::new(arg_str_path).is_absolute() {
+        PathBuf::from(arg_str_path)
+    } else {
+        Path::new(arg_str_path_dir_cwd).join(arg_str_path)
+    };
+    let string_path = path_buf.to_string_lossy().into_owned();
+    get_path_normalized(&string_path).unwrap_or(string_path)
+}
+
+fn get_vec_of_dirs_on_path() -> Vec<String> {
+    match std::env::var_os("PATH") {
+        Some(os_string_path) => std::env::split_paths(&os_string_path)
+            .map(|path_buf| path_buf.to_string_lossy().into_owned())
+            .collect(),
+        None => vec![],
+    }
+}
+
+#[cfg(not(windows))]
+fn get_vec_of_names_with_extensions(arg_str_name: &str) -> Vec<String> {
+    vec![arg_str_name.to_string()]
+}
+
+/// Returns the name with each PATHEXT extension appended, unless it already has an extension
+#[cfg(windows)]
+fn get_vec_of_names_with_extensions(arg_str_name: &str) -> Vec<String> {
+    if Path::new(arg_str_name).extension().is_some() {
+        return vec![arg_str_name.to_string()];
+    }
+    let string_extensions =
+        std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string());
+    string_extensions
+        .split(';')
+        .filter(|item_str_extension| !item_str_extension.is_empty())
+        .map(|item_str_extension| format!("{}{}", arg_str_name, item_str_extension,))
+        .collect()
+}
+
+#[cfg(unix)]
+fn is_executable(arg_path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    match std::fs::metadata(arg_path) {
+        Ok(metadata) => metadata.is_file() && metadata.permissions().mode() & 0o111 != 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_executable(arg_path: &Path) -> bool {
+    arg_path.is_file()
+}
+//
+// Tests
+//
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_file_with_mode(arg_string_path: &str, arg_int_mode: u32) {
+        std::fs::write(arg_string_path, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(
+            arg_string_path,
+            std::fs::Permissions::from_mode(arg_int_mode),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_get_path_of_executable_in_dirs() {
+        let string_path_dir = get_dir_temp_for_tests("get_path_of_executable_in_dirs");
+        for item_str_dir in ["a", "b", "c"] {
+            std::fs::create_dir(format!("{}/{}", string_path_dir, item_str_dir,)).unwrap();
+        }
+        write_file_with_mode(&format!("{}/a/tool", string_path_dir,), 0o644);
+        write_file_with_mode(&format!("{}/b/tool", string_path_dir,), 0o755);
+        write_file_with_mode(&format!("{}/c/tool", string_path_dir,), 0o700);
+        let vec_of_dirs = [
+            format!("{}/a", string_path_dir,),
+            format!("{}/b", string_path_dir,),
+            format!("{}/b/", string_path_dir,),
+            format!("{}/c", string_path_dir,),
+        ];
+        assert_eq!(
+            get_path_of_executable_in_dirs(&"tool", &vec_of_dirs),
+            Ok(format!("{}/b/tool", string_path_dir,))
+        );
+        assert_eq!(
+            get_all_paths_of_executable_in_dirs(&"tool", &vec_of_dirs),
+            Ok(vec![
+                format!("{}/b/tool", string_path_dir,),
+                format!("{}/c/tool", string_path_dir,),
+            ])
+        );
+        let err = get_path_of_executable_in_dirs(&"missing_tool", &vec_of_dirs).unwrap_err();
+        assert!(err.contains(&format!("{}/a", string_path_dir,)), "{}", err,);
+        assert_eq!(
+            get_path_of_executable_in_dirs(&format!("{}/b/tool", string_path_dir,), &["/nowhere"]),
+            Ok(format!("{}/b/tool", string_path_dir,))
+        );
+    }
+
+    #[test]
+    fn test_get_path_of_executable() {
+        // sh is required by POSIX, so it's on PATH everywhere these tests run
+        let string_path = get_path_of_executable(&"sh").unwrap();
+        assert!(Path::new(&string_path).is_absolute());
+        assert!(string_path.ends_with("/sh"));
+    }
+}
@@ -0,0 +1,400 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::path::Path;
+//
+// Libraries - local
+//
+use crate::get_base_name;
+//
+// Public - constants
+//
+/// The compound extensions that are treated as one extension, unless a *_with_compound() function is given others
+/// Matching is ASCII case-insensitive, so "X.TAR.GZ" has the full extension "TAR.GZ".
+pub const SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT: &[&str] = &[
+    "tar.gz", "tar.bz2", "tar.xz", "tar.zst", "tar.lz", "tar.lzma", "tar.Z", "tar.br", "d.ts",
+    "min.js", "min.css",
+];
+//
+// Public - get - extensions
+//
+/// Returns the file name without its full extension
+/// A name made only of a leading '.' and text is a dotfile, so ".bashrc" is its own stem, while
+/// ".bashrc.bak" has the stem ".bashrc".
+/// Returns None if the path has no file name
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let option_string_stem = get_stem( &"dir/archive.tar.gz" );
+/// // option_string_stem = Some( "archive" )
+pub fn get_stem<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    get_stem_with_compound(arg_string_path, SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT)
+}
+
+/// Same as get_stem(), except only the compound extensions in arg_slice_of_extensions_compound are kept whole
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_slice_of_extensions_compound: slice of string-likes, with or without their leading period, ie [ "pkg.tar.zst" ]
+/// # Examples
+/// let option_string_stem = get_stem_with_compound( &"a-1.pkg.tar.zst", &[ "pkg.tar.zst" ] );
+/// // option_string_stem = Some( "a-1" )
+pub fn get_stem_with_compound<T1: Debug + Display, T2: Display>(
+    arg_string_path: &T1,
+    arg_slice_of_extensions_compound: &[T2],
+) -> Option<String> {
+    let string_name = get_base_name(arg_string_path)?;
+    Some(
+        get_stem_and_full_extension(&string_name, arg_slice_of_extensions_compound)
+            .0
+            .to_string(),
+    )
+}
+
+/// Returns the extension without the leading period, keeping compound extensions whole
+/// Unlike get_extension(), which returns "gz" for 'x.tar.gz', this returns "tar.gz". Only compound
+/// extensions in SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT are joined, so 'my.report.pdf' returns "pdf".
+/// Returns None if there's no extension, ie for "Makefile" or ".bashrc"
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let option_string_extension = get_full_extension( &"x.tar.gz" );
+/// // option_string_extension = Some( "tar.gz" )
+pub fn get_full_extension<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    get_full_extension_with_compound(arg_string_path, SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT)
+}
+
+/// Same as get_full_extension(), except only the compound extensions in arg_slice_of_extensions_compound are joined
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_slice_of_extensions_compound: slice of string-likes, with or without their leading period, ie [ "pkg.tar.zst" ]
+/// # Examples
+/// let option_string_extension = get_full_extension_with_compound( &"a-1.pkg.tar.zst", &[ "pkg.tar.zst" ] );
+/// // option_string_extension = Some( "pkg.tar.zst" )
+pub fn get_full_extension_with_compound<T1: Debug + Display, T2: Display>(
+    arg_string_path: &T1,
+    arg_slice_of_extensions_compound: &[T2],
+) -> Option<String> {
+    let string_name = get_base_name(arg_string_path)?;
+    let str_extension =
+        get_stem_and_full_extension(&string_name, arg_slice_of_extensions_compound).1;
+    if str_extension.is_empty() {
+        return None;
+    }
+    Some(str_extension[1..].to_string())
+}
+
+/// Returns each part of the full extension, ie [ "tar", "gz" ] for 'x.tar.gz'
+/// Returns an empty vec if there's no extension
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let vec_of_extensions = get_extensions( &"x.tar.gz" );
+/// // vec_of_extensions = [ "tar", "gz" ]
+pub fn get_extensions<T: Debug + Display>(arg_string_path: &T) -> Vec<String> {
+    get_extensions_with_compound(arg_string_path, SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT)
+}
+
+/// Same as get_extensions(), except only the compound extensions in arg_slice_of_extensions_compound are split out
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_slice_of_extensions_compound: slice of string-likes, with or without their leading period, ie [ "pkg.tar.zst" ]
+/// # Examples
+/// let vec_of_extensions = get_extensions_with_compound( &"a-1.pkg.tar.zst", &[ "pkg.tar.zst" ] );
+/// // vec_of_extensions = [ "pkg", "tar", "zst" ]
+pub fn get_extensions_with_compound<T1: Debug + Display, T2: Display>(
+    arg_string_path: &T1,
+    arg_slice_of_extensions_compound: &[T2],
+) -> Vec<String> {
+    match get_full_extension_with_compound(arg_string_path, arg_slice_of_extensions_compound) {
+        Some(string_extension) => string_extension
+            .split('.')
+            .map(|item_str| item_str.to_string())
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// Returns the path with its full extension replaced by arg_string_extension
+/// The new extension may be written with or without its leading period; an empty one removes the
+/// extension. A path without an extension gets one added.
+/// In case of a failure ( ie the path has no file name ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_string_extension: string-like, ie "tar.xz"
+/// # Examples
+/// let string_path = match get_path_with_extension_replaced( &"dir/x.tar.gz", &"zip" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "dir/x.zip"
+pub fn get_path_with_extension_replaced<T1: Debug + Display, T2: Display>(
+    arg_string_path: &T1,
+    arg_string_extension: &T2,
+) -> Result<String, String> {
+    get_path_with_extension_replaced_with_compound(
+        arg_string_path,
+        arg_string_extension,
+        SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT,
+    )
+}
+
+/// Same as get_path_with_extension_replaced(), except only the compound extensions in arg_slice_of_extensions_compound are replaced whole
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_string_extension: string-like, ie "tar.xz"
+/// * arg_slice_of_extensions_compound: slice of string-likes, with or without their leading period, ie [ "pkg.tar.zst" ]
+/// # Examples
+/// let string_path = match get_path_with_extension_replaced_with_compound( &"a-1.pkg.tar.zst", &"zip", &[ "pkg.tar.zst" ] ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "a-1.zip"
+pub fn get_path_with_extension_replaced_with_compound<
+    T1: Debug + Display,
+    T2: Display,
+    T3: Display,
+>(
+    arg_string_path: &T1,
+    arg_string_extension: &T2,
+    arg_slice_of_extensions_compound: &[T3],
+) -> Result<String, String> {
+    let string_name = get_base_name_or_error(arg_string_path)?;
+    let str_stem = get_stem_and_full_extension(&string_name, arg_slice_of_extensions_compound).0;
+    Ok(get_path_with_file_name(
+        arg_string_path,
+        &get_name_with_extension(str_stem, arg_string_extension),
+    ))
+}
+
+/// Returns the path with arg_string_extension appended after any extension it already has
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_string_extension: string-like, with or without its leading period
+/// # Examples
+/// let string_path = match get_path_with_extension_added( &"backup.tar", &"gz" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "backup.tar.gz"
+pub fn get_path_with_extension_added<T1: Debug + Display, T2: Display>(
+    arg_string_path: &T1,
+    arg_string_extension: &T2,
+) -> Result<String, String> {
+    let string_name = get_base_name_or_error(arg_string_path)?;
+    Ok(get_path_with_file_name(
+        arg_string_path,
+        &get_name_with_extension(&string_name, arg_string_extension),
+    ))
+}
+
+/// Returns the path with its last component replaced by arg_string_name
+/// In case of a failure ( the path has no file name, or arg_string_name is empty, '.', '..' or
+/// contains a separator ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_string_name: string-like file name
+/// # Examples
+/// let string_path = match get_path_with_file_name_replaced( &"dir/old.txt", &"new.md" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "dir/new.md"
+pub fn get_path_with_file_name_replaced<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path: &T1,
+    arg_string_name: &T2,
+) -> Result<String, String> {
+    let string_name = format!("{}", arg_string_name,);
+    if string_name.is_empty()
+        || string_name == "."
+        || string_name == ".."
+        || string_name.contains('/')
+        || string_name.contains(std::path::MAIN_SEPARATOR)
+    {
+        return Err([
+            "Error: arg_string_name must be a single file name.".to_string(),
+            format!("arg_string_name = {:?}", arg_string_name,),
+        ]
+        .join("\n"));
+    }
+    get_base_name_or_error(arg_string_path)?;
+    Ok(get_path_with_file_name(arg_string_path, &string_name))
+}
+//
+// Private
+//
+/// Splits a file name into its stem and full extension, the extension keeping its period
+/// Leading periods belong to the stem, and the stem is never left empty, so ".tar.gz" is ( ".tar", ".gz" ).
+/// A trailing period isn't an extension.
+pub(crate) fn get_stem_and_full_extension<'a, T: Display>(
+    arg_str_name: &'a str,
+    arg_slice_of_extensions_compound: &[T],
+) -> (&'a str, &'a str) {
+    let int_len_leading_dots = arg_str_name.len() - arg_str_name.trim_start_matches('.').len();
+    let option_int_index_dot = arg_str_name[int_len_leading_dots..]
+        .rfind('.')
+        .map(|int_index| int_index + int_len_leading_dots);
+    let int_index_dot = match option_int_index_dot {
+        Some(int_index) if int_index + 1 < arg_str_name.len() => int_index,
+        _ => return (arg_str_name, ""),
+    };
+    // The longest match wins, so "pkg.tar.zst" beats "tar.zst" when both are listed
+    let str_name_lowercase = arg_str_name.to_ascii_lowercase();
+    let mut int_index_split = int_index_dot;
+    for item in arg_slice_of_extensions_compound {
+        let string_extension = format!("{}", item,);
+        let item_string_extension = string_extension.trim_start_matches('.');
+        let int_index_compound = match arg_str_name
+            .len()
+            .checked_sub(item_string_extension.len() + 1)
+        {
+            Some(int_index) if int_index > int_len_leading_dots => int_index,
+            _ => continue,
+        };
+        // The offset counts bytes, so it can land inside a multi-byte character; get() is None then
+        if int_index_compound < int_index_split
+            && str_name_lowercase.get(int_index_compound..)
+                == Some(format!(".{}", item_string_extension.to_ascii_lowercase(),).as_str())
+        {
+            int_index_split = int_index_compound;
+        }
+    }
+    arg_str_name.split_at(int_index_split)
+}
+
+fn get_base_name_or_error<T: Debug + Display>(arg_string_path: &T) -> Result<String, String> {
+    match get_base_name(arg_string_path) {
+        Some(string_name) => Ok(string_name),
+        None => Err([
+            "Error: path has no file name.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n")),
+    }
+}
+
+fn get_name_with_extension<T: Display>(arg_str_stem: &str, arg_string_extension: &T) -> String {
+    let string_extension = format!("{}", arg_string_extension,);
+    let str_extension = string_extension.trim_start_matches('.');
+    if str_extension.is_empty() {
+        return arg_str_stem.to_string();
+    }
+    format!("{}.{}", arg_str_stem, str_extension,)
+}
+
+fn get_path_with_file_name<T: Display>(arg_string_path: &T, arg_str_name: &str) -> String {
+    Path::new(&format!("{}", arg_string_path,))
+        .with_file_name(arg_str_name)
+        .to_string_lossy()
+        .into_owned()
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_stem_and_extensions() {
+        for (item_str_path, item_str_stem, item_option_str_extension) in [
+            ("dir/x.tar.gz", "x", Some("tar.gz")),
+            ("X.TAR.GZ", "X", Some("TAR.GZ")),
+            ("my.report.pdf", "my.report", Some("pdf")),
+            ("types.d.ts", "types", Some("d.ts")),
+            (".bashrc", ".bashrc", None),
+            (".bashrc.bak", ".bashrc", Some("bak")),
+            (".tar.gz", ".tar", Some("gz")),
+            ("..hidden", "..hidden", None),
+            ("Makefile", "Makefile", None),
+            ("trailing.", "trailing.", None),
+            ("résumé.pdf", "résumé", Some("pdf")),
+            ("étar.gz", "étar", Some("gz")),
+            ("archivé.tar.gz", "archivé", Some("tar.gz")),
+        ] {
+            assert_eq!(
+                get_stem(&item_str_path),
+                Some(item_str_stem.to_string()),
+                "{}",
+                item_str_path,
+            );
+            assert_eq!(
+                get_full_extension(&item_str_path),
+                item_option_str_extension.map(|item_str| item_str.to_string()),
+                "{}",
+                item_str_path,
+            );
+        }
+        assert_eq!(get_extensions(&"x.tar.gz"), vec!["tar", "gz"]);
+        assert_eq!(get_extensions(&"x"), Vec::<String>::new());
+        assert_eq!(get_stem(&"/"), None);
+        assert_eq!(
+            get_stem_and_full_extension("a.tar.gz", SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT),
+            ("a", ".tar.gz")
+        );
+        assert_eq!(
+            get_stem_and_full_extension("Makefile", SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT),
+            ("Makefile", "")
+        );
+    }
+
+    #[test]
+    fn test_get_path_with() {
+        assert_eq!(
+            get_path_with_extension_replaced(&"dir/x.tar.gz", &"zip"),
+            Ok("dir/x.zip".to_string())
+        );
+        assert_eq!(
+            get_path_with_extension_replaced(&".bashrc", &".bak"),
+            Ok(".bashrc.bak".to_string())
+        );
+        assert_eq!(
+            get_path_with_extension_replaced(&"x.tar.gz", &""),
+            Ok("x".to_string())
+        );
+        assert_eq!(
+            get_path_with_extension_added(&"backup.tar", &"gz"),
+            Ok("backup.tar.gz".to_string())
+        );
+        assert_eq!(
+            get_path_with_file_name_replaced(&"dir/old.txt", &"new.md"),
+            Ok("dir/new.md".to_string())
+        );
+        assert!(get_path_with_file_name_replaced(&"dir/old.txt", &"a/b").is_err());
+        assert!(get_path_with_file_name_replaced(&"dir/old.txt", &"..").is_err());
+        assert!(get_path_with_extension_added(&"/", &"gz").is_err());
+    }
+
+    #[test]
+    fn test_get_with_compound() {
+        let slice_of_extensions = [".pkg.tar.zst", "tar.zst", "single"];
+        assert_eq!(
+            get_full_extension_with_compound(&"a-1.pkg.tar.zst", &slice_of_extensions),
+            Some("pkg.tar.zst".to_string())
+        );
+        assert_eq!(
+            get_stem_with_compound(&"a-1.pkg.tar.zst", &slice_of_extensions),
+            Some("a-1".to_string())
+        );
+        assert_eq!(
+            get_extensions_with_compound(&"x.tar.gz", &slice_of_extensions),
+            vec!["gz"]
+        );
+        assert_eq!(
+            get_path_with_extension_replaced_with_compound(
+                &"a-1.pkg.tar.zst",
+                &"zip",
+                &slice_of_extensions
+            ),
+            Ok("a-1.zip".to_string())
+        );
+        assert_eq!(
+            get_full_extension(&"a-1.pkg.tar.zst"),
+            Some("tar.zst".to_string())
+        );
+        assert_eq!(
+            get_full_extension_with_compound(&"x.tar.gz", &[] as &[&str]),
+            Some("gz".to_string())
+        );
+    }
+}
@@ -0,0 +1,833 @@
+//
+// Libraries - native
+//
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io::{Error, ErrorKind};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+//
+// Public - traits
+//
+/// The filesystem operations easy_paths needs, so the _with_fs functions can run against any backend
+/// Paths are passed through as given; read_dir() returns each entry joined onto the path it was given,
+/// like std::fs::read_dir().
+pub trait FileSystem {
+    /// Returns the metadata of the path, following symlinks
+    fn metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata>;
+    /// Returns the metadata of the path itself, without following a final symlink
+    fn symlink_metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata>;
+    /// Returns the paths of the entries in a directory
+    fn read_dir(&self, arg_path: &Path) -> std::io::Result<Vec<PathBuf>>;
+    /// Returns the contents of a file
+    fn read(&self, arg_path: &Path) -> std::io::Result<Vec<u8>>;
+    /// Creates or truncates a file and writes arg_slice_of_bytes to it
+    fn write(&self, arg_path: &Path, arg_slice_of_bytes: &[u8]) -> std::io::Result<()>;
+    /// Creates a directory; its parent must exist
+    fn create_dir(&self, arg_path: &Path) -> std::io::Result<()>;
+    /// Removes a file or symlink
+    fn remove_file(&self, arg_path: &Path) -> std::io::Result<()>;
+    /// Removes an empty directory
+    fn remove_dir(&self, arg_path: &Path) -> std::io::Result<()>;
+    /// Returns the target of a symlink, as stored
+    fn read_link(&self, arg_path: &Path) -> std::io::Result<PathBuf>;
+    /// Creates a symlink at arg_path_link pointing at arg_path_target
+    fn symlink(&self, arg_path_target: &Path, arg_path_link: &Path) -> std::io::Result<()>;
+    /// Returns the absolute path with every symlink, '.' and '..' resolved; the path must exist
+    fn canonicalize(&self, arg_path: &Path) -> std::io::Result<PathBuf>;
+
+    /// Creates a directory and any missing parents
+    fn create_dir_all(&self, arg_path: &Path) -> std::io::Result<()> {
+        if self.is_dir(arg_path) {
+            return Ok(());
+        }
+        if let Some(path_parent) = arg_path.parent() {
+            if !path_parent.as_os_str().is_empty() {
+                self.create_dir_all(path_parent)?;
+            }
+        }
+        match self.create_dir(arg_path) {
+            Err(err) if err.kind() == ErrorKind::AlreadyExists && self.is_dir(arg_path) => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Returns true if something exists at the path, following symlinks
+    fn exists(&self, arg_path: &Path) -> bool {
+        self.metadata(arg_path).is_ok()
+    }
+
+    /// Returns true if the path is a directory, following symlinks
+    fn is_dir(&self, arg_path: &Path) -> bool {
+        self.metadata(arg_path)
+            .map(|metadata| metadata.is_dir())
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the path is a file, following symlinks
+    fn is_file(&self, arg_path: &Path) -> bool {
+        self.metadata(arg_path)
+            .map(|metadata| metadata.is_file())
+            .unwrap_or(false)
+    }
+}
+//
+// Public - enums
+//
+/// What kind of entry a path is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FsFileType {
+    File,
+    Dir,
+    Symlink,
+    /// Anything else, ie a socket or device
+    Other,
+}
+//
+// Public - structs
+//
+/// The parts of a path's metadata every FileSystem can report
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FsMetadata {
+    pub file_type: FsFileType,
+    /// Size in bytes; 0 for directories and symlinks in InMemoryFs
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+    pub readonly: bool,
+}
+
+impl FsMetadata {
+    pub fn is_dir(&self) -> bool {
+        self.file_type == FsFileType::Dir
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.file_type == FsFileType::File
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.file_type == FsFileType::Symlink
+    }
+}
+
+impl From<std::fs::Metadata> for FsMetadata {
+    fn from(arg_metadata: std::fs::Metadata) -> Self {
+        let file_type = if arg_metadata.is_symlink() {
+            FsFileType::Symlink
+        } else if arg_metadata.is_dir() {
+            FsFileType::Dir
+        } else if arg_metadata.is_file() {
+            FsFileType::File
+        } else {
+            FsFileType::Other
+        };
+        FsMetadata {
+            file_type,
+            len: arg_metadata.len(),
+            modified: arg_metadata.modified().ok(),
+            readonly: arg_metadata.permissions().readonly(),
+        }
+    }
+}
+
+/// The real filesystem, through std::fs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OsFs;
+
+impl FileSystem for OsFs {
+    fn metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        std::fs::metadata(arg_path).map(FsMetadata::from)
+    }
+
+    fn symlink_metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        std::fs::symlink_metadata(arg_path).map(FsMetadata::from)
+    }
+
+    fn read_dir(&self, arg_path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        std::fs::read_dir(arg_path)?
+            .map(|item_result| item_result.map(|dir_entry| dir_entry.path()))
+            .collect()
+    }
+
+    fn read(&self, arg_path: &Path) -> std::io::Result<Vec<u8>> {
+        std::fs::read(arg_path)
+    }
+
+    fn write(&self, arg_path: &Path, arg_slice_of_bytes: &[u8]) -> std::io::Result<()> {
+        std::fs::write(arg_path, arg_slice_of_bytes)
+    }
+
+    fn create_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        std::fs::create_dir(arg_path)
+    }
+
+    fn create_dir_all(&self, arg_path: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(arg_path)
+    }
+
+    fn remove_file(&self, arg_path: &Path) -> std::io::Result<()> {
+        std::fs::remove_file(arg_path)
+    }
+
+    fn remove_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        std::fs::remove_dir(arg_path)
+    }
+
+    fn read_link(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        std::fs::read_link(arg_path)
+    }
+
+    #[cfg(unix)]
+    fn symlink(&self, arg_path_target: &Path, arg_path_link: &Path) -> std::io::Result<()> {
+        std::os::unix::fs::symlink(arg_path_target, arg_path_link)
+    }
+
+    #[cfg(windows)]
+    fn symlink(&self, arg_path_target: &Path, arg_path_link: &Path) -> std::io::Result<()> {
+        let path_buf_target = match arg_path_link.parent() {
+            Some(path_parent) => path_parent.join(arg_path_target),
+            None => arg_path_target.to_path_buf(),
+        };
+        if path_buf_target.is_dir() {
+            std::os::windows::fs::symlink_dir(arg_path_target, arg_path_link)
+        } else {
+            std::os::windows::fs::symlink_file(arg_path_target, arg_path_link)
+        }
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn symlink(&self, _arg_path_target: &Path, _arg_path_link: &Path) -> std::io::Result<()> {
+        Err(Error::from(ErrorKind::Unsupported))
+    }
+
+    fn canonicalize(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        std::fs::canonicalize(arg_path)
+    }
+
+    fn exists(&self, arg_path: &Path) -> bool {
+        arg_path.exists()
+    }
+}
+
+/// A filesystem held in memory, for tests that shouldn't depend on the disk
+/// It has unix semantics: '/' is the only root, relative paths are relative to '/', and symlinks are
+/// resolved component by component, so '..' after a symlink goes up from the symlink's target.
+/// Errors can be injected with with_error(), so any operation that reaches that path fails, ie to
+/// simulate EACCES partway through a walk.
+/// # Examples
+/// let in_memory_fs = InMemoryFs::new()
+///     .with_file( &"/project/src/main.rs", "fn main() {}" )
+///     .with_symlink( &"src", &"/project/link" )
+///     .with_error( &"/project/secret", std::io::ErrorKind::PermissionDenied );
+/// let vec_of_paths = get_paths_in_dir_and_sub_dirs_with_fs( &in_memory_fs, &"/project" ).unwrap();
+#[derive(Debug)]
+pub struct InMemoryFs {
+    mutex_of_nodes: Mutex<BTreeMap<PathBuf, InMemoryNode>>,
+    mutex_of_errors: Mutex<BTreeMap<PathBuf, ErrorKind>>,
+}
+
+impl Default for InMemoryFs {
+    fn default() -> Self {
+        let mut map_of_nodes = BTreeMap::new();
+        map_of_nodes.insert(PathBuf::from("/"), InMemoryNode::new(InMemoryKind::Dir));
+        InMemoryFs {
+            mutex_of_nodes: Mutex::new(map_of_nodes),
+            mutex_of_errors: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl InMemoryFs {
+    /// Returns a filesystem containing only the root directory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a directory, creating missing parents
+    /// # Arguments
+    /// * arg_string_path: string-like path
+    pub fn with_dir<T: std::fmt::Display>(self, arg_string_path: &T) -> Self {
+        self.insert_with_parents(arg_string_path, InMemoryKind::Dir);
+        self
+    }
+
+    /// Adds a file, creating missing parents and replacing anything already there
+    /// # Arguments
+    /// * arg_string_path: string-like path
+    /// * arg_contents: bytes or a string
+    pub fn with_file<T: std::fmt::Display, C: AsRef<[u8]>>(
+        self,
+        arg_string_path: &T,
+        arg_contents: C,
+    ) -> Self {
+        self.insert_with_parents(
+            arg_string_path,
+            InMemoryKind::File(arg_contents.as_ref().to_vec()),
+        );
+        self
+    }
+
+    /// Adds a symlink at arg_string_path_link pointing at arg_string_path_target, creating missing parents
+    /// The target is stored as given, so a relative target is resolved from the link's directory.
+    /// # Arguments
+    /// * arg_string_path_target: string-like path, which needn't exist
+    /// * arg_string_path_link: string-like path
+    pub fn with_symlink<T1: std::fmt::Display, T2: std::fmt::Display>(
+        self,
+        arg_string_path_target: &T1,
+        arg_string_path_link: &T2,
+    ) -> Self {
+        self.insert_with_parents(
+            arg_string_path_link,
+            InMemoryKind::Symlink(PathBuf::from(format!("{}", arg_string_path_target,))),
+        );
+        self
+    }
+
+    /// Makes every operation that reaches arg_string_path fail with arg_error_kind
+    /// # Arguments
+    /// * arg_string_path: string-like path
+    /// * arg_error_kind: ie ErrorKind::PermissionDenied for EACCES or ErrorKind::NotFound for ENOENT
+    pub fn with_error<T: std::fmt::Display>(
+        self,
+        arg_string_path: &T,
+        arg_error_kind: ErrorKind,
+    ) -> Self {
+        self.lock_errors().insert(
+            get_path_buf_key(Path::new(&format!("{}", arg_string_path,))),
+            arg_error_kind,
+        );
+        self
+    }
+
+    /// Sets the modification time of an existing entry
+    /// # Arguments
+    /// * arg_string_path: string-like path
+    /// * arg_system_time: SystemTime
+    pub fn with_modified<T: std::fmt::Display>(
+        self,
+        arg_string_path: &T,
+        arg_system_time: SystemTime,
+    ) -> Self {
+        let path_buf = get_path_buf_key(Path::new(&format!("{}", arg_string_path,)));
+        if let Some(node) = self.lock_nodes().get_mut(&path_buf) {
+            node.modified = arg_system_time;
+        }
+        self
+    }
+
+    /// Makes an existing entry read-only, so writing or removing it fails with PermissionDenied
+    /// # Arguments
+    /// * arg_string_path: string-like path
+    pub fn with_readonly<T: std::fmt::Display>(self, arg_string_path: &T) -> Self {
+        let path_buf = get_path_buf_key(Path::new(&format!("{}", arg_string_path,)));
+        if let Some(node) = self.lock_nodes().get_mut(&path_buf) {
+            node.readonly = true;
+        }
+        self
+    }
+}
+
+impl FileSystem for InMemoryFs {
+    fn metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        let map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, true)?;
+        get_node(&map_of_nodes, &path_buf).map(InMemoryNode::get_metadata)
+    }
+
+    fn symlink_metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        let map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, false)?;
+        get_node(&map_of_nodes, &path_buf).map(InMemoryNode::get_metadata)
+    }
+
+    fn read_dir(&self, arg_path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, true)?;
+        if !matches!(get_node(&map_of_nodes, &path_buf)?.kind, InMemoryKind::Dir) {
+            return Err(Error::from(ErrorKind::NotADirectory));
+        }
+        Ok(get_vec_of_children(&map_of_nodes, &path_buf)
+            .into_iter()
+            .map(|item_os_string_name| arg_path.join(item_os_string_name))
+            .collect())
+    }
+
+    fn read(&self, arg_path: &Path) -> std::io::Result<Vec<u8>> {
+        let map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, true)?;
+        match &get_node(&map_of_nodes, &path_buf)?.kind {
+            InMemoryKind::File(vec_of_bytes) => Ok(vec_of_bytes.clone()),
+            _ => Err(Error::from(ErrorKind::IsADirectory)),
+        }
+    }
+
+    fn write(&self, arg_path: &Path, arg_slice_of_bytes: &[u8]) -> std::io::Result<()> {
+        let mut map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, true)?;
+        match map_of_nodes.get(&path_buf) {
+            Some(node) if node.readonly => return Err(Error::from(ErrorKind::PermissionDenied)),
+            Some(node) if matches!(node.kind, InMemoryKind::Dir) => {
+                return Err(Error::from(ErrorKind::IsADirectory))
+            }
+            Some(_) => {}
+            None => check_parent_is_dir(&map_of_nodes, &path_buf)?,
+        }
+        map_of_nodes.insert(
+            path_buf,
+            InMemoryNode::new(InMemoryKind::File(arg_slice_of_bytes.to_vec())),
+        );
+        Ok(())
+    }
+
+    fn create_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        let mut map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, false)?;
+        if map_of_nodes.contains_key(&path_buf) {
+            return Err(Error::from(ErrorKind::AlreadyExists));
+        }
+        check_parent_is_dir(&map_of_nodes, &path_buf)?;
+        map_of_nodes.insert(path_buf, InMemoryNode::new(InMemoryKind::Dir));
+        Ok(())
+    }
+
+    fn remove_file(&self, arg_path: &Path) -> std::io::Result<()> {
+        let mut map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, false)?;
+        let node = get_node(&map_of_nodes, &path_buf)?;
+        if node.readonly {
+            return Err(Error::from(ErrorKind::PermissionDenied));
+        }
+        if matches!(node.kind, InMemoryKind::Dir) {
+            return Err(Error::from(ErrorKind::IsADirectory));
+        }
+        map_of_nodes.remove(&path_buf);
+        Ok(())
+    }
+
+    fn remove_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        let mut map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, false)?;
+        let node = get_node(&map_of_nodes, &path_buf)?;
+        if node.readonly || path_buf == Path::new("/") {
+            return Err(Error::from(ErrorKind::PermissionDenied));
+        }
+        if !matches!(node.kind, InMemoryKind::Dir) {
+            return Err(Error::from(ErrorKind::NotADirectory));
+        }
+        if !get_vec_of_children(&map_of_nodes, &path_buf).is_empty() {
+            return Err(Error::from(ErrorKind::DirectoryNotEmpty));
+        }
+        map_of_nodes.remove(&path_buf);
+        Ok(())
+    }
+
+    fn read_link(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        let map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, false)?;
+        match &get_node(&map_of_nodes, &path_buf)?.kind {
+            InMemoryKind::Symlink(path_buf_target) => Ok(path_buf_target.clone()),
+            _ => Err(Error::from(ErrorKind::InvalidInput)),
+        }
+    }
+
+    fn symlink(&self, arg_path_target: &Path, arg_path_link: &Path) -> std::io::Result<()> {
+        let mut map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path_link, false)?;
+        if map_of_nodes.contains_key(&path_buf) {
+            return Err(Error::from(ErrorKind::AlreadyExists));
+        }
+        check_parent_is_dir(&map_of_nodes, &path_buf)?;
+        map_of_nodes.insert(
+            path_buf,
+            InMemoryNode::new(InMemoryKind::Symlink(arg_path_target.to_path_buf())),
+        );
+        Ok(())
+    }
+
+    fn canonicalize(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        let map_of_nodes = self.lock_nodes();
+        let path_buf = self.get_path_buf_resolved(&map_of_nodes, arg_path, true)?;
+        get_node(&map_of_nodes, &path_buf)?;
+        Ok(path_buf)
+    }
+}
+//
+// Private
+//
+#[derive(Clone, Debug)]
+enum InMemoryKind {
+    Dir,
+    File(Vec<u8>),
+    Symlink(PathBuf),
+}
+
+#[derive(Clone, Debug)]
+struct InMemoryNode {
+    kind: InMemoryKind,
+    modified: SystemTime,
+    readonly: bool,
+}
+
+impl InMemoryNode {
+    fn new(arg_kind: InMemoryKind) -> Self {
+        InMemoryNode {
+            kind: arg_kind,
+            modified: SystemTime::now(),
+            readonly: false,
+        }
+    }
+
+    fn get_metadata(&self) -> FsMetadata {
+        let (file_type, int_len) = match &self.kind {
+            InMemoryKind::Dir => (FsFileType::Dir, 0),
+            InMemoryKind::File(vec_of_bytes) => (FsFileType::File, vec_of_bytes.len() as u64),
+            InMemoryKind::Symlink(_) => (FsFileType::Symlink, 0),
+        };
+        FsMetadata {
+            file_type,
+            len: int_len,
+            modified: Some(self.modified),
+            readonly: self.readonly,
+        }
+    }
+}
+
+impl InMemoryFs {
+    /// Returns the path with every symlink before the last component resolved, and the last one too
+    /// if arg_bool_follow_last is set
+    /// The last component needn't exist, so callers can create it; anything before it must be a
+    /// directory. Injected errors are raised for every path reached on the way.
+    fn get_path_buf_resolved(
+        &self,
+        arg_map_of_nodes: &BTreeMap<PathBuf, InMemoryNode>,
+        arg_path: &Path,
+        arg_bool_follow_last: bool,
+    ) -> std::io::Result<PathBuf> {
+        let map_of_errors = self.lock_errors();
+        get_path_buf_resolved_with(
+            &get_path_buf_without_prefix(&get_path_buf_absolute(arg_path)),
+            arg_bool_follow_last,
+            false,
+            |path_candidate| {
+                if let Some(error_kind) = map_of_errors.get(path_candidate) {
+                    return Err(Error::from(*error_kind));
+                }
+                arg_map_of_nodes
+                    .get(path_candidate)
+                    .map(InMemoryNode::get_metadata)
+                    .ok_or_else(|| Error::from(ErrorKind::NotFound))
+            },
+            |path_candidate| match arg_map_of_nodes.get(path_candidate).map(|node| &node.kind) {
+                Some(InMemoryKind::Symlink(path_buf_target)) => {
+                    Ok(get_path_buf_without_prefix(path_buf_target))
+                }
+                _ => Err(Error::from(ErrorKind::InvalidInput)),
+            },
+        )
+    }
+
+    /// Inserts the node, turning every missing or non-directory ancestor into a directory
+    fn insert_with_parents<T: std::fmt::Display>(
+        &self,
+        arg_string_path: &T,
+        arg_kind: InMemoryKind,
+    ) {
+        let path_buf = get_path_buf_key(Path::new(&format!("{}", arg_string_path,)));
+        let mut map_of_nodes = self.lock_nodes();
+        for item_path_ancestor in path_buf.ancestors().skip(1) {
+            let bool_is_dir = matches!(
+                map_of_nodes.get(item_path_ancestor).map(|node| &node.kind),
+                Some(InMemoryKind::Dir)
+            );
+            if !bool_is_dir {
+                map_of_nodes.insert(
+                    item_path_ancestor.to_path_buf(),
+                    InMemoryNode::new(InMemoryKind::Dir),
+                );
+            }
+        }
+        map_of_nodes.insert(path_buf, InMemoryNode::new(arg_kind));
+    }
+
+    fn lock_errors(&self) -> std::sync::MutexGuard<'_, BTreeMap<PathBuf, ErrorKind>> {
+        self.mutex_of_errors
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    fn lock_nodes(&self) -> std::sync::MutexGuard<'_, BTreeMap<PathBuf, InMemoryNode>> {
+        self.mutex_of_nodes
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+fn check_parent_is_dir(
+    arg_map_of_nodes: &BTreeMap<PathBuf, InMemoryNode>,
+    arg_path_buf: &Path,
+) -> std::io::Result<()> {
+    match arg_path_buf.parent() {
+        Some(path_parent) => match get_node(arg_map_of_nodes, path_parent)?.kind {
+            InMemoryKind::Dir => Ok(()),
+            _ => Err(Error::from(ErrorKind::NotADirectory)),
+        },
+        None => Err(Error::from(ErrorKind::AlreadyExists)),
+    }
+}
+
+fn get_node<'a>(
+    arg_map_of_nodes: &'a BTreeMap<PathBuf, InMemoryNode>,
+    arg_path: &Path,
+) -> std::io::Result<&'a InMemoryNode> {
+    arg_map_of_nodes
+        .get(arg_path)
+        .ok_or_else(|| Error::from(ErrorKind::NotFound))
+}
+
+/// Relative paths are relative to the root, as InMemoryFs has no cwd
+fn get_path_buf_absolute(arg_path: &Path) -> PathBuf {
+    Path::new("/").join(arg_path)
+}
+
+/// Returns the absolute path with '.' and '..' resolved lexically, for the builder methods
+fn get_path_buf_key(arg_path: &Path) -> PathBuf {
+    let mut path_buf_to_return = PathBuf::from("/");
+    for item_component in arg_path.components() {
+        match item_component {
+            Component::ParentDir => {
+                path_buf_to_return.pop();
+            }
+            Component::Normal(os_str) => path_buf_to_return.push(os_str),
+            Component::RootDir | Component::CurDir | Component::Prefix(_) => {}
+        }
+    }
+    path_buf_to_return
+}
+
+fn get_vec_of_children(
+    arg_map_of_nodes: &BTreeMap<PathBuf, InMemoryNode>,
+    arg_path_dir: &Path,
+) -> Vec<OsString> {
+    arg_map_of_nodes
+        .range(arg_path_dir.to_path_buf()..)
+        .skip(1)
+        .take_while(|(item_path_buf, _)| item_path_buf.starts_with(arg_path_dir))
+        .filter(|(item_path_buf, _)| item_path_buf.parent() == Some(arg_path_dir))
+        .filter_map(|(item_path_buf, _)| {
+            item_path_buf
+                .file_name()
+                .map(|os_str| os_str.to_os_string())
+        })
+        .collect()
+}
+
+/// Returns the path with every symlink resolved through the closures, the way the OS would walk it
+/// Symlinks before the last component are always followed, and the last one too if
+/// arg_bool_follow_last is set. '..' at the root stays at the root, and a Windows prefix is kept.
+/// The last component needn't exist, so callers can create it. Before it, a missing component is an
+/// error, or with arg_bool_missing_is_lexical it and the rest are applied lexically, leaving any other
+/// error to the closures.
+pub(crate) fn get_path_buf_resolved_with<FM, FL>(
+    arg_path: &Path,
+    arg_bool_follow_last: bool,
+    arg_bool_missing_is_lexical: bool,
+    arg_closure_symlink_metadata: FM,
+    arg_closure_read_link: FL,
+) -> std::io::Result<PathBuf>
+where
+    FM: Fn(&Path) -> std::io::Result<FsMetadata>,
+    FL: Fn(&Path) -> std::io::Result<PathBuf>,
+{
+    const INT_SYMLINKS_MAX: usize = 40;
+    let mut stack_of_components: Vec<PathBuf> = vec![];
+    push_components_reversed(&mut stack_of_components, arg_path);
+    let mut path_buf_current = PathBuf::from("/");
+    let mut int_symlinks = 0;
+    while let Some(path_buf_component) = stack_of_components.pop() {
+        let path_buf_candidate = match path_buf_component.components().next() {
+            Some(Component::Prefix(_)) => {
+                path_buf_current = path_buf_component;
+                continue;
+            }
+            Some(Component::RootDir) => {
+                // Keep any drive prefix, and start again from its root
+                path_buf_current = path_buf_current
+                    .components()
+                    .take_while(|component| matches!(component, Component::Prefix(_)))
+                    .collect::<PathBuf>();
+                path_buf_current.push(Component::RootDir);
+                continue;
+            }
+            Some(Component::ParentDir) => {
+                path_buf_current.pop();
+                continue;
+            }
+            Some(Component::Normal(_)) => path_buf_current.join(&path_buf_component),
+            Some(Component::CurDir) | None => continue,
+        };
+        let bool_is_last = stack_of_components.is_empty();
+        match arg_closure_symlink_metadata(&path_buf_candidate) {
+            Ok(metadata) if metadata.is_symlink() && (!bool_is_last || arg_bool_follow_last) => {
+                int_symlinks += 1;
+                if int_symlinks > INT_SYMLINKS_MAX {
+                    return Err(Error::other("too many levels of symbolic links"));
+                }
+                push_components_reversed(
+                    &mut stack_of_components,
+                    &arg_closure_read_link(&path_buf_candidate)?,
+                );
+            }
+            Ok(metadata) if !bool_is_last && !arg_bool_missing_is_lexical && !metadata.is_dir() => {
+                return Err(Error::from(ErrorKind::NotADirectory))
+            }
+            Ok(_) => path_buf_current = path_buf_candidate,
+            Err(err)
+                if (bool_is_last || arg_bool_missing_is_lexical)
+                    && err.kind() == ErrorKind::NotFound =>
+            {
+                path_buf_current = path_buf_candidate
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(path_buf_current)
+}
+
+/// Returns the path without its Windows prefix, for paths that are always rooted at '/'
+pub(crate) fn get_path_buf_without_prefix(arg_path: &Path) -> PathBuf {
+    arg_path
+        .components()
+        .filter(|component| !matches!(component, Component::Prefix(_)))
+        .collect::<PathBuf>()
+}
+
+/// Pushes each component as its own path, so the last one pushed comes out first
+fn push_components_reversed(arg_stack_of_components: &mut Vec<PathBuf>, arg_path: &Path) {
+    let vec_of_components = arg_path
+        .components()
+        .map(|component| PathBuf::from(component.as_os_str()))
+        .collect::<Vec<PathBuf>>();
+    arg_stack_of_components.extend(vec_of_components.into_iter().rev());
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_in_memory_fs() -> InMemoryFs {
+        InMemoryFs::new()
+            .with_file(&"/project/src/main.rs", "fn main() {}")
+            .with_dir(&"/project/empty")
+            .with_symlink(&"src", &"/project/link")
+            .with_symlink(&"../src/main.rs", &"/project/empty/../link_main")
+            .with_symlink(&"/loop_b", &"/loop_a")
+            .with_symlink(&"/loop_a", &"/loop_b")
+    }
+
+    #[test]
+    fn test_in_memory_fs_resolution() {
+        let in_memory_fs = get_in_memory_fs();
+        assert!(in_memory_fs.is_dir(Path::new("/project/link")));
+        assert!(in_memory_fs
+            .symlink_metadata(Path::new("/project/link"))
+            .unwrap()
+            .is_symlink());
+        assert_eq!(
+            in_memory_fs
+                .read(Path::new("project/link/main.rs"))
+                .unwrap(),
+            b"fn main() {}"
+        );
+        // '..' after a symlink goes up from the target, like on a real filesystem
+        assert_eq!(
+            in_memory_fs
+                .canonicalize(Path::new("/project/link/../empty"))
+                .unwrap(),
+            PathBuf::from("/project/empty")
+        );
+        assert_eq!(
+            in_memory_fs.read_link(Path::new("/project/link")).unwrap(),
+            PathBuf::from("src")
+        );
+        assert!(in_memory_fs.metadata(Path::new("/loop_a")).is_err());
+        assert_eq!(
+            in_memory_fs
+                .metadata(Path::new("/project/src/main.rs/x"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotADirectory
+        );
+        assert_eq!(
+            in_memory_fs.read_dir(Path::new("/project")).unwrap(),
+            vec![
+                PathBuf::from("/project/empty"),
+                PathBuf::from("/project/link"),
+                PathBuf::from("/project/link_main"),
+                PathBuf::from("/project/src"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_in_memory_fs_mutation() {
+        let in_memory_fs = get_in_memory_fs()
+            .with_readonly(&"/project/src/main.rs")
+            .with_error(&"/project/secret", ErrorKind::PermissionDenied);
+        in_memory_fs
+            .create_dir_all(Path::new("/project/a/b"))
+            .unwrap();
+        in_memory_fs
+            .write(Path::new("/project/a/b/c.txt"), b"abc")
+            .unwrap();
+        assert_eq!(
+            in_memory_fs
+                .metadata(Path::new("/project/a/b/c.txt"))
+                .unwrap()
+                .len,
+            3
+        );
+        assert_eq!(
+            in_memory_fs
+                .remove_dir(Path::new("/project/a/b"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DirectoryNotEmpty
+        );
+        in_memory_fs
+            .remove_file(Path::new("/project/a/b/c.txt"))
+            .unwrap();
+        in_memory_fs.remove_dir(Path::new("/project/a/b")).unwrap();
+        assert_eq!(
+            in_memory_fs
+                .write(Path::new("/project/src/main.rs"), b"")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            in_memory_fs
+                .write(Path::new("/missing/x"), b"")
+                .unwrap_err()
+                .kind(),
+            ErrorKind::NotFound
+        );
+        assert_eq!(
+            in_memory_fs
+                .create_dir(Path::new("/project/secret"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::PermissionDenied
+        );
+        in_memory_fs
+            .symlink(Path::new("nowhere"), Path::new("/project/broken"))
+            .unwrap();
+        assert!(!in_memory_fs.exists(Path::new("/project/broken")));
+        assert!(in_memory_fs
+            .symlink_metadata(Path::new("/project/broken"))
+            .is_ok());
+    }
+}
@@ -0,0 +1,379 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::path::Path;
+//
+// Libraries - local
+//
+use crate::windows::{WindowsPath, WindowsPrefix};
+use crate::{get_path_buf_absolute_with_normalization, PathNormalization};
+//
+// Public - get - urls
+//
+/// Returns a file:// URL for arg_string_path
+/// Relative paths are joined onto the cwd and normalized first, without touching the disk, so they needn't
+/// exist. Every byte outside A-Z, a-z, 0-9 and '-', '.', '_', '~' is percent-encoded, so spaces, '#', '%'
+/// and non-ASCII characters survive.
+/// Windows paths are recognised on every platform: 'C:\a' becomes "file:///C:/a" and '\\server\share\a'
+/// becomes "file://server/share/a".
+/// In case of a failure ( ie a drive-relative path like 'C:a' ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let string_url = match get_file_url_from_path( &"/tmp/my file#1.txt" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_url = "file:///tmp/my%20file%231.txt"
+pub fn get_file_url_from_path<T: Debug + Display>(arg_string_path: &T) -> Result<String, String> {
+    let string_path = format!("{}", arg_string_path,);
+    if is_windows_path(&string_path) {
+        let string_path = if cfg!(windows) && !Path::new(&string_path).is_absolute() {
+            get_path_absolute_lexical(&string_path)?
+        } else {
+            string_path
+        };
+        return get_file_url_from_windows_path(&string_path, arg_string_path);
+    }
+    let string_path = get_path_absolute_lexical(&string_path)?;
+    Ok(format!(
+        "file://{}",
+        string_path
+            .split('/')
+            .map(get_string_percent_encoded)
+            .collect::<Vec<String>>()
+            .join("/"),
+    ))
+}
+
+/// Returns the path a file:// URL points at
+/// Percent-escapes are decoded, and any query ( '?...' ) or fragment ( '#...' ) is dropped.
+/// A URL with a drive letter ( "file:///C:/a" ) returns a Windows path ( 'C:\a' ), and one with a host
+/// other than localhost ( "file://server/share/a" ) returns a UNC path ( '\\server\share\a' ), whatever
+/// the platform.
+/// In case of a failure ( wrong scheme, an escape that decodes to '/' or NUL, a '\' in a Windows path,
+/// invalid UTF-8 ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_url: string-like URL
+/// # Examples
+/// let string_path = match get_path_from_file_url( &"file:///tmp/my%20file%231.txt" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "/tmp/my file#1.txt"
+pub fn get_path_from_file_url<T: Debug + Display>(arg_string_url: &T) -> Result<String, String> {
+    let string_url = format!("{}", arg_string_url,);
+    let str_after_scheme = match string_url.get(..5) {
+        Some(str_scheme) if str_scheme.eq_ignore_ascii_case("file:") => &string_url[5..],
+        _ => {
+            return Err([
+                "Error: URL doesn't use the file scheme.".to_string(),
+                format!("arg_string_url = {}", arg_string_url,),
+            ]
+            .join("\n"))
+        }
+    };
+    let str_after_scheme = match str_after_scheme.find(['?', '#']) {
+        Some(int_index) => &str_after_scheme[..int_index],
+        None => str_after_scheme,
+    };
+    let (str_host, str_path) = match str_after_scheme.strip_prefix("//") {
+        Some(str_after_slashes) => {
+            let int_index = str_after_slashes
+                .find('/')
+                .unwrap_or(str_after_slashes.len());
+            (
+                &str_after_slashes[..int_index],
+                &str_after_slashes[int_index..],
+            )
+        }
+        None => ("", str_after_scheme),
+    };
+    // 'file://server' is a bare host, so it's the only URL allowed an empty path
+    let bool_is_path_valid =
+        str_path.starts_with('/') || (str_path.is_empty() && !str_host.is_empty());
+    if !bool_is_path_valid {
+        return Err([
+            "Error: file URL doesn't contain an absolute path.".to_string(),
+            format!("arg_string_url = {}", arg_string_url,),
+        ]
+        .join("\n"));
+    }
+    let string_host = get_string_percent_decoded(str_host, arg_string_url)?;
+    let vec_of_segments = str_path
+        .split('/')
+        .skip(1)
+        .map(|item_str_segment| get_string_percent_decoded(item_str_segment, arg_string_url))
+        .collect::<Result<Vec<String>, String>>()?;
+    let bool_is_local = string_host.is_empty() || string_host.eq_ignore_ascii_case("localhost");
+    let option_char_drive = match vec_of_segments.first() {
+        Some(string_segment) => get_option_char_drive(string_segment),
+        None => None,
+    };
+    // '\' is an ordinary byte in POSIX names, but in a Windows path it would be a separator
+    let bool_is_windows = !bool_is_local || option_char_drive.is_some();
+    if bool_is_windows
+        && (string_host.contains('\\')
+            || vec_of_segments
+                .iter()
+                .any(|item_string_segment| item_string_segment.contains('\\')))
+    {
+        return Err([
+            "Error: file URL for a Windows path contains a '\\'.".to_string(),
+            format!("arg_string_url = {}", arg_string_url,),
+        ]
+        .join("\n"));
+    }
+    match (bool_is_local, option_char_drive) {
+        (true, Some(char_drive)) => {
+            let vec_of_components = &vec_of_segments[1..];
+            Ok(format!("{}:\\{}", char_drive, vec_of_components.join("\\"),))
+        }
+        (true, None) => Ok(format!("/{}", vec_of_segments.join("/"),)),
+        (false, None) => Ok(format!(
+            "\\\\{}\\{}",
+            string_host,
+            vec_of_segments.join("\\"),
+        )),
+        (false, Some(_)) => Err([
+            "Error: file URL has both a host and a drive letter.".to_string(),
+            format!("arg_string_url = {}", arg_string_url,),
+        ]
+        .join("\n")),
+    }
+}
+//
+// Private
+//
+fn get_file_url_from_windows_path<T: Debug + Display>(
+    arg_str_path: &str,
+    arg_string_path_original: &T,
+) -> Result<String, String> {
+    let windows_path = WindowsPath::new(&arg_str_path).normalized();
+    let str_components = windows_path
+        .components()
+        .iter()
+        .map(|item_string| get_string_percent_encoded(item_string))
+        .collect::<Vec<String>>()
+        .join("/");
+    match windows_path.prefix() {
+        WindowsPrefix::Disk(char_drive) | WindowsPrefix::VerbatimDisk(char_drive)
+            if windows_path.has_root() =>
+        {
+            Ok(format!("file:///{}:/{}", char_drive, str_components,))
+        }
+        WindowsPrefix::Unc(string_server, string_share)
+        | WindowsPrefix::VerbatimUnc(string_server, string_share) => Ok(format!(
+            "file://{}/{}/{}",
+            get_string_percent_encoded(string_server),
+            get_string_percent_encoded(string_share),
+            str_components,
+        )
+        .trim_end_matches('/')
+        .to_string()),
+        _ => Err([
+            "Error: Windows path has no drive letter and root, or UNC share, so it has no file URL."
+                .to_string(),
+            format!("arg_string_path = {}", arg_string_path_original,),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Returns the path with a leading '~' expanded, joined onto the cwd if it's relative, and normalized
+/// Unlike get_absolute_path(), this never leaves a path relative because it doesn't exist.
+fn get_path_absolute_lexical(arg_str_path: &str) -> Result<String, String> {
+    Ok(
+        get_path_buf_absolute_with_normalization(&arg_str_path, PathNormalization::Lexical)?
+            .to_string_lossy()
+            .into_owned(),
+    )
+}
+
+/// Returns the drive letter if the segment is 'C:' or the older 'C|'
+fn get_option_char_drive(arg_str_segment: &str) -> Option<char> {
+    let mut iter_of_chars = arg_str_segment.chars();
+    match (
+        iter_of_chars.next(),
+        iter_of_chars.next(),
+        iter_of_chars.next(),
+    ) {
+        (Some(char_drive), Some(':' | '|'), None) if char_drive.is_ascii_alphabetic() => {
+            Some(char_drive)
+        }
+        _ => None,
+    }
+}
+
+fn get_string_percent_decoded<T: Debug + Display>(
+    arg_str: &str,
+    arg_string_url: &T,
+) -> Result<String, String> {
+    let vec_of_bytes_in = arg_str.as_bytes();
+    let mut vec_of_bytes_out = Vec::with_capacity(vec_of_bytes_in.len());
+    let mut int_index = 0;
+    while int_index < vec_of_bytes_in.len() {
+        if vec_of_bytes_in[int_index] != b'%' {
+            vec_of_bytes_out.push(vec_of_bytes_in[int_index]);
+            int_index += 1;
+            continue;
+        }
+        let option_int_byte = arg_str
+            .get(int_index + 1..int_index + 3)
+            .and_then(|str_hex| u8::from_str_radix(str_hex, 16).ok());
+        match option_int_byte {
+            Some(b'/' | 0) => {
+                return Err([
+                    "Error: file URL contains an escaped separator or NUL.".to_string(),
+                    format!("arg_string_url = {}", arg_string_url,),
+                ]
+                .join("\n"))
+            }
+            Some(int_byte) => vec_of_bytes_out.push(int_byte),
+            None => {
+                return Err([
+                    "Error: '%' must be followed by two hex digits.".to_string(),
+                    format!("arg_string_url = {}", arg_string_url,),
+                ]
+                .join("\n"))
+            }
+        }
+        int_index += 3;
+    }
+    match String::from_utf8(vec_of_bytes_out) {
+        Ok(string_result) => Ok(string_result),
+        Err(err) => Err([
+            "Error: file URL decodes to invalid UTF-8.".to_string(),
+            format!("err = {}", err,),
+            format!("arg_string_url = {}", arg_string_url,),
+        ]
+        .join("\n")),
+    }
+}
+
+fn get_string_percent_encoded(arg_str: &str) -> String {
+    let mut string_to_return = String::with_capacity(arg_str.len());
+    for item_byte in arg_str.bytes() {
+        if item_byte.is_ascii_alphanumeric() || matches!(item_byte, b'-' | b'.' | b'_' | b'~') {
+            string_to_return.push(item_byte as char);
+        } else {
+            string_to_return.push_str(&format!("%{:02X}", item_byte,));
+        }
+    }
+    string_to_return
+}
+
+/// Returns true if the path should be read with Windows rules
+/// On unix, '//server/share' is a valid POSIX path, so only drive letters and '\\' prefixes count.
+fn is_windows_path(arg_str_path: &str) -> bool {
+    if cfg!(windows) || arg_str_path.starts_with("\\\\") {
+        return true;
+    }
+    matches!(
+        WindowsPath::new(&arg_str_path).prefix(),
+        WindowsPrefix::Disk(_)
+    )
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_file_url_from_path() {
+        for (item_str_path, item_str_expected) in [
+            (
+                "/tmp/my file#1 100%.txt",
+                "file:///tmp/my%20file%231%20100%25.txt",
+            ),
+            ("/tmp/caf\u{e9}/./x/../y", "file:///tmp/caf%C3%A9/y"),
+            ("C:\\Users\\Me\\a b.txt", "file:///C:/Users/Me/a%20b.txt"),
+            (
+                "\\\\server\\share\\dir\\file",
+                "file://server/share/dir/file",
+            ),
+        ] {
+            assert_eq!(
+                get_file_url_from_path(&item_str_path),
+                Ok(item_str_expected.to_string())
+            );
+        }
+        assert_eq!(
+            get_file_url_from_path(&"test"),
+            Ok(format!(
+                "file://{}",
+                crate::get_absolute_path(&"test").replace(' ', "%20")
+            ))
+        );
+        assert!(get_file_url_from_path(&"C:relative").is_err());
+        let string_url = get_file_url_from_path(&"missing_dir/../missing_dir/x.txt").unwrap();
+        assert!(string_url.starts_with("file:///"), "{}", string_url);
+        assert!(string_url.ends_with("/missing_dir/x.txt"), "{}", string_url);
+        assert_eq!(
+            get_path_from_file_url(&string_url),
+            Ok(format!(
+                "{}/missing_dir/x.txt",
+                std::env::current_dir().unwrap().display(),
+            ))
+        );
+    }
+
+    #[test]
+    fn test_get_path_from_file_url() {
+        for (item_str_url, item_str_expected) in [
+            (
+                "file:///tmp/my%20file%231%20100%25.txt",
+                "/tmp/my file#1 100%.txt",
+            ),
+            ("file://localhost/tmp/caf%C3%A9", "/tmp/caf\u{e9}"),
+            ("file:/tmp/a", "/tmp/a"),
+            ("FILE:///tmp/a?query#fragment", "/tmp/a"),
+            ("file:///C:/Users/Me/a%20b.txt", "C:\\Users\\Me\\a b.txt"),
+            ("file:///c|/a", "c:\\a"),
+            ("file:///tmp/a%5Cb", "/tmp/a\\b"),
+            (
+                "file://server/share/dir/file",
+                "\\\\server\\share\\dir\\file",
+            ),
+        ] {
+            assert_eq!(
+                get_path_from_file_url(&item_str_url),
+                Ok(item_str_expected.to_string())
+            );
+        }
+        for item_str_url in [
+            "http://example.com/a",
+            "file:///a%2Fb",
+            "file:///C:/a%5Cb",
+            "file://server/share/a%5Cb",
+            "file:///a%00b",
+            "file:///a%zz",
+            "file:///a%FF",
+            "file:relative",
+        ] {
+            assert!(
+                get_path_from_file_url(&item_str_url).is_err(),
+                "{}",
+                item_str_url,
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for item_str_path in [
+            "/tmp/a b/#%?/\u{1F600}",
+            "/tmp/back\\slash",
+            "C:\\a\\b c",
+            "\\\\server\\share\\a",
+        ] {
+            assert_eq!(
+                get_path_from_file_url(&get_file_url_from_path(&item_str_path).unwrap()),
+                Ok(item_str_path.to_string())
+            );
+        }
+    }
+}
@@ -0,0 +1,819 @@
+//
+// Libraries - native
+//
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::fmt::{Debug, Display};
+use std::fs::{Metadata, ReadDir};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+//
+// Libraries - local
+//
+use crate::encoding::{get_string_from_path_with_encoding, PathEncoding};
+use crate::{
+    get_absolute_path, get_dir_proj_root, is_path_inside_dir_parent,
+    raise_error_if_path_points_to_cargo_toml, raise_error_if_path_points_to_main_rs,
+    raise_error_if_path_points_to_project_root, raise_error_if_path_points_to_src,
+};
+//
+// Public - structs
+//
+/// The kind of disk entry a Find query should match
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FindType {
+    Dir,
+    File,
+    Symlink,
+}
+
+/// Query builder modelled after GNU find
+/// Each filter narrows the results further; all filters must match for a path to be returned.
+/// Depth counts the same way GNU find does: the root is depth 0, its children are depth 1.
+/// By default the root itself is excluded (min depth 1), matching get_paths_in_dir_and_sub_dirs()
+#[derive(Clone, Debug)]
+pub struct Find {
+    path_buf_root: PathBuf,
+    vec_of_globs_name: Vec<String>,
+    vec_of_globs_name_case_insensitive: Vec<String>,
+    option_duration_newer_than: Option<Duration>,
+    option_duration_older_than: Option<Duration>,
+    option_int_size_gt: Option<u64>,
+    option_int_size_lt: Option<u64>,
+    option_find_type: Option<FindType>,
+    option_int_depth_max: Option<usize>,
+    int_depth_min: usize,
+    bool_follow_symlinks: bool,
+    path_encoding: PathEncoding,
+}
+
+/// Lazy iterator returned by Find::into_iter()
+/// Directories are only read once the iterator reaches them.
+/// Entries that can't be read, or can't be converted to a String under the query's PathEncoding,
+/// are returned as errors, and iteration continues afterwards.
+pub struct FindIter {
+    find: Find,
+    bool_root_pending: bool,
+    stack_of_read_dirs: Vec<(ReadDir, usize)>,
+    option_string_err_pending: Option<String>,
+    set_of_path_bufs_visited: HashSet<PathBuf>,
+    system_time_now: SystemTime,
+}
+
+/// Lazy iterator returned by Find::into_iter_os()
+/// Same as FindIter, except paths are returned as OsStrings, so badly encoded names never fail.
+pub struct FindIterOs(FindIter);
+//
+// Public - get - find
+//
+/// Returns a Find query rooted at arg_string_path_root
+/// Nothing touches the disk until the query is iterated or an action is run.
+/// # Arguments
+/// * arg_string_path_root: string-like path to the directory to search
+/// # Examples
+/// let vec_of_string_paths = easy_paths::find( &"test" )
+///     .name( "*.txt" )
+///     .type_file()
+///     .max_depth( 3 )
+///     .into_iter()
+///     .collect::<Result<Vec<String>, String>>();
+pub fn find<T: Debug + Display>(arg_string_path_root: &T) -> Find {
+    find_in_path(Path::new(&format!("{}", arg_string_path_root,)))
+}
+
+/// Same as find(), except the root is a Path, so it needn't be valid UTF-8
+pub(crate) fn find_in_path(arg_path_root: &Path) -> Find {
+    Find {
+        path_buf_root: arg_path_root.to_path_buf(),
+        vec_of_globs_name: vec![],
+        vec_of_globs_name_case_insensitive: vec![],
+        option_duration_newer_than: None,
+        option_duration_older_than: None,
+        option_int_size_gt: None,
+        option_int_size_lt: None,
+        option_find_type: None,
+        option_int_depth_max: None,
+        int_depth_min: 1,
+        bool_follow_symlinks: false,
+        path_encoding: PathEncoding::Strict,
+    }
+}
+
+impl Find {
+    //
+    // Public - filters
+    //
+    /// Only match entries whose base name matches the glob ( '*', '?', '[a-z]', '[!a]' )
+    /// # Arguments
+    /// * arg_string_glob: string-like glob
+    pub fn name<T: Display>(mut self, arg_string_glob: T) -> Self {
+        self.vec_of_globs_name.push(format!("{}", arg_string_glob,));
+        self
+    }
+
+    /// Same as name(), except letter case is ignored
+    /// # Arguments
+    /// * arg_string_glob: string-like glob
+    pub fn iname<T: Display>(mut self, arg_string_glob: T) -> Self {
+        self.vec_of_globs_name_case_insensitive
+            .push(format!("{}", arg_string_glob,).to_lowercase());
+        self
+    }
+
+    /// Only match entries modified within the duration leading up to now
+    /// # Arguments
+    /// * arg_duration: how far back to look
+    pub fn newer_than(mut self, arg_duration: Duration) -> Self {
+        self.option_duration_newer_than = Some(arg_duration);
+        self
+    }
+
+    /// Only match entries last modified longer ago than the duration
+    /// # Arguments
+    /// * arg_duration: minimum age
+    pub fn older_than(mut self, arg_duration: Duration) -> Self {
+        self.option_duration_older_than = Some(arg_duration);
+        self
+    }
+
+    /// Only match entries larger than arg_int_bytes
+    /// # Arguments
+    /// * arg_int_bytes: size in bytes
+    pub fn size_gt(mut self, arg_int_bytes: u64) -> Self {
+        self.option_int_size_gt = Some(arg_int_bytes);
+        self
+    }
+
+    /// Only match entries smaller than arg_int_bytes
+    /// # Arguments
+    /// * arg_int_bytes: size in bytes
+    pub fn size_lt(mut self, arg_int_bytes: u64) -> Self {
+        self.option_int_size_lt = Some(arg_int_bytes);
+        self
+    }
+
+    /// Only match directories
+    pub fn type_dir(mut self) -> Self {
+        self.option_find_type = Some(FindType::Dir);
+        self
+    }
+
+    /// Only match regular files
+    pub fn type_file(mut self) -> Self {
+        self.option_find_type = Some(FindType::File);
+        self
+    }
+
+    /// Only match symlinks
+    /// This never matches anything if follow_symlinks() is also set, since links are resolved first.
+    pub fn type_symlink(mut self) -> Self {
+        self.option_find_type = Some(FindType::Symlink);
+        self
+    }
+
+    /// Do not descend further than arg_int_depth levels below the root
+    /// # Arguments
+    /// * arg_int_depth: usize
+    pub fn max_depth(mut self, arg_int_depth: usize) -> Self {
+        self.option_int_depth_max = Some(arg_int_depth);
+        self
+    }
+
+    /// Do not return anything less than arg_int_depth levels below the root
+    /// Passing 0 includes the root itself.
+    /// # Arguments
+    /// * arg_int_depth: usize
+    pub fn min_depth(mut self, arg_int_depth: usize) -> Self {
+        self.int_depth_min = arg_int_depth;
+        self
+    }
+
+    /// Resolve symlinks while walking, including descending into linked directories
+    /// Each directory is only entered once, so link loops can't cause an endless walk.
+    pub fn follow_symlinks(mut self) -> Self {
+        self.bool_follow_symlinks = true;
+        self
+    }
+    /// Choose how paths that aren't valid UTF-8 are turned into Strings
+    /// The default, PathEncoding::Strict, reports each such path as an error without ending the walk.
+    /// # Arguments
+    /// * arg_path_encoding: PathEncoding
+    pub fn encoding(mut self, arg_path_encoding: PathEncoding) -> Self {
+        self.path_encoding = arg_path_encoding;
+        self
+    }
+    //
+    // Public - iterators
+    //
+    /// Returns an iterator of OsString paths instead of Strings
+    /// # Examples
+    /// let vec_of_os_strings = easy_paths::find( &"test" )
+    ///     .into_iter_os()
+    ///     .collect::<Result<Vec<OsString>, String>>();
+    pub fn into_iter_os(self) -> FindIterOs {
+        FindIterOs(self.into_iter())
+    }
+    //
+    // Public - actions
+    //
+    /// Prints every match to stdout, one per line
+    /// Returns the number of paths printed, or the first error encountered
+    /// # Examples
+    /// let int_count = match easy_paths::find( &"test" ).name( "*.txt" ).print() {
+    ///     Ok( int_result ) => { int_result }
+    ///     Err( err ) => { panic!( "{}", err, ) }
+    /// };
+    pub fn print(self) -> Result<usize, String> {
+        self.exec(|arg_string_path| {
+            println!("{}", arg_string_path,);
+            Ok(())
+        })
+    }
+
+    /// Runs the closure on every match
+    /// Iteration stops at the first error, either from the walk or returned by the closure
+    /// Returns the number of paths processed
+    /// # Arguments
+    /// * arg_closure: FnMut( &str ) -> Result<(), String>
+    /// # Examples
+    /// let mut vec_of_string_paths = vec![];
+    /// let result = easy_paths::find( &"test" ).type_file().exec( | item_str_path | {
+    ///     vec_of_string_paths.push( item_str_path.to_string() );
+    ///     Ok( () )
+    /// } );
+    pub fn exec<F: FnMut(&str) -> Result<(), String>>(
+        self,
+        mut arg_closure: F,
+    ) -> Result<usize, String> {
+        let mut int_count = 0;
+        for item_result in self {
+            arg_closure(&item_result?)?;
+            int_count += 1;
+        }
+        Ok(int_count)
+    }
+
+    /// Deletes every match, deepest paths first
+    /// Like GNU find -delete, a matched directory is removed with remove_dir() once its matched children
+    /// are gone, so a directory that still holds anything the query didn't match is an error instead
+    /// of being emptied. Before anything is removed, every match is checked against the project guards
+    /// ( project root, src, Cargo.toml, main.rs, and any directory containing the project ).
+    /// If a single match fails a guard, nothing is deleted.
+    /// Returns the deleted paths
+    /// # Examples
+    /// let vec_of_string_paths_deleted = match easy_paths::find( &"/tmp/cache" ).name( "*.log" ).delete() {
+    ///     Ok( vec_result ) => { vec_result }
+    ///     Err( err ) => { panic!( "{}", err, ) }
+    /// };
+    pub fn delete(self) -> Result<Vec<String>, String> {
+        let mut vec_of_string_paths = self.into_iter().collect::<Result<Vec<String>, String>>()?;
+        for item_string_path in &vec_of_string_paths {
+            raise_error_if_path_is_protected(item_string_path)?;
+        }
+        //
+        // Reminder: children have to go before their parents, or remove_dir() will fail
+        //
+        vec_of_string_paths.sort_by_key(|item_string_path| {
+            std::cmp::Reverse(Path::new(item_string_path).components().count())
+        });
+        let mut vec_to_return = vec![];
+        for item_string_path in vec_of_string_paths {
+            let path = Path::new(&item_string_path);
+            let result = if path.is_dir() && !path.is_symlink() {
+                std::fs::remove_dir(path)
+            } else {
+                std::fs::remove_file(path)
+            };
+            if let Err(err) = result {
+                return Err([
+                    "Error: failed to delete path.".to_string(),
+                    format!("err = {}", err,),
+                    format!("item_string_path = {}", item_string_path,),
+                    format!("paths already deleted = {:#?}", vec_to_return,),
+                ]
+                .join("\n"));
+            }
+            vec_to_return.push(item_string_path);
+        }
+        Ok(vec_to_return)
+    }
+    //
+    // Private
+    //
+    fn is_match(&self, arg_path: &Path, arg_metadata: &Metadata, arg_time_now: SystemTime) -> bool {
+        if let Some(find_type) = self.option_find_type {
+            let bool_type_matches = match find_type {
+                FindType::Dir => arg_metadata.is_dir(),
+                FindType::File => arg_metadata.is_file(),
+                FindType::Symlink => arg_metadata.file_type().is_symlink(),
+            };
+            if !bool_type_matches {
+                return false;
+            }
+        }
+        if !self.vec_of_globs_name.is_empty() || !self.vec_of_globs_name_case_insensitive.is_empty()
+        {
+            let string_name = match arg_path.file_name() {
+                Some(os_str_result) => os_str_result.to_string_lossy().to_string(),
+                None => return false,
+            };
+            if !self
+                .vec_of_globs_name
+                .iter()
+                .all(|item_glob| is_match_for_glob(item_glob, &string_name))
+            {
+                return false;
+            }
+            let string_name_lowercase = string_name.to_lowercase();
+            if !self
+                .vec_of_globs_name_case_insensitive
+                .iter()
+                .all(|item_glob| is_match_for_glob(item_glob, &string_name_lowercase))
+            {
+                return false;
+            }
+        }
+        if let Some(int_size) = self.option_int_size_gt {
+            if arg_metadata.len() <= int_size {
+                return false;
+            }
+        }
+        if let Some(int_size) = self.option_int_size_lt {
+            if arg_metadata.len() >= int_size {
+                return false;
+            }
+        }
+        if self.option_duration_newer_than.is_some() || self.option_duration_older_than.is_some() {
+            let system_time_modified = match arg_metadata.modified() {
+                Ok(system_time_result) => system_time_result,
+                Err(_err) => return false,
+            };
+            let duration_age = arg_time_now
+                .duration_since(system_time_modified)
+                .unwrap_or(Duration::ZERO);
+            if let Some(duration) = self.option_duration_newer_than {
+                if duration_age > duration {
+                    return false;
+                }
+            }
+            if let Some(duration) = self.option_duration_older_than {
+                if duration_age <= duration {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    fn is_depth_allowed_to_descend(&self, arg_int_depth: usize) -> bool {
+        match self.option_int_depth_max {
+            Some(int_depth_max) => arg_int_depth < int_depth_max,
+            None => true,
+        }
+    }
+
+    fn get_metadata(&self, arg_path: &Path) -> std::io::Result<Metadata> {
+        if self.bool_follow_symlinks {
+            std::fs::metadata(arg_path)
+        } else {
+            std::fs::symlink_metadata(arg_path)
+        }
+    }
+}
+
+impl IntoIterator for Find {
+    type Item = Result<String, String>;
+    type IntoIter = FindIter;
+
+    fn into_iter(self) -> FindIter {
+        FindIter {
+            find: self,
+            bool_root_pending: true,
+            stack_of_read_dirs: vec![],
+            option_string_err_pending: None,
+            set_of_path_bufs_visited: HashSet::new(),
+            system_time_now: SystemTime::now(),
+        }
+    }
+}
+
+impl FindIter {
+    /// Queues the directory for reading, if the depth limit and the loop check allow it
+    /// Errors are held back until after the directory itself has been returned
+    fn push_dir_if_necessary(
+        &mut self,
+        arg_path: &Path,
+        arg_metadata: &Metadata,
+        arg_int_depth: usize,
+    ) {
+        if !arg_metadata.is_dir() || !self.find.is_depth_allowed_to_descend(arg_int_depth) {
+            return;
+        }
+        if self.find.bool_follow_symlinks {
+            let path_buf_canonical = match std::fs::canonicalize(arg_path) {
+                Ok(path_buf_result) => path_buf_result,
+                Err(_err) => arg_path.to_path_buf(),
+            };
+            if !self.set_of_path_bufs_visited.insert(path_buf_canonical) {
+                return;
+            }
+        }
+        match std::fs::read_dir(arg_path) {
+            Ok(read_dir_result) => self
+                .stack_of_read_dirs
+                .push((read_dir_result, arg_int_depth + 1)),
+            Err(err) => {
+                self.option_string_err_pending = Some(
+                    [
+                        "Error: failed to read directory.".to_string(),
+                        format!("err = {}", err,),
+                        format!("arg_path = {:?}", arg_path,),
+                    ]
+                    .join("\n"),
+                )
+            }
+        }
+    }
+}
+
+impl FindIter {
+    /// Advances the walk to the next match
+    fn next_path_buf(&mut self) -> Option<Result<PathBuf, String>> {
+        if let Some(string_err) = self.option_string_err_pending.take() {
+            return Some(Err(string_err));
+        }
+        if self.bool_root_pending {
+            self.bool_root_pending = false;
+            let path_buf_root = self.find.path_buf_root.clone();
+            let metadata_root = match self.find.get_metadata(&path_buf_root) {
+                Ok(metadata_result) => metadata_result,
+                Err(err) => {
+                    return Some(Err([
+                        "Error: failed to get meta data from root path.".to_string(),
+                        format!("err = {}", err,),
+                        format!("path_buf_root = {}", self.find.path_buf_root.display(),),
+                    ]
+                    .join("\n")))
+                }
+            };
+            //
+            // Reminder: the root is always walked, even when not following symlinks, like GNU find -H
+            //
+            let metadata_root_for_walk = match std::fs::metadata(&path_buf_root) {
+                Ok(metadata_result) => metadata_result,
+                Err(_err) => metadata_root.clone(),
+            };
+            self.push_dir_if_necessary(&path_buf_root, &metadata_root_for_walk, 0);
+            if self.find.int_depth_min == 0
+                && self
+                    .find
+                    .is_match(&path_buf_root, &metadata_root, self.system_time_now)
+            {
+                return Some(Ok(path_buf_root));
+            }
+        }
+        loop {
+            // Also reached when the root wasn't returned, so an error reading it isn't lost
+            if let Some(string_err) = self.option_string_err_pending.take() {
+                return Some(Err(string_err));
+            }
+            let (read_dir, int_depth) = self.stack_of_read_dirs.last_mut()?;
+            let int_depth = *int_depth;
+            let dir_entry = match read_dir.next() {
+                Some(Ok(dir_entry_result)) => dir_entry_result,
+                Some(Err(err)) => {
+                    return Some(Err([
+                        "Error: failed to extract item_dir_entry".to_string(),
+                        format!("err = {}", err,),
+                    ]
+                    .join("\n")))
+                }
+                None => {
+                    self.stack_of_read_dirs.pop();
+                    continue;
+                }
+            };
+            let path_buf = dir_entry.path();
+            let metadata = match self.find.get_metadata(&path_buf) {
+                Ok(metadata_result) => metadata_result,
+                Err(err) => {
+                    return Some(Err([
+                        "Error: failed to get meta data from path.".to_string(),
+                        format!("err = {}", err,),
+                        format!("path_buf = {:?}", path_buf,),
+                    ]
+                    .join("\n")))
+                }
+            };
+            self.push_dir_if_necessary(&path_buf, &metadata, int_depth);
+            if int_depth >= self.find.int_depth_min
+                && self
+                    .find
+                    .is_match(&path_buf, &metadata, self.system_time_now)
+            {
+                return Some(Ok(path_buf));
+            }
+        }
+    }
+}
+impl Iterator for FindIter {
+    type Item = Result<String, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(match self.next_path_buf()? {
+            Ok(path_buf) => get_string_from_path_with_encoding(&path_buf, self.find.path_encoding),
+            Err(err) => Err(err),
+        })
+    }
+}
+
+impl Iterator for FindIterOs {
+    type Item = Result<OsString, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(
+            self.0
+                .next_path_buf()?
+                .map(|path_buf| path_buf.into_os_string()),
+        )
+    }
+}
+//
+// Private
+//
+/// Returns true if arg_str matches the glob
+/// Supports '*', '?', character classes ( '[abc]', '[a-z]', '[!a]' ) and '\' escapes
+pub(crate) fn is_match_for_glob(arg_str_glob: &str, arg_str: &str) -> bool {
+    let vec_of_chars_glob = arg_str_glob.chars().collect::<Vec<char>>();
+    let vec_of_chars = arg_str.chars().collect::<Vec<char>>();
+    let mut int_index_glob = 0;
+    let mut int_index = 0;
+    //
+    // Position to resume from when a '*' needs to swallow one more character
+    //
+    let mut option_backtrack: Option<(usize, usize)> = None;
+    while int_index < vec_of_chars.len() {
+        if int_index_glob < vec_of_chars_glob.len() {
+            match vec_of_chars_glob[int_index_glob] {
+                '*' => {
+                    option_backtrack = Some((int_index_glob, int_index));
+                    int_index_glob += 1;
+                    continue;
+                }
+                '?' => {
+                    int_index_glob += 1;
+                    int_index += 1;
+                    continue;
+                }
+                '[' => {
+                    if let Some((bool_matched, int_index_glob_next)) = get_result_from_char_class(
+                        &vec_of_chars_glob,
+                        int_index_glob,
+                        vec_of_chars[int_index],
+                    ) {
+                        if bool_matched {
+                            int_index_glob = int_index_glob_next;
+                            int_index += 1;
+                            continue;
+                        }
+                    } else if vec_of_chars[int_index] == '[' {
+                        //
+                        // Unterminated class, so treat '[' as a literal
+                        //
+                        int_index_glob += 1;
+                        int_index += 1;
+                        continue;
+                    }
+                }
+                '\\' if int_index_glob + 1 < vec_of_chars_glob.len() => {
+                    if vec_of_chars_glob[int_index_glob + 1] == vec_of_chars[int_index] {
+                        int_index_glob += 2;
+                        int_index += 1;
+                        continue;
+                    }
+                }
+                char_glob => {
+                    if char_glob == vec_of_chars[int_index] {
+                        int_index_glob += 1;
+                        int_index += 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        match option_backtrack {
+            Some((int_index_glob_star, int_index_star)) => {
+                option_backtrack = Some((int_index_glob_star, int_index_star + 1));
+                int_index_glob = int_index_glob_star + 1;
+                int_index = int_index_star + 1;
+            }
+            None => return false,
+        }
+    }
+    vec_of_chars_glob[int_index_glob..]
+        .iter()
+        .all(|item_char| *item_char == '*')
+}
+
+/// Returns ( matched, index after the class ), or None if the class is never closed
+fn get_result_from_char_class(
+    arg_vec_of_chars_glob: &[char],
+    arg_int_index_open: usize,
+    arg_char: char,
+) -> Option<(bool, usize)> {
+    let mut int_index = arg_int_index_open + 1;
+    let bool_negated = matches!(arg_vec_of_chars_glob.get(int_index), Some('!') | Some('^'));
+    if bool_negated {
+        int_index += 1;
+    }
+    let mut bool_matched = false;
+    let mut bool_first = true;
+    loop {
+        let char_current = *arg_vec_of_chars_glob.get(int_index)?;
+        if char_current == ']' && !bool_first {
+            return Some((bool_matched != bool_negated, int_index + 1));
+        }
+        bool_first = false;
+        if arg_vec_of_chars_glob.get(int_index + 1) == Some(&'-')
+            && matches!(arg_vec_of_chars_glob.get(int_index + 2), Some(char_end) if *char_end != ']')
+        {
+            let char_end = arg_vec_of_chars_glob[int_index + 2];
+            if char_current <= arg_char && arg_char <= char_end {
+                bool_matched = true;
+            }
+            int_index += 3;
+        } else {
+            if char_current == arg_char {
+                bool_matched = true;
+            }
+            int_index += 1;
+        }
+    }
+}
+
+/// Returns an error if deleting arg_string_path would damage the project
+fn raise_error_if_path_is_protected(arg_string_path: &str) -> Result<(), String> {
+    let string_path_absolute = get_absolute_path(&arg_string_path);
+    raise_error_if_path_points_to_project_root(&string_path_absolute)?;
+    raise_error_if_path_points_to_src(&string_path_absolute)?;
+    raise_error_if_path_points_to_cargo_toml(&string_path_absolute)?;
+    raise_error_if_path_points_to_main_rs(&string_path_absolute)?;
+    if is_path_inside_dir_parent(&get_dir_proj_root(), &string_path_absolute) {
+        return Err([
+            "Error: arg_string_path contains the project directory.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n"));
+    }
+    Ok(())
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+
+    #[test]
+    fn test_find_name_type_file() {
+        let mut result = match find(&"test")
+            .name("*.txt")
+            .type_file()
+            .into_iter()
+            .collect::<Result<Vec<String>, String>>()
+        {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        result.sort();
+        let expected = [
+            "test/test_a/text_a_a.txt",
+            "test/test_b/test_c/text_b_c_a.txt",
+            "test/test_b/test_c/text_b_c_b.txt",
+        ]
+        .iter()
+        .map(|item_str| item_str.to_string())
+        .collect::<Vec<String>>();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn test_find_max_depth_type_dir() {
+        let mut result = match find(&"test")
+            .type_dir()
+            .max_depth(1)
+            .into_iter()
+            .collect::<Result<Vec<String>, String>>()
+        {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        result.sort();
+        assert_eq!(
+            result,
+            vec!["test/test_a".to_string(), "test/test_b".to_string()]
+        )
+    }
+
+    #[test]
+    fn test_find_size_and_age() {
+        assert_eq!(
+            find(&"test")
+                .type_file()
+                .size_gt(1_000_000_000)
+                .into_iter()
+                .count(),
+            0,
+        );
+        assert_eq!(
+            find(&"test")
+                .type_file()
+                .newer_than(Duration::from_secs(60 * 60 * 24 * 365 * 100))
+                .into_iter()
+                .count(),
+            3,
+        )
+    }
+
+    #[test]
+    fn test_find_delete() {
+        let string_path_dir = get_dir_temp_for_tests("find_delete");
+        std::fs::create_dir(format!("{}/c.log", string_path_dir,)).unwrap();
+        for item_str_name in ["a.log", "b.log", "keep.txt", "c.log/d.log"] {
+            std::fs::write(format!("{}/{}", string_path_dir, item_str_name,), "x").unwrap();
+        }
+        let mut result = match find(&string_path_dir).name("*.log").delete() {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        result.sort();
+        assert_eq!(
+            result,
+            vec![
+                format!("{}/a.log", string_path_dir,),
+                format!("{}/b.log", string_path_dir,),
+                format!("{}/c.log", string_path_dir,),
+                format!("{}/c.log/d.log", string_path_dir,),
+            ]
+        );
+        assert!(Path::new(&format!("{}/keep.txt", string_path_dir,)).exists());
+    }
+
+    #[test]
+    fn test_find_delete_keeps_unmatched_children() {
+        let string_path_dir = get_dir_temp_for_tests("find_delete_keeps_unmatched_children");
+        std::fs::create_dir(format!("{}/logs", string_path_dir,)).unwrap();
+        std::fs::write(format!("{}/logs/old.txt", string_path_dir,), "x").unwrap();
+        std::fs::write(format!("{}/logs/new.log", string_path_dir,), "x").unwrap();
+        match find(&string_path_dir).name("*log*").delete() {
+            Ok(vec_result) => panic!("Deleted a non-empty directory: {:?}", vec_result),
+            Err(_err) => {}
+        }
+        assert!(!Path::new(&format!("{}/logs/new.log", string_path_dir,)).exists());
+        assert!(Path::new(&format!("{}/logs/old.txt", string_path_dir,)).exists());
+    }
+
+    #[test]
+    fn test_find_delete_refuses_project_files() {
+        match find(&get_dir_proj_root())
+            .name("Cargo.toml")
+            .max_depth(1)
+            .delete()
+        {
+            Ok(vec_result) => panic!("Deleted protected paths: {:?}", vec_result),
+            Err(_err) => {}
+        }
+        assert!(Path::new(&format!("{}/Cargo.toml", get_dir_proj_root(),)).exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_reports_unreadable_root() {
+        use std::os::unix::fs::PermissionsExt;
+        let string_path_dir = get_dir_temp_for_tests("find_reports_unreadable_root");
+        std::fs::set_permissions(&string_path_dir, std::fs::Permissions::from_mode(0o000)).unwrap();
+        // Root can read the directory anyway, and then the empty root walks cleanly
+        let bool_is_readable = std::fs::read_dir(&string_path_dir).is_ok();
+        let vec_of_results = find(&string_path_dir)
+            .into_iter()
+            .collect::<Vec<Result<String, String>>>();
+        std::fs::set_permissions(&string_path_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        if bool_is_readable {
+            assert_eq!(vec_of_results, vec![]);
+        } else {
+            assert_eq!(vec_of_results.len(), 1);
+            assert!(vec_of_results[0].is_err());
+        }
+    }
+
+    #[test]
+    fn test_is_match_for_glob() {
+        assert!(is_match_for_glob("*.log", "server.log"));
+        assert!(is_match_for_glob("file?.[a-c]xt", "file1.bxt"));
+        assert!(is_match_for_glob("[!.]*", "visible"));
+        assert!(!is_match_for_glob("[!.]*", ".hidden"));
+        assert!(!is_match_for_glob("*.log", "server.log.gz"));
+        assert!(is_match_for_glob("a*b*c", "aXXbYYc"));
+    }
+}
@@ -0,0 +1,266 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::io::Error;
+use std::path::{Component, Path, PathBuf};
+//
+// Libraries - local
+//
+use crate::file_system::get_path_buf_resolved_with;
+use crate::{FileSystem, OsFs};
+//
+// Public - get - joined paths
+//
+/// Returns arg_string_path_base joined with untrusted parts, normalized, and guaranteed to stay inside the base
+/// Unlike get_path_joined(), an absolute part is an error instead of replacing the base, and so is any
+/// '..' that would step above the base, even if later parts come back into it. NUL bytes are rejected too.
+/// The disk isn't touched, so a symlink inside the base can still lead outside;
+/// get_path_joined_within_resolved() checks for that.
+/// # Arguments
+/// * arg_string_path_base: string-like path of the directory to stay inside
+/// * arg_slice_of_parts: slice of string-likes, usually from a request
+/// # Examples
+/// let string_path = match get_path_joined_within( &"/srv/uploads", &[ "alice", "../bob/a.txt" ] ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "/srv/uploads/bob/a.txt"
+/// // get_path_joined_within( &"/srv/uploads", &[ "../../etc/passwd" ] ) is an error
+pub fn get_path_joined_within<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_base: &T1,
+    arg_slice_of_parts: &[T2],
+) -> Result<String, String> {
+    let path_buf = get_path_buf_joined_within(arg_string_path_base, arg_slice_of_parts)?;
+    get_string_from_path_buf(path_buf, arg_string_path_base)
+}
+
+/// Returns the same as get_path_joined_within(), but with existing symlinks resolved, and an error if any leads outside the base
+/// The base is resolved too, and the result is the real, absolute location. Components that don't
+/// exist yet are kept as they are. Nothing stops the tree from changing after this returns, so this
+/// guards against symlinks already planted in the base, not against a concurrent attacker.
+/// # Arguments
+/// * arg_string_path_base: string-like path of the directory to stay inside
+/// * arg_slice_of_parts: slice of string-likes, usually from a request
+/// # Examples
+/// // With /srv/uploads/alice -> /etc
+/// let result = get_path_joined_within_resolved( &"/srv/uploads", &[ "alice", "passwd" ] );
+/// // result is an error, while get_path_joined_within() returns "/srv/uploads/alice/passwd"
+pub fn get_path_joined_within_resolved<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_base: &T1,
+    arg_slice_of_parts: &[T2],
+) -> Result<String, String> {
+    let path_buf_joined = get_path_buf_joined_within(arg_string_path_base, arg_slice_of_parts)?;
+    let closure_get_error = |err: Error, path: &Path| {
+        [
+            "Error: failed to resolve symlinks.".to_string(),
+            format!("err = {}", err,),
+            format!("path = {}", path.display(),),
+        ]
+        .join("\n")
+    };
+    let path_buf_base = Path::new(&format!("{}", arg_string_path_base,)).to_path_buf();
+    let path_buf_base_resolved = get_path_buf_with_symlinks_resolved(&path_buf_base)
+        .map_err(|err| closure_get_error(err, &path_buf_base))?;
+    let path_buf_resolved = get_path_buf_with_symlinks_resolved(&path_buf_joined)
+        .map_err(|err| closure_get_error(err, &path_buf_joined))?;
+    if !path_buf_resolved.starts_with(&path_buf_base_resolved) {
+        return Err([
+            "Error: path leads outside the base directory through a symlink.".to_string(),
+            format!("path_buf_joined = {}", path_buf_joined.display(),),
+            format!("path_buf_resolved = {}", path_buf_resolved.display(),),
+            format!("arg_string_path_base = {}", arg_string_path_base,),
+        ]
+        .join("\n"));
+    }
+    get_string_from_path_buf(path_buf_resolved, arg_string_path_base)
+}
+//
+// Public - ( logic ) are / is
+//
+/// Returns true if arg_string_path is really inside arg_string_dir_parent, with existing symlinks resolved in both
+/// Unlike is_path_inside_dir_parent(), '..' and symlinks are taken into account, so '/A/B/../../etc' and
+/// a link from '/A/B/link' to '/etc' are not inside '/A/B'. Parts that don't exist yet are compared
+/// lexically. A path is inside itself. Returns false if resolving fails, ie on a symlink loop.
+/// # Arguments
+/// * arg_string_path: string-like
+/// * arg_string_dir_parent: string-like
+/// # Examples
+/// let result = is_path_inside_dir_parent_resolved( &"/A/B/C/D", &"/A/B/C" );
+pub fn is_path_inside_dir_parent_resolved<T1: Display, T2: Display>(
+    arg_string_path: &T1,
+    arg_string_dir_parent: &T2,
+) -> bool {
+    match (
+        get_path_buf_with_symlinks_resolved(Path::new(&format!("{}", arg_string_path,))),
+        get_path_buf_with_symlinks_resolved(Path::new(&format!("{}", arg_string_dir_parent,))),
+    ) {
+        (Ok(path_buf), Ok(path_buf_dir_parent)) => path_buf.starts_with(path_buf_dir_parent),
+        _ => false,
+    }
+}
+//
+// Private
+//
+/// Returns the base joined with the parts, normalized, or an error if a part is unsafe
+fn get_path_buf_joined_within<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_base: &T1,
+    arg_slice_of_parts: &[T2],
+) -> Result<PathBuf, String> {
+    let string_path_base = format!("{}", arg_string_path_base,);
+    if string_path_base.contains('\0') {
+        return Err([
+            "Error: base contains a NUL byte.".to_string(),
+            format!("arg_string_path_base = {:?}", arg_string_path_base,),
+        ]
+        .join("\n"));
+    }
+    let mut vec_of_components: Vec<String> = vec![];
+    for item_part in arg_slice_of_parts {
+        let string_part = format!("{}", item_part,);
+        let closure_get_error = |str_reason: &str| {
+            [
+                format!("Error: {}", str_reason,),
+                format!("item_part = {:?}", item_part,),
+                format!("arg_string_path_base = {}", arg_string_path_base,),
+            ]
+            .join("\n")
+        };
+        if string_part.contains('\0') {
+            return Err(closure_get_error("part contains a NUL byte."));
+        }
+        for item_component in Path::new(&string_part).components() {
+            match item_component {
+                Component::Prefix(_) | Component::RootDir => {
+                    return Err(closure_get_error("part is absolute."));
+                }
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if vec_of_components.pop().is_none() {
+                        return Err(closure_get_error("part leads outside the base directory."));
+                    }
+                }
+                Component::Normal(os_str) => {
+                    vec_of_components.push(os_str.to_string_lossy().to_string());
+                }
+            }
+        }
+    }
+    let mut path_buf = match crate::get_path_normalized(&string_path_base) {
+        Some(string_result) => PathBuf::from(string_result),
+        None => PathBuf::from(&string_path_base),
+    };
+    path_buf.extend(vec_of_components);
+    Ok(path_buf)
+}
+
+/// Returns the path made absolute with every existing symlink resolved, the way the OS would walk it
+/// Once a component doesn't exist, the rest are applied lexically.
+pub(crate) fn get_path_buf_with_symlinks_resolved(arg_path: &Path) -> std::io::Result<PathBuf> {
+    get_path_buf_resolved_with(
+        &std::path::absolute(arg_path)?,
+        true,
+        true,
+        |path_candidate| OsFs.symlink_metadata(path_candidate),
+        |path_candidate| std::fs::read_link(path_candidate),
+    )
+}
+
+fn get_string_from_path_buf<T: Display>(
+    arg_path_buf: PathBuf,
+    arg_string_path_base: &T,
+) -> Result<String, String> {
+    match arg_path_buf.into_os_string().into_string() {
+        Ok(string_result) => Ok(string_result),
+        Err(os_string) => Err([
+            "Error: joined path is not valid UTF-8.".to_string(),
+            format!("os_string = {:?}", os_string,),
+            format!("arg_string_path_base = {}", arg_string_path_base,),
+        ]
+        .join("\n")),
+    }
+}
+
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_path_joined_within() {
+        for (item_slice_of_parts, item_str_expected) in [
+            (&["a.txt"][..], "/srv/uploads/a.txt"),
+            (&["alice", "../bob/./a.txt"][..], "/srv/uploads/bob/a.txt"),
+            (&["alice", "..", ""][..], "/srv/uploads"),
+            (&[][..], "/srv/uploads"),
+        ] {
+            assert_eq!(
+                get_path_joined_within(&"/srv/uploads/", item_slice_of_parts).unwrap(),
+                item_str_expected,
+            );
+        }
+        for item_slice_of_parts in [
+            &["../../etc/passwd"][..],
+            &["..", "uploads", "a.txt"][..],
+            &["alice", "/etc/passwd"][..],
+            &["a\0.txt"][..],
+        ] {
+            assert!(
+                get_path_joined_within(&"/srv/uploads", item_slice_of_parts).is_err(),
+                "{:?}",
+                item_slice_of_parts,
+            );
+        }
+        assert_eq!(
+            get_path_joined_within(&"uploads/./x/..", &["a.txt"]).unwrap(),
+            "uploads/a.txt"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_get_path_joined_within_resolved() {
+        let string_dir = crate::get_dir_temp_for_tests("get_path_joined_within_resolved");
+        let path_buf_base = Path::new(&string_dir).join("base");
+        let path_buf_outside = Path::new(&string_dir).join("outside");
+        std::fs::create_dir_all(path_buf_base.join("sub")).unwrap();
+        std::fs::create_dir_all(&path_buf_outside).unwrap();
+        std::os::unix::fs::symlink(&path_buf_outside, path_buf_base.join("escape")).unwrap();
+        std::os::unix::fs::symlink("sub", path_buf_base.join("inside")).unwrap();
+        std::os::unix::fs::symlink("../../base", path_buf_base.join("sub/up")).unwrap();
+        std::os::unix::fs::symlink("loop", path_buf_base.join("loop")).unwrap();
+        let string_base = path_buf_base.to_str().unwrap();
+        let string_base_canonical = std::fs::canonicalize(&path_buf_base).unwrap();
+        assert_eq!(
+            get_path_joined_within_resolved(&string_base, &["inside", "new.txt"]).unwrap(),
+            string_base_canonical.join("sub/new.txt").to_str().unwrap()
+        );
+        assert_eq!(
+            get_path_joined_within_resolved(&string_base, &["sub/up/sub"]).unwrap(),
+            string_base_canonical.join("sub").to_str().unwrap()
+        );
+        assert!(get_path_joined_within(&string_base, &["escape", "a.txt"]).is_ok());
+        assert!(get_path_joined_within_resolved(&string_base, &["escape", "a.txt"]).is_err());
+        assert!(get_path_joined_within_resolved(&string_base, &["loop"]).is_err());
+        // The lexical check passes, while the symlink-aware one doesn't
+        let string_path_escaped = path_buf_base.join("escape/a.txt");
+        assert!(crate::is_path_inside_dir_parent(
+            &string_path_escaped.display(),
+            &string_base
+        ));
+        assert!(!is_path_inside_dir_parent_resolved(
+            &string_path_escaped.display(),
+            &string_base
+        ));
+        assert!(is_path_inside_dir_parent_resolved(
+            &path_buf_base.join("inside/../sub/x").display(),
+            &string_base
+        ));
+        assert!(!is_path_inside_dir_parent_resolved(
+            &path_buf_base.join("sub/../../outside").display(),
+            &string_base
+        ));
+    }
+}
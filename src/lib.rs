@@ -4,13 +4,104 @@
 use std::ffi::OsStr;
 use std::fmt::{Debug, Display};
 use std::fs::DirEntry;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 //
 // Libraries - downloaded
 //
 use shellexpand;
 use substring::Substring;
 //
+// Modules
+//
+mod easy_path;
+mod encoding;
+mod executable;
+mod extension;
+mod file_system;
+mod file_url;
+mod find;
+mod join;
+mod lock;
+#[cfg(unix)]
+mod permissions;
+pub mod posix;
+mod sandbox;
+mod sanitize;
+mod search;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod sort;
+mod symlink;
+#[cfg(test)]
+mod test_utils;
+#[cfg(feature = "async")]
+pub mod tokio;
+mod unique;
+mod upwards;
+pub mod windows;
+mod xdg;
+
+pub use easy_path::{EasyPath, EasyPathRef};
+pub use encoding::{
+    get_base_name_as_os_string, get_paths_in_dir_and_sub_dirs_as_os_strings,
+    get_paths_in_dir_and_sub_dirs_lossy, get_paths_in_dir_as_os_strings, get_paths_in_dir_lossy,
+    get_string_from_type_osstr_lossy, get_vec_by_splitting_path_as_os_strings, PathEncoding,
+    PathsLossy,
+};
+#[cfg(unix)]
+pub use encoding::{get_os_string_from_escaped_string, get_string_from_type_osstr_escaped};
+pub use executable::{
+    get_all_paths_of_executable, get_all_paths_of_executable_in_dirs, get_path_of_executable,
+    get_path_of_executable_in_dirs,
+};
+pub use extension::{
+    get_extensions, get_extensions_with_compound, get_full_extension,
+    get_full_extension_with_compound, get_path_with_extension_added,
+    get_path_with_extension_replaced, get_path_with_extension_replaced_with_compound,
+    get_path_with_file_name_replaced, get_stem, get_stem_with_compound,
+    SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT,
+};
+pub use file_system::{FileSystem, FsFileType, FsMetadata, InMemoryFs, OsFs};
+pub use file_url::{get_file_url_from_path, get_path_from_file_url};
+pub use find::{find, Find, FindIter, FindIterOs, FindType};
+pub use join::{
+    get_path_joined_within, get_path_joined_within_resolved, is_path_inside_dir_parent_resolved,
+};
+pub use lock::{
+    lock_file_exclusive, lock_file_exclusive_with_timeout, lock_file_shared,
+    lock_file_shared_with_timeout, try_lock_file_exclusive, try_lock_file_shared, FileLockGuard,
+    FileLockKind, PidLockFile,
+};
+#[cfg(unix)]
+pub use permissions::{
+    chown_recursive, get_permission_report, make_executable, set_permissions_mode,
+    set_permissions_recursive, PermissionReport,
+};
+pub use sandbox::{OverlayFs, RootedFs};
+pub use sanitize::{
+    get_file_name_violations, get_sanitized_file_name, raise_error_if_file_name_is_unsafe,
+    FileNameRule, SanitizeOptions,
+};
+pub use search::{search_in_files, SearchMatch, SearchOptions};
+pub use sort::{
+    get_paths_sorted_by_component, get_paths_sorted_by_depth, get_paths_sorted_by_file_size,
+    get_paths_sorted_by_length, get_paths_sorted_by_modified, get_paths_sorted_case_insensitive,
+    get_paths_sorted_dirs_first, get_paths_sorted_natural,
+};
+pub use symlink::{
+    create_symlink_relative, find_broken_symlinks, get_symlink_chain, get_symlink_target,
+    is_broken_symlink, is_symlink,
+};
+#[cfg(test)]
+pub(crate) use test_utils::get_dir_temp_for_tests;
+pub use unique::{get_path_unique, get_path_unique_created, UniquePathStyle};
+pub use upwards::{find_all_files_upwards, find_file_upwards, UpwardsStop};
+pub use xdg::{
+    get_dir_cache, get_dir_cache_created, get_dir_config, get_dir_config_created, get_dir_data,
+    get_dir_data_created, get_dir_runtime, get_dir_state, get_dir_state_created, get_dir_user,
+    get_dirs_config_search, get_dirs_data_search, UserDir,
+};
+//
 // Tests
 //
 #[cfg(test)]
@@ -273,6 +364,77 @@ mod tests {
         assert_eq!(result, expected)
     }
 
+    fn get_in_memory_fs_like_test_dir() -> InMemoryFs {
+        InMemoryFs::new()
+            .with_file(&"/test/test_a/text_a_a.txt", "")
+            .with_file(&"/test/test_b/test_c/text_b_c_a.txt", "")
+            .with_file(&"/test/test_b/test_c/text_b_c_b.txt", "")
+    }
+
+    #[test]
+    fn test_get_paths_in_dir_and_sub_dirs_with_fs() {
+        let in_memory_fs = get_in_memory_fs_like_test_dir();
+        assert_eq!(
+            get_paths_in_dir_with_fs(&in_memory_fs, &"/test"),
+            Ok(vec!["/test/test_a".to_string(), "/test/test_b".to_string()])
+        );
+        // InMemoryFs lists entries in sorted order, so the result doesn't depend on the disk
+        assert_eq!(
+            get_paths_in_dir_and_sub_dirs_with_fs(&in_memory_fs, &"/test"),
+            Ok([
+                "/test/test_b",
+                "/test/test_b/test_c",
+                "/test/test_b/test_c/text_b_c_b.txt",
+                "/test/test_b/test_c/text_b_c_a.txt",
+                "/test/test_a",
+                "/test/test_a/text_a_a.txt",
+            ]
+            .iter()
+            .map(|item_str| item_str.to_string())
+            .collect::<Vec<String>>())
+        );
+        assert_eq!(
+            get_paths_to_only_dirs_in_dir_and_sub_dirs_with_fs(&in_memory_fs, &"/test"),
+            Ok(vec![
+                "/test/test_b".to_string(),
+                "/test/test_b/test_c".to_string(),
+                "/test/test_a".to_string(),
+            ])
+        );
+        assert_eq!(
+            get_paths_to_only_files_in_dir_and_sub_dirs_with_fs(&in_memory_fs, &"/test/test_a"),
+            Ok(vec!["/test/test_a/text_a_a.txt".to_string()])
+        );
+        let in_memory_fs = get_in_memory_fs_like_test_dir()
+            .with_error(&"/test/test_b/test_c", std::io::ErrorKind::PermissionDenied);
+        let err = get_paths_in_dir_and_sub_dirs_with_fs(&in_memory_fs, &"/test").unwrap_err();
+        assert!(err.contains("/test/test_b/test_c"), "{}", err,);
+    }
+
+    #[test]
+    fn test_existence_with_fs() {
+        let in_memory_fs = get_in_memory_fs_like_test_dir();
+        assert!(is_dir_with_fs(&in_memory_fs, &"/test/test_a"));
+        assert!(is_file_with_fs(&in_memory_fs, &"/test/test_a/text_a_a.txt"));
+        assert!(!is_existing_path_with_fs(&in_memory_fs, &"/test/test_d"));
+        assert_eq!(
+            get_common_path_with_fs(&in_memory_fs, &["/test/test_b/x/y", "/test/test_b/z"]),
+            Ok("/test/test_b".to_string())
+        );
+        assert_eq!(
+            get_dir_ancestor_that_exists_with_fs(&in_memory_fs, &"/test/test_a/missing/deeper"),
+            Some("/test/test_a".to_string())
+        );
+        assert!(raise_error_if_path_does_not_exist_with_fs(&in_memory_fs, &"/test/test_a").is_ok());
+        let err = raise_error_if_path_does_not_exist_with_fs(&in_memory_fs, &"/test/missing")
+            .unwrap_err();
+        assert!(
+            err.contains("ancestor that actually exists = /test"),
+            "{}",
+            err,
+        );
+    }
+
     #[test]
     fn test_get_relative_path() {
         let string_path_abs_root = "/A/B/C";
@@ -285,6 +447,122 @@ mod tests {
         assert_eq!(result, expected)
     }
 
+    #[test]
+    fn test_are_paths_the_same() {
+        let string_path_dir = get_dir_temp_for_tests("are_paths_the_same");
+        std::fs::create_dir(format!("{}/A", string_path_dir,)).unwrap();
+        std::fs::write(format!("{}/A/file.txt", string_path_dir,), "").unwrap();
+        assert!(are_paths_the_same(
+            &"A/../A/file.txt",
+            &"A/./file.txt",
+            &string_path_dir,
+            PathComparison::Lexical,
+        ));
+        assert!(!are_paths_the_same(
+            &"a/FILE.txt",
+            &"A/file.txt",
+            &string_path_dir,
+            PathComparison::Lexical,
+        ));
+        assert!(are_paths_the_same(
+            &"a/FILE.txt",
+            &"A/file.txt",
+            &string_path_dir,
+            PathComparison::CaseInsensitive,
+        ));
+        assert!(!are_paths_the_same(
+            &"A/missing.txt",
+            &"A/missing.txt",
+            &string_path_dir,
+            PathComparison::Canonical,
+        ));
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(
+                format!("{}/A", string_path_dir,),
+                format!("{}/B", string_path_dir,),
+            )
+            .unwrap();
+            std::fs::hard_link(
+                format!("{}/A/file.txt", string_path_dir,),
+                format!("{}/hard_link.txt", string_path_dir,),
+            )
+            .unwrap();
+            assert!(!are_paths_the_same(
+                &"B/file.txt",
+                &"A/file.txt",
+                &string_path_dir,
+                PathComparison::Lexical,
+            ));
+            assert!(are_paths_the_same(
+                &"B/file.txt",
+                &"A/file.txt",
+                &string_path_dir,
+                PathComparison::Canonical,
+            ));
+            assert!(!are_paths_the_same(
+                &"hard_link.txt",
+                &"A/file.txt",
+                &string_path_dir,
+                PathComparison::Canonical,
+            ));
+            assert!(are_paths_the_same(
+                &"hard_link.txt",
+                &"B/file.txt",
+                &string_path_dir,
+                PathComparison::SameFile,
+            ));
+        }
+    }
+
+    #[test]
+    fn test_get_path_normalized() {
+        assert_eq!(
+            get_path_normalized(&"/A/./B/../C/"),
+            Some("/A/C".to_string())
+        );
+        assert_eq!(get_path_normalized(&"/.."), Some("/".to_string()));
+        assert_eq!(
+            get_path_normalized(&"../A/../../B"),
+            Some("../../B".to_string())
+        );
+        assert_eq!(get_path_normalized(&"A/.."), Some(".".to_string()));
+    }
+
+    #[test]
+    fn test_get_relative_path_between() {
+        for (item_str_from, item_str_to, item_str_expected) in [
+            ("/A/X", "/A/B/C", "../B/C"),
+            ("/A/B", "/A/B/C/D", "C/D"),
+            ("/A/B/C", "/A", "../.."),
+            ("/A/B", "/X/Y", "../../X/Y"),
+            ("/A/./B/../B", "/A/B/", "."),
+        ] {
+            let result = match get_relative_path_between(
+                &item_str_from,
+                &item_str_to,
+                PathNormalization::Lexical,
+            ) {
+                Ok(string_result) => string_result,
+                Err(err) => panic!("{}", err,),
+            };
+            assert_eq!(result, item_str_expected.to_string())
+        }
+        let result = match get_relative_path_between(
+            &"test/test_a",
+            &"test/test_b/test_c/text_b_c_a.txt",
+            PathNormalization::Canonical,
+        ) {
+            Ok(string_result) => string_result,
+            Err(err) => panic!("{}", err,),
+        };
+        assert_eq!(result, "../test_b/test_c/text_b_c_a.txt".to_string());
+        match get_relative_path_between(&"test", &"test/missing", PathNormalization::Canonical) {
+            Ok(string_result) => panic!("Canonicalized a missing path: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+
     #[test]
     fn test_get_vec_by_splitting_path() {
         let string_path = "test/test_b/test_c/text_b_c_a.txt";
@@ -495,6 +773,31 @@ mod tests {
     }
 }
 //
+// Public - enums
+//
+/// How paths are normalized before they're compared or related to each other
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathNormalization {
+    /// '.' and '..' are resolved as text; the disk is never touched and symlinks are not followed
+    Lexical,
+    /// std::fs::canonicalize(): symlinks are resolved and the path must exist
+    Canonical,
+}
+
+/// How are_paths_the_same() and its variants decide that two paths are the same
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathComparison {
+    /// '.', '..' and trailing separators are resolved as text; the disk is never touched
+    Lexical,
+    /// Both paths are canonicalized, so symlinks are resolved; a path that doesn't exist is never the same
+    Canonical,
+    /// Both paths must exist and point to the same device and inode, which also catches hard links
+    /// and bind mounts. On non-unix platforms this falls back to Canonical
+    SameFile,
+    /// Like Lexical, but letter case is ignored, as on macOS and Windows volumes
+    CaseInsensitive,
+}
+//
 // Public - get - paths
 //
 /// This attempts to get the full path
@@ -580,13 +883,9 @@ pub fn get_absolute_path_or_error<T: Debug + Display>(
 /// let expected = "text_a_a.txt".to_string();
 /// assert_eq!( result, expected )
 pub fn get_base_name<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
-    match Path::new(&format!("{}", arg_string_path,)).file_name() {
-        Some(os_str_result) => match os_str_result.to_str() {
-            Some(str_result) => Some(str_result.to_string()),
-            None => return None,
-        },
-        None => return None,
-    }
+    EasyPathRef::from(format!("{}", arg_string_path,).as_str())
+        .base_name()
+        .map(|str_result| str_result.to_string())
 }
 
 /// Returns a string path which is shared between all paths in the slice of string-like values
@@ -610,102 +909,46 @@ pub fn get_base_name<T: Debug + Display>(arg_string_path: &T) -> Option<String>
 /// // fetching the 'parent' directory until it finds one that exists. In this scenario, that
 /// // directory would be '/A'
 pub fn get_common_path<T: Debug + Display>(arg_slice_of_strings: &[T]) -> Result<String, String> {
-    let vec_of_path_bufs =
-        get_path_bufs_sorted_by_size_starting_with_shortest(&arg_slice_of_strings);
-    let mut path_buf_prefix_to_return = match vec_of_path_bufs.get(0) {
-        Some(path_buf_result) => path_buf_result.clone(),
-        None => {
-            return Err([
-                "Error: Failed to get value at index 0.".to_string(),
-                format!(
-                    "arg_slice_of_strings.len() = {}",
-                    arg_slice_of_strings.len()
-                ),
-                format!(
-                    "arg_slice_of_strings.len() = {:#?}",
-                    arg_slice_of_strings.len()
-                ),
-            ]
-            .join("\n"))
-        }
-    };
-    loop {
-        let bool_all_path_bufs_meet_requirement = {
-            let mut bool_all_path_bufs_meet_requirement = true;
-            for item_path_buf in &vec_of_path_bufs {
-                if !item_path_buf.starts_with(&path_buf_prefix_to_return) {
-                    bool_all_path_bufs_meet_requirement = false;
-                    break;
-                }
-            }
-            bool_all_path_bufs_meet_requirement
-        };
-        if bool_all_path_bufs_meet_requirement {
-            match path_buf_prefix_to_return.to_str() {
-                Some(_str_result) => break,
-                None => {
-                    return Err([
-                        "Error: Failed to extract str from PathBuf.".to_string(),
-                        format!(
-                            "path buf value at failure = {:?}",
-                            path_buf_prefix_to_return
-                        ),
-                        format!("arg_slice_of_strings = {:#?}", arg_slice_of_strings,),
-                    ]
-                    .join("\n"))
-                }
-            }
-        } else {
-            path_buf_prefix_to_return = match path_buf_prefix_to_return.parent() {
-                Some(path_result) => PathBuf::from(path_result),
-                None => {
-                    return Err([
-                        "Error: Attempted to access non-existent parent.".to_string(),
-                        format!(
-                            "path buf value at failure = {:?}",
-                            path_buf_prefix_to_return
-                        ),
-                        format!("arg_slice_of_strings = {:#?}", arg_slice_of_strings,),
-                    ]
-                    .join("\n"))
-                }
-            }
-        }
-    }
+    get_common_path_with_fs(&OsFs, arg_slice_of_strings)
+}
+
+/// Same as get_common_path(), except existence is checked in arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem, ie OsFs or InMemoryFs
+/// * arg_slice_of_strings: slice of string-like paths
+/// # Examples
+/// let in_memory_fs = InMemoryFs::new().with_dir( &"/A/B" );
+/// let result = match get_common_path_with_fs( &in_memory_fs, &[ "/A/B/C", "/A/B/D" ] ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err ) }
+/// };
+/// // result = "/A/B"
+pub fn get_common_path_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_slice_of_strings: &[T],
+) -> Result<String, String> {
+    let string_prefix = get_common_prefix(arg_slice_of_strings)?;
     //
     // Keep getting parent dir until it exists
     //
+    let mut path_prefix = Path::new(&string_prefix);
     loop {
-        if path_buf_prefix_to_return.exists() {
+        if arg_file_system.exists(path_prefix) {
             break;
         }
-        path_buf_prefix_to_return = match path_buf_prefix_to_return.parent() {
-            Some(path_result) => PathBuf::from(path_result),
+        path_prefix = match path_prefix.parent() {
+            Some(path_result) => path_result,
             None => {
                 return Err([
                     "Error: Attempted to access non-existent parent.".to_string(),
-                    format!(
-                        "path buf value at failure = {:?}",
-                        path_buf_prefix_to_return
-                    ),
+                    format!("path buf value at failure = {:?}", path_prefix),
                     format!("arg_slice_of_strings = {:#?}", arg_slice_of_strings,),
                 ]
                 .join("\n"))
             }
         }
     }
-    match path_buf_prefix_to_return.to_str() {
-        Some(str_result) => Ok(str_result.to_string()),
-        None => Err([
-            "Error: Failed to extract str from PathBuf.".to_string(),
-            format!(
-                "path buf value at failure = {:?}",
-                path_buf_prefix_to_return
-            ),
-            format!("arg_slice_of_strings = {:#?}", arg_slice_of_strings,),
-        ]
-        .join("\n")),
-    }
+    Ok(path_prefix.to_string_lossy().into_owned())
 }
 
 /// Returns a string or None which is the same shared path represented within
@@ -797,17 +1040,9 @@ pub fn get_dir_ancestor_n_levels_up<T: Debug + Display>(
     arg_string_path: &T,
     arg_n: usize,
 ) -> Option<String> {
-    let mut path_buf = PathBuf::from(format!("{}", arg_string_path,));
-    for _ in 0..arg_n {
-        path_buf = match path_buf.parent() {
-            Some(path_result) => PathBuf::from(path_result),
-            None => return None,
-        };
-    }
-    match path_buf.to_str() {
-        Some(str_result) => Some(str_result.to_string()),
-        None => None,
-    }
+    EasyPathRef::from(format!("{}", arg_string_path,).as_str())
+        .parent_n(arg_n)
+        .map(|easy_path_ref_result| easy_path_ref_result.to_string())
 }
 
 /// Returns a string or None which is the part of the argument path that actually exists
@@ -822,20 +1057,23 @@ pub fn get_dir_ancestor_n_levels_up<T: Debug + Display>(
 /// // The returned string will be "/A/B" if it exists and "/A/B/C" does not
 /// // The returned string will be "/A" if all in-between paths do not exist
 pub fn get_dir_ancestor_that_exists<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
-    let mut path_buf = PathBuf::from(format!("{}", arg_string_path,));
-    loop {
-        if path_buf.exists() {
-            return match path_buf.to_str() {
-                Some(str_result) => Some(str_result.to_string()),
-                None => None,
-            };
-        } else {
-            path_buf = match path_buf.parent() {
-                Some(path_result) => PathBuf::from(path_result),
-                None => return None,
-            };
-        }
-    }
+    get_dir_ancestor_that_exists_with_fs(&OsFs, arg_string_path)
+}
+
+/// Same as get_dir_ancestor_that_exists(), except existence is checked in arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like path
+pub fn get_dir_ancestor_that_exists_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> Option<String> {
+    let string_path = format!("{}", arg_string_path,);
+    Path::new(&string_path)
+        .ancestors()
+        .find(|item_path| arg_file_system.exists(item_path))
+        .and_then(|path_result| path_result.to_str())
+        .map(|str_result| str_result.to_string())
 }
 
 /// Returns a string or None which is the path to the current working directory
@@ -877,13 +1115,9 @@ pub fn get_dir_cwd() -> Result<String, String> {
 /// };
 /// // result = "/A/B"
 pub fn get_dir_name<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
-    match PathBuf::from(format!("{}", arg_string_path,)).parent() {
-        Some(path_result) => match path_result.to_str() {
-            Some(str_result) => Some(str_result.to_string()),
-            None => None,
-        },
-        None => None,
-    }
+    EasyPathRef::from(format!("{}", arg_string_path,).as_str())
+        .dir_name()
+        .map(|easy_path_ref_result| easy_path_ref_result.to_string())
 }
 
 /// Returns a string representing the path to the project root directory
@@ -903,13 +1137,9 @@ pub fn get_dir_proj_root() -> String {
 /// };
 /// // result = "txt"
 pub fn get_extension<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
-    match Path::new(&format!("{}", arg_string_path,)).extension() {
-        Some(os_str_result) => match os_str_result.to_str() {
-            Some(str_result) => Some(str_result.to_string()),
-            None => return None,
-        },
-        None => return None,
-    }
+    EasyPathRef::from(format!("{}", arg_string_path,).as_str())
+        .extension()
+        .map(|str_result| str_result.to_string())
 }
 
 /// Returns a string path pointing at the binary file created by the compilation process
@@ -993,6 +1223,7 @@ pub fn get_only_file_paths_from_slice<T: Debug + Display>(
 }
 
 /// Returns a string path that is the result of combining a slice of string-like values
+/// An absolute element replaces everything before it; use get_path_joined_within() for untrusted parts.
 /// In case of a failure, this returns None
 /// # Arguments
 /// * arg_slice_of_strings: slice of string-likes
@@ -1014,6 +1245,27 @@ pub fn get_path_joined<T: Debug + Display>(arg_slice_of_strings: &[T]) -> Option
     }
 }
 
+/// Returns a string path with '.' and '..' resolved lexically
+/// The disk isn't touched, so symlinks are not followed ( 'link/..' is treated as '.' ).
+/// '..' directly under the root is dropped, while leading '..' in relative paths is kept.
+/// An empty relative result is returned as '.'
+/// In case of a failure, this returns None
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let string_path = match get_path_normalized( &"/A/./B/../C/" ) {
+///     Some( string_result ) => { string_result }
+///     None => { panic!( "Failed to normalize path." ) }
+/// };
+/// // string_path = "/A/C"
+pub fn get_path_normalized<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    let path_buf = get_path_buf_normalized(Path::new(&format!("{}", arg_string_path,)));
+    if path_buf.as_os_str().is_empty() {
+        return Some(".".to_string());
+    }
+    path_buf.to_str().map(|str_result| str_result.to_string())
+}
+
 /// Returns a string with the '~' expanded within the path
 /// # Arguments
 /// * arg_string_path: this is a string-like reference
@@ -1050,45 +1302,48 @@ pub fn get_path_with_tilde_expanded_if_necessary<T: Debug + Display>(
 /// ].iter().map( | item_str | { item_str.to_string() } ).collect::<Vec<String>>();
 /// assert_eq!( result, expected )
 pub fn get_paths_in_dir<T: Debug + Display>(arg_string_path: &T) -> Result<Vec<String>, String> {
-    let slice_of_read_dirs = match std::fs::read_dir(&format!("{}", arg_string_path,)) {
-        Ok(data_from_read_dir) => data_from_read_dir,
-        Err(err) => {
-            return Err([
-                "Error: failed to read directory.".to_string(),
-                format!("err = {}", err,),
-                format!("arg_string_path_dir = {}", &arg_string_path,),
-            ]
-            .join("\n"))
-        }
-    };
-    //
-    // Convert slice_of_read_dirs to a vec of strings
-    //
-    let mut vec_to_return = vec![];
-    for item_dir_entry_result in slice_of_read_dirs {
-        vec_to_return.push(match &item_dir_entry_result {
-            Ok(item_dir_entry_from_result) => match item_dir_entry_from_result.path().to_str() {
-                Some(str_from_path_buf_to_return) => str_from_path_buf_to_return.to_string(),
-                None => {
-                    return Err([
-                        "Error: failed to extract str from arg_path_buf".to_string(),
-                        format!(
-                            "item_dir_entry_from_result = {:?}",
-                            &item_dir_entry_from_result,
-                        ),
-                    ]
-                    .join("\n"))
-                }
-            },
+    get_paths_in_dir_with_fs(&OsFs, arg_string_path)
+}
+
+/// Same as get_paths_in_dir(), except the directory is read from arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like path
+/// # Examples
+/// let in_memory_fs = InMemoryFs::new().with_file( &"/test/a.txt", "" ).with_dir( &"/test/b" );
+/// let result = match get_paths_in_dir_with_fs( &in_memory_fs, &"/test" ) {
+///     Ok( vec_result ) => vec_result,
+///     Err( err ) => panic!( "{}", err, )
+/// };
+/// // result = [ "/test/a.txt", "/test/b" ]
+pub fn get_paths_in_dir_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> Result<Vec<String>, String> {
+    let vec_of_path_bufs =
+        match arg_file_system.read_dir(Path::new(&format!("{}", arg_string_path,))) {
+            Ok(vec_result) => vec_result,
             Err(err) => {
                 return Err([
-                    "Error: failed to extract item_dir_entry".to_string(),
+                    "Error: failed to read directory.".to_string(),
                     format!("err = {}", err,),
-                    format!("item_dir_entry_result = {:?}", &item_dir_entry_result,),
+                    format!("arg_string_path_dir = {}", &arg_string_path,),
                 ]
                 .join("\n"))
             }
-        })
+        };
+    let mut vec_to_return = vec![];
+    for item_path_buf in vec_of_path_bufs {
+        match item_path_buf.to_str() {
+            Some(str_result) => vec_to_return.push(str_result.to_string()),
+            None => {
+                return Err([
+                    "Error: failed to extract str from arg_path_buf".to_string(),
+                    format!("item_path_buf = {:?}", &item_path_buf,),
+                ]
+                .join("\n"))
+            }
+        }
     }
     Ok(vec_to_return)
 }
@@ -1115,135 +1370,53 @@ pub fn get_paths_in_dir<T: Debug + Display>(arg_string_path: &T) -> Result<Vec<S
 pub fn get_paths_in_dir_and_sub_dirs<T: Debug + Display>(
     arg_string_path: &T,
 ) -> Result<Vec<String>, String> {
-    let slice_of_read_dirs = match std::fs::read_dir(&format!("{}", arg_string_path,)) {
-        Ok(data_from_read_dir) => data_from_read_dir,
-        Err(err) => {
-            return Err([
-                "Error: failed to read directory.".to_string(),
-                format!("err = {}", err,),
-                format!("arg_string_path = {}", &arg_string_path,),
-            ]
-            .join("\n"))
-        }
-    };
-    let mut vec_of_string_paths_files_contained_in_dir = vec![];
-    for item_dir_entry_result in slice_of_read_dirs {
-        vec_of_string_paths_files_contained_in_dir.push(match &item_dir_entry_result {
-            Ok(item_dir_entry) => match item_dir_entry.path().to_str() {
-                Some(str_from_path_buf) => str_from_path_buf.to_string(),
-                None => {
-                    return Err([
-                        "Error: failed to extract str from arg_path_buf".to_string(),
-                        format!("item_dir_entry = {:?}", &item_dir_entry,),
-                    ]
-                    .join("\n"))
-                }
-            },
+    get_paths_in_dir_and_sub_dirs_with_fs(&OsFs, arg_string_path)
+}
+
+/// Same as get_paths_in_dir_and_sub_dirs(), except the directories are read from arg_file_system
+/// Symlinks to directories are followed, as they are by get_paths_in_dir_and_sub_dirs().
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like path
+/// # Examples
+/// let in_memory_fs = InMemoryFs::new()
+///     .with_file( &"/test/a/a.txt", "" )
+///     .with_error( &"/test/b", std::io::ErrorKind::PermissionDenied );
+/// let err = get_paths_in_dir_and_sub_dirs_with_fs( &in_memory_fs, &"/test" ).unwrap_err();
+pub fn get_paths_in_dir_and_sub_dirs_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> Result<Vec<String>, String> {
+    let mut stack_of_dir_entries_to_process =
+        get_paths_in_dir_with_fs(arg_file_system, arg_string_path)?;
+    let mut vec_to_return: Vec<String> = Vec::new();
+    while let Some(item_string_path_dir) = stack_of_dir_entries_to_process.pop() {
+        let metadata_from_path = match arg_file_system.metadata(Path::new(&item_string_path_dir)) {
+            Ok(metadata_extracted) => metadata_extracted,
             Err(err) => {
                 return Err([
-                    "Error: failed to extract item_dir_entry".to_string(),
-                    format!("err = {}", err,),
-                    format!("item_dir_entry_result = {:?}", &item_dir_entry_result,),
+                    "Error: failed to get meta data from arg_string_path.".to_string(),
+                    format!("err = {:?}", err,),
+                    format!("item_string_path_dir = {}", item_string_path_dir,),
+                    format!("arg_string_path = {}", arg_string_path,),
                 ]
                 .join("\n"))
             }
-        })
-    }
-    //
-    // Iterate through sub paths
-    //
-    let mut stack_of_dir_entries_to_process = Vec::from(vec_of_string_paths_files_contained_in_dir);
-    //
-    // Iterate over sub-paths and make sure nothing broke
-    //
-    let mut vec_to_return: Vec<String> = Vec::new();
-    loop {
-        match stack_of_dir_entries_to_process.pop() {
-            Some(item_string_path_dir) => {
-                //
-                // Create string copy to push to vec
-                //
-                vec_to_return.push(item_string_path_dir.clone());
-                //
-                // Prep next iteration
-                //
-                let metadata_from_path = match std::fs::metadata(&item_string_path_dir) {
-                    Ok(metadata_extracted) => metadata_extracted,
-                    Err(err) => {
-                        return Err([
-                            "Error: failed to get meta data from arg_string_path.".to_string(),
-                            format!("err = {:?}", err,),
-                            format!("arg_string_path = {}", arg_string_path,),
-                        ]
-                        .join("\n"))
-                    }
-                };
-                if metadata_from_path.is_dir() {
-                    stack_of_dir_entries_to_process.extend({
-                        let string_path_dir = format!("{}", item_string_path_dir,);
-                        let read_dir_results = match std::fs::read_dir(&string_path_dir) {
-                            Ok(data_from_read_dir) => data_from_read_dir,
-                            Err(err) => {
-                                return Err([
-                                    "Error: failed to read directory.".to_string(),
-                                    format!("err = {}", err,),
-                                    format!("string_path_dir = {}", &string_path_dir,),
-                                ]
-                                .join("\n"))
-                            }
-                        };
-                        let mut vec_of_results = vec![];
-                        for item_dir_entry_result in read_dir_results {
-                            //
-                            // Get string path from dir entry result
-                            //
-                            vec_of_results.push(match &item_dir_entry_result {
-                                Ok(item_dir_entry_from_result) => {
-                                    match item_dir_entry_from_result.path().to_str() {
-                                        Some(str_from_path_buf) => str_from_path_buf.to_string(),
-                                        None => {
-                                            return Err([
-                                                "Error: failed to extract str from arg_path_buf"
-                                                    .to_string(),
-                                                format!(
-                                                    "item_dir_entry_from_result.path() = {:?}",
-                                                    &item_dir_entry_from_result.path(),
-                                                ),
-                                            ]
-                                            .join("\n"))
-                                        }
-                                    }
-                                }
-                                Err(err) => {
-                                    return Err([
-                                        "Error: failed to extract item_dir_entry".to_string(),
-                                        format!("err = {}", err,),
-                                        format!(
-                                            "item_dir_entry_result = {:?}",
-                                            &item_dir_entry_result,
-                                        ),
-                                    ]
-                                    .join("\n"))
-                                }
-                            })
-                        }
-                        vec_of_results
-                    });
-                }
-            }
-            //
-            // Exit loop when we're out of sub directories
-            //
-            None => break,
+        };
+        if metadata_from_path.is_dir() {
+            stack_of_dir_entries_to_process.extend(get_paths_in_dir_with_fs(
+                arg_file_system,
+                &item_string_path_dir,
+            )?);
         }
+        vec_to_return.push(item_string_path_dir);
     }
-    //
-    // Return vec
-    //
     Ok(vec_to_return)
 }
 
 /// Returns a vec of string paths, sorted by their depth, with the shortest first
+/// Note: this actually sorts in PathBuf order, which only matches depth order for paths along one branch.
+/// Use get_paths_sorted_by_depth() or get_paths_sorted_by_length() for a true sort.
 /// # Arguments
 /// * arg_slice_of_strings: [] of string-likes
 /// # Examples
@@ -1298,16 +1471,24 @@ pub fn get_paths_sorted_by_size_starting_with_shortest<T: Debug + Display>(
 pub fn get_paths_to_only_dirs_in_dir_and_sub_dirs<T: Display>(
     arg_string_path_dir: &T,
 ) -> Result<Vec<String>, String> {
-    Ok(
-        match get_paths_in_dir_and_sub_dirs(&format!("{}", arg_string_path_dir,)) {
-            Ok(result) => result,
-            Err(err) => return Err(err),
-        }
-        .iter()
-        .filter(|item_string_path| Path::new(&format!("{}", item_string_path,)).is_dir())
-        .map(|item_string_path| item_string_path.clone())
-        .collect::<Vec<String>>(),
-    )
+    get_paths_to_only_dirs_in_dir_and_sub_dirs_with_fs(&OsFs, arg_string_path_dir)
+}
+
+/// Same as get_paths_to_only_dirs_in_dir_and_sub_dirs(), except everything is read from arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path_dir: string-like path
+pub fn get_paths_to_only_dirs_in_dir_and_sub_dirs_with_fs<F: FileSystem + ?Sized, T: Display>(
+    arg_file_system: &F,
+    arg_string_path_dir: &T,
+) -> Result<Vec<String>, String> {
+    Ok(get_paths_in_dir_and_sub_dirs_with_fs(
+        arg_file_system,
+        &format!("{}", arg_string_path_dir,),
+    )?
+    .into_iter()
+    .filter(|item_string_path| arg_file_system.is_dir(Path::new(item_string_path)))
+    .collect::<Vec<String>>())
 }
 
 /// Returns a vec of string paths inside directory
@@ -1329,16 +1510,24 @@ pub fn get_paths_to_only_dirs_in_dir_and_sub_dirs<T: Display>(
 pub fn get_paths_to_only_files_in_dir_and_sub_dirs<T: Display>(
     arg_string_path_dir: &T,
 ) -> Result<Vec<String>, String> {
-    Ok(
-        match get_paths_in_dir_and_sub_dirs(&format!("{}", arg_string_path_dir,)) {
-            Ok(result) => result,
-            Err(err) => return Err(err),
-        }
-        .iter()
-        .filter(|item_string_path| Path::new(&format!("{}", item_string_path,)).is_file())
-        .map(|item_string_path| item_string_path.clone())
-        .collect::<Vec<String>>(),
-    )
+    get_paths_to_only_files_in_dir_and_sub_dirs_with_fs(&OsFs, arg_string_path_dir)
+}
+
+/// Same as get_paths_to_only_files_in_dir_and_sub_dirs(), except everything is read from arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path_dir: string-like path
+pub fn get_paths_to_only_files_in_dir_and_sub_dirs_with_fs<F: FileSystem + ?Sized, T: Display>(
+    arg_file_system: &F,
+    arg_string_path_dir: &T,
+) -> Result<Vec<String>, String> {
+    Ok(get_paths_in_dir_and_sub_dirs_with_fs(
+        arg_file_system,
+        &format!("{}", arg_string_path_dir,),
+    )?
+    .into_iter()
+    .filter(|item_string_path| arg_file_system.is_file(Path::new(item_string_path)))
+    .collect::<Vec<String>>())
 }
 
 /// Returns a string that's a relative path after the prefix is removed
@@ -1383,6 +1572,74 @@ pub fn get_relative_path<T1: Debug + Display, T2: Debug + Display>(
     }
 }
 
+/// Returns the path to arg_string_path_to relative to the directory arg_string_path_dir_from,
+/// climbing with '..' as needed
+/// Both paths are first made absolute ( relative paths are taken from the cwd and '~' is expanded ),
+/// then normalized according to arg_path_normalization.
+/// If both paths are the same, this returns '.'
+/// In case of a failure ( cwd unavailable, path doesn't exist in Canonical mode, or the paths are on
+/// different drives / UNC shares ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_dir_from: string-like path to the starting directory
+/// * arg_string_path_to: string-like path to the destination
+/// * arg_path_normalization: PathNormalization
+/// # Examples
+/// let string_path_relative = match get_relative_path_between( &"/A/X", &"/A/B/C", PathNormalization::Lexical ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path_relative = "../B/C"
+pub fn get_relative_path_between<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_dir_from: &T1,
+    arg_string_path_to: &T2,
+    arg_path_normalization: PathNormalization,
+) -> Result<String, String> {
+    let path_buf_from =
+        get_path_buf_absolute_with_normalization(arg_string_path_dir_from, arg_path_normalization)?;
+    let path_buf_to =
+        get_path_buf_absolute_with_normalization(arg_string_path_to, arg_path_normalization)?;
+    let vec_of_components_from = path_buf_from.components().collect::<Vec<Component>>();
+    let vec_of_components_to = path_buf_to.components().collect::<Vec<Component>>();
+    let get_prefix = |arg_vec_of_components: &Vec<Component>| match arg_vec_of_components.first() {
+        Some(Component::Prefix(prefix_component)) => {
+            Some(prefix_component.as_os_str().to_os_string())
+        }
+        _ => None,
+    };
+    if get_prefix(&vec_of_components_from) != get_prefix(&vec_of_components_to) {
+        return Err([
+            "Error: paths are on different drives or UNC shares, so no relative path exists."
+                .to_string(),
+            format!("arg_string_path_dir_from = {}", arg_string_path_dir_from,),
+            format!("arg_string_path_to = {}", arg_string_path_to,),
+        ]
+        .join("\n"));
+    }
+    let int_count_shared = vec_of_components_from
+        .iter()
+        .zip(vec_of_components_to.iter())
+        .take_while(|(item_left, item_right)| item_left == item_right)
+        .count();
+    let mut path_buf_to_return = PathBuf::new();
+    for _ in int_count_shared..vec_of_components_from.len() {
+        path_buf_to_return.push("..");
+    }
+    for item_component in &vec_of_components_to[int_count_shared..] {
+        path_buf_to_return.push(item_component.as_os_str());
+    }
+    if path_buf_to_return.as_os_str().is_empty() {
+        return Ok(".".to_string());
+    }
+    match path_buf_to_return.to_str() {
+        Some(str_result) => Ok(str_result.to_string()),
+        None => Err([
+            "Error: failed getting str from path.".to_string(),
+            format!("path_buf_to_return = {:?}", path_buf_to_return,),
+        ]
+        .join("\n")),
+    }
+}
+
 /// Returns a vec resulting from splitting the path into substrings
 /// Returns None in case of failure
 /// # Arguments
@@ -1413,13 +1670,15 @@ pub fn get_vec_by_splitting_path<T: Debug + Display>(arg_string_path: &T) -> Opt
 /// * arg_string_path_left: string-like path to compare
 /// * arg_string_right: string-like path to compare
 /// * arg_string_path_working_dir: This serves as the 'root working directory' if relative paths are used
+/// * arg_path_comparison: PathComparison deciding what counts as the same path
 /// # Examples
-/// let result = are_paths_the_same( &"/<project dir>/test", &"test" );
+/// let result = are_paths_the_same( &"/<project dir>/test/../test", &"test/", PathComparison::Lexical );
 /// // result = true
 pub fn are_paths_the_same<T1: Debug + Display, T2: Debug + Display, T3: Debug + Display>(
     arg_string_path_left: &T1,
     arg_string_path_right: &T2,
     arg_string_path_working_dir: &T3,
+    arg_path_comparison: PathComparison,
 ) -> bool {
     let path_buf_working_dir = PathBuf::from(format!("{}", arg_string_path_working_dir,));
     let path_buf_left = {
@@ -1442,7 +1701,23 @@ pub fn are_paths_the_same<T1: Debug + Display, T2: Debug + Display, T3: Debug +
                 .collect::<PathBuf>()
         }
     };
-    path_buf_left == path_buf_right
+    match arg_path_comparison {
+        PathComparison::Lexical => {
+            get_path_buf_normalized(&path_buf_left) == get_path_buf_normalized(&path_buf_right)
+        }
+        PathComparison::CaseInsensitive => {
+            get_path_buf_normalized(&path_buf_left)
+                .to_string_lossy()
+                .to_lowercase()
+                == get_path_buf_normalized(&path_buf_right)
+                    .to_string_lossy()
+                    .to_lowercase()
+        }
+        PathComparison::Canonical => {
+            are_path_bufs_the_same_canonical(&path_buf_left, &path_buf_right)
+        }
+        PathComparison::SameFile => are_path_bufs_the_same_file(&path_buf_left, &path_buf_right),
+    }
 }
 
 /// Returns true if both paths are pointing to the same dir / file on the disk
@@ -1450,8 +1725,9 @@ pub fn are_paths_the_same<T1: Debug + Display, T2: Debug + Display, T3: Debug +
 /// Due to the cwd fetch's potential for errors, this function requires unpacking
 /// # Arguments
 /// * arg_string_path: string-like path
+/// * arg_path_comparison: PathComparison deciding what counts as the same path
 /// # Examples
-/// let result = match are_paths_the_same( &"/<project dir>/test", &"test" ) {
+/// let result = match are_paths_the_same_assume_cwd( &"/<project dir>/test", &"test", PathComparison::Canonical ) {
 ///     Ok( bool_result ) => { bool_result }
 ///     Err( err ) => { panic!( "{}", err, ) }
 /// };
@@ -1459,6 +1735,7 @@ pub fn are_paths_the_same<T1: Debug + Display, T2: Debug + Display, T3: Debug +
 pub fn are_paths_the_same_assume_cwd<T1: Debug + Display, T2: Debug + Display>(
     arg_string_path_left: &T1,
     arg_string_path_right: &T2,
+    arg_path_comparison: PathComparison,
 ) -> Result<bool, String> {
     Ok(are_paths_the_same(
         &arg_string_path_left,
@@ -1467,6 +1744,7 @@ pub fn are_paths_the_same_assume_cwd<T1: Debug + Display, T2: Debug + Display>(
             Ok(string_result) => string_result,
             Err(err) => return Err(err),
         },
+        arg_path_comparison,
     ))
 }
 
@@ -1475,17 +1753,20 @@ pub fn are_paths_the_same_assume_cwd<T1: Debug + Display, T2: Debug + Display>(
 /// If a relative path is used, this assumes the working directory is the project's root
 /// # Arguments
 /// * arg_string_path: string-like path
+/// * arg_path_comparison: PathComparison deciding what counts as the same path
 /// # Examples
-/// let result = are_paths_the_same( &"/<project dir>/test", &"test" );
+/// let result = are_paths_the_same_assume_project_dir( &"/<project dir>/test", &"./test", PathComparison::Lexical );
 /// // result = true
 pub fn are_paths_the_same_assume_project_dir<T1: Debug + Display, T2: Debug + Display>(
     arg_string_path_left: &T1,
     arg_string_path_right: &T2,
+    arg_path_comparison: PathComparison,
 ) -> bool {
     are_paths_the_same(
         &arg_string_path_left,
         &arg_string_path_right,
         &env!("CARGO_MANIFEST_DIR"),
+        arg_path_comparison,
     )
 }
 
@@ -1513,6 +1794,7 @@ pub fn is_absolute<T: Debug + Display>(arg_string_path: &T) -> bool {
 }
 
 /// Returns bool is path is a directory
+/// Symlinks are followed; use is_symlink() to tell them apart.
 /// # Arguments
 /// * arg_string_path: a string-like path
 /// # Examples
@@ -1521,6 +1803,17 @@ pub fn is_dir<T: Debug + Display>(arg_string_path: &T) -> bool {
     return PathBuf::from(format!("{}", arg_string_path,)).is_dir();
 }
 
+/// Returns true if the path is a directory in arg_file_system, following symlinks
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like path
+pub fn is_dir_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> bool {
+    arg_file_system.is_dir(Path::new(&format!("{}", arg_string_path,)))
+}
+
 /// Returns bool is path is a directory
 /// # Arguments
 /// * arg_string_path: string-like path
@@ -1530,7 +1823,19 @@ pub fn is_existing_path<T: Debug + Display>(arg_string_path: &T) -> bool {
     return PathBuf::from(format!("{}", arg_string_path,)).exists();
 }
 
+/// Returns true if the path exists in arg_file_system, following symlinks
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like path
+pub fn is_existing_path_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> bool {
+    arg_file_system.exists(Path::new(&format!("{}", arg_string_path,)))
+}
+
 /// Returns bool is path is a directory
+/// Symlinks are followed; use is_symlink() to tell them apart.
 /// # Arguments
 /// * arg_string_path: string-like path
 /// # Examples
@@ -1539,6 +1844,17 @@ pub fn is_file<T: Debug + Display>(arg_string_path: &T) -> bool {
     return PathBuf::from(format!("{}", arg_string_path,)).is_file();
 }
 
+/// Returns true if the path is a file in arg_file_system, following symlinks
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like path
+pub fn is_file_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> bool {
+    arg_file_system.is_file(Path::new(&format!("{}", arg_string_path,)))
+}
+
 /// Returns true if arg is type Path
 /// # Arguments
 /// * arg: any data type
@@ -1562,6 +1878,8 @@ pub fn is_path_buf_type<T>(_arg: &T) -> bool {
 }
 
 /// Returns true if arg_string_path is inside arg_string_dir_parent
+/// This is a purely lexical check, so '..' and symlinks can get around it; use
+/// is_path_inside_dir_parent_resolved() for untrusted paths.
 /// # Arguments
 /// * arg_string_path: string-like
 /// * arg_string_dir_parent: string-like
@@ -1587,20 +1905,29 @@ pub fn is_path_inside_dir_parent<T1: Display, T2: Display>(
 pub fn raise_error_if_path_does_not_exist<T: Debug + Display>(
     arg_string_path: &T,
 ) -> Result<(), String> {
-    if !Path::new(format!("{}", arg_string_path,).as_str()).exists() {
-        return Err(
-            [
-                "Error: path does not exist.".to_string(),
-                format!("arg_string_path = {}", arg_string_path,),
-                match get_dir_ancestor_that_exists(&arg_string_path) {
-                    Some(string_result) => {
-                        format!("ancestor that actually exists = {}", string_result,)
-                    }
-                    None => format!("No existing ancestor exists."),
-                },
-            ]
-            .join("\n"),
-        );
+    raise_error_if_path_does_not_exist_with_fs(&OsFs, arg_string_path)
+}
+
+/// Same as raise_error_if_path_does_not_exist(), except existence is checked in arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like
+pub fn raise_error_if_path_does_not_exist_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> Result<(), String> {
+    if !arg_file_system.exists(Path::new(&format!("{}", arg_string_path,))) {
+        return Err([
+            "Error: path does not exist.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+            match get_dir_ancestor_that_exists_with_fs(arg_file_system, arg_string_path) {
+                Some(string_result) => {
+                    format!("ancestor that actually exists = {}", string_result,)
+                }
+                None => "No existing ancestor exists.".to_string(),
+            },
+        ]
+        .join("\n"));
     }
     Ok(())
 }
@@ -1616,6 +1943,17 @@ pub fn raise_error_if_path_does_not_exist<T: Debug + Display>(
 /// };
 pub fn raise_error_if_path_is_not_in_project<T: Debug + Display>(
     arg_string_path: &T,
+) -> Result<(), String> {
+    raise_error_if_path_is_not_in_project_with_fs(&OsFs, arg_string_path)
+}
+
+/// Same as raise_error_if_path_is_not_in_project(), except relative paths are looked up in arg_file_system
+/// # Arguments
+/// * arg_file_system: any FileSystem
+/// * arg_string_path: string-like
+pub fn raise_error_if_path_is_not_in_project_with_fs<F: FileSystem + ?Sized, T: Debug + Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
 ) -> Result<(), String> {
     let path_buf_control = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
     let bool_raise_error = {
@@ -1623,21 +1961,16 @@ pub fn raise_error_if_path_is_not_in_project<T: Debug + Display>(
         if path_buf_from_arg.is_absolute() {
             !path_buf_from_arg.starts_with(&path_buf_control)
         } else {
-            ![&path_buf_control, &path_buf_from_arg]
-                .iter()
-                .collect::<PathBuf>()
-                .exists()
+            !arg_file_system.exists(&path_buf_control.join(&path_buf_from_arg))
         }
     };
     if bool_raise_error {
-        return Err(
-            [
-                "Error: arg_string_path is either the project directory or outside it.".to_string(),
-                format!("arg_string_path = {}", arg_string_path,),
-                format!("path_buf_control = {:?}", path_buf_control,),
-            ]
-            .join("\n"),
-        );
+        return Err([
+            "Error: arg_string_path is either the project directory or outside it.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+            format!("path_buf_control = {:?}", path_buf_control,),
+        ]
+        .join("\n"));
     }
     Ok(())
 }
@@ -1681,7 +2014,7 @@ pub fn raise_error_if_path_points_to_project_root<T: Display>(
 pub fn raise_error_if_path_points_to_src<T: Debug + Display>(
     arg_string_path: &T,
 ) -> Result<(), String> {
-    if are_paths_the_same_assume_project_dir(arg_string_path, &"src") {
+    if are_paths_the_same_assume_project_dir(arg_string_path, &"src", PathComparison::Lexical) {
         return Err(
             [
                 "Error: arg_string_path points at the src directory.".to_string(),
@@ -1705,7 +2038,11 @@ pub fn raise_error_if_path_points_to_src<T: Debug + Display>(
 pub fn raise_error_if_path_points_to_cargo_toml<T: Debug + Display>(
     arg_string_path: &T,
 ) -> Result<(), String> {
-    if are_paths_the_same_assume_project_dir(arg_string_path, &"Cargo.toml") {
+    if are_paths_the_same_assume_project_dir(
+        arg_string_path,
+        &"Cargo.toml",
+        PathComparison::Lexical,
+    ) {
         return Err(
             [
                 "Error: arg_string_path points at Cargo.toml.".to_string(),
@@ -1729,7 +2066,11 @@ pub fn raise_error_if_path_points_to_cargo_toml<T: Debug + Display>(
 pub fn raise_error_if_path_points_to_main_rs<T: Debug + Display>(
     arg_string_path: &T,
 ) -> Result<(), String> {
-    if are_paths_the_same_assume_project_dir(arg_string_path, &"src/main.rs") {
+    if are_paths_the_same_assume_project_dir(
+        arg_string_path,
+        &"src/main.rs",
+        PathComparison::Lexical,
+    ) {
         return Err(
             [
                 "Error: arg_string_path points at main.rs.".to_string(),
@@ -1820,6 +2161,79 @@ pub fn get_string_from_type_path_buf(arg_path_buf: &PathBuf) -> Option<String> {
 //
 // Private
 //
+/// Returns true if both paths canonicalize to the same path, and false if either can't be canonicalized
+fn are_path_bufs_the_same_canonical(arg_path_buf_left: &Path, arg_path_buf_right: &Path) -> bool {
+    match (
+        std::fs::canonicalize(arg_path_buf_left),
+        std::fs::canonicalize(arg_path_buf_right),
+    ) {
+        (Ok(path_buf_left), Ok(path_buf_right)) => path_buf_left == path_buf_right,
+        _ => false,
+    }
+}
+
+/// Returns true if both paths exist and share a device and inode
+#[cfg(unix)]
+fn are_path_bufs_the_same_file(arg_path_buf_left: &Path, arg_path_buf_right: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (
+        std::fs::metadata(arg_path_buf_left),
+        std::fs::metadata(arg_path_buf_right),
+    ) {
+        (Ok(metadata_left), Ok(metadata_right)) => {
+            metadata_left.dev() == metadata_right.dev()
+                && metadata_left.ino() == metadata_right.ino()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn are_path_bufs_the_same_file(arg_path_buf_left: &Path, arg_path_buf_right: &Path) -> bool {
+    are_path_bufs_the_same_canonical(arg_path_buf_left, arg_path_buf_right)
+}
+
+/// Returns the path made absolute against the cwd, then normalized
+pub(crate) fn get_path_buf_absolute_with_normalization<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_path_normalization: PathNormalization,
+) -> Result<PathBuf, String> {
+    match arg_path_normalization {
+        PathNormalization::Canonical => {
+            Ok(PathBuf::from(get_absolute_path_or_error(arg_string_path)?))
+        }
+        PathNormalization::Lexical => {
+            let path_buf =
+                PathBuf::from(get_path_with_tilde_expanded_if_necessary(arg_string_path));
+            if path_buf.is_absolute() {
+                return Ok(get_path_buf_normalized(&path_buf));
+            }
+            Ok(get_path_buf_normalized(
+                &PathBuf::from(get_dir_cwd()?).join(path_buf),
+            ))
+        }
+    }
+}
+
+/// Returns the path with '.' and '..' resolved without touching the disk
+fn get_path_buf_normalized(arg_path: &Path) -> PathBuf {
+    let mut vec_of_components: Vec<Component> = vec![];
+    for item_component in arg_path.components() {
+        match item_component {
+            Component::CurDir => {}
+            Component::ParentDir => match vec_of_components.last() {
+                Some(Component::Normal(_)) => {
+                    vec_of_components.pop();
+                }
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => vec_of_components.push(item_component),
+            },
+            _ => vec_of_components.push(item_component),
+        }
+    }
+    vec_of_components.iter().collect::<PathBuf>()
+}
+
 fn get_path_bufs_sorted_by_size_starting_with_shortest<T: Debug + Display>(
     arg_slice_of_strings: &[T],
 ) -> Vec<PathBuf> {
@@ -0,0 +1,462 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{ErrorKind, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+//
+// Public - enums
+//
+/// Which kind of lock a FileLockGuard holds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileLockKind {
+    /// Only one holder at a time, and no shared holders
+    Exclusive,
+    /// Any number of holders at a time, but no exclusive holder
+    Shared,
+}
+//
+// Public - structs
+//
+/// An advisory lock on a file, released when this is dropped
+/// Locks use flock() on Unix and LockFileEx() on Windows. On Unix they're advisory: they only keep out
+/// processes that also lock the file. They belong to the open file, so two guards for the same path
+/// conflict even within one process.
+#[derive(Debug)]
+pub struct FileLockGuard {
+    file: File,
+    path_buf: PathBuf,
+    file_lock_kind: FileLockKind,
+}
+
+impl FileLockGuard {
+    /// Returns the locked file, ie to read or write through it
+    pub fn file(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns the kind of lock held
+    pub fn kind(&self) -> FileLockKind {
+        self.file_lock_kind
+    }
+
+    /// Returns the path that was locked
+    pub fn path(&self) -> &Path {
+        &self.path_buf
+    }
+}
+
+impl Drop for FileLockGuard {
+    fn drop(&mut self) {
+        // Closing the file releases the lock anyway; this just doesn't wait for it
+        let _ = self.file.unlock();
+    }
+}
+
+/// A lock file holding the PID of the one process allowed to run, removed when this is dropped
+/// The file stays locked for as long as this exists, and the OS drops the lock when the process exits,
+/// however it exits. A lock file that's present but not locked was left by a process that's gone,
+/// so it's taken over; was_stale() reports when that happened.
+/// # Examples
+/// let pid_lock_file = match PidLockFile::acquire( &"/run/user/1000/my_daemon.pid" ) {
+///     Ok( pid_lock_file ) => { pid_lock_file }
+///     Err( err ) => { panic!( "{}", err, ) }   // names the PID that's already running
+/// };
+#[derive(Debug)]
+pub struct PidLockFile {
+    file: File,
+    path_buf: PathBuf,
+    bool_was_stale: bool,
+}
+
+impl PidLockFile {
+    /// Returns the lock for arg_string_path, writing this process's PID to it
+    /// In case of a failure ( ie another live process holds it ), this returns an error explaining what happened
+    /// # Arguments
+    /// * arg_string_path: string-like path to the lock file
+    pub fn acquire<T: Debug + Display>(arg_string_path: &T) -> Result<Self, String> {
+        let path_buf = PathBuf::from(format!("{}", arg_string_path,));
+        // Another process can remove the file between our open() and lock(); then the lock is on a
+        // file nobody else can see, so open the path again
+        const INT_ATTEMPTS_MAX: usize = 10;
+        for _ in 0..INT_ATTEMPTS_MAX {
+            let mut file = get_file_opened_for_lock(&path_buf, FileLockKind::Exclusive)
+                .map_err(|err| get_string_error("failed to open lock file.", err, &path_buf))?;
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(TryLockError::WouldBlock) => {
+                    return Err([
+                        "Error: lock file is held by another process.".to_string(),
+                        match get_pid_from_file(&mut file) {
+                            Some(int_pid) => format!("pid = {}", int_pid,),
+                            None => "pid = unknown".to_string(),
+                        },
+                        format!("arg_string_path = {}", arg_string_path,),
+                    ]
+                    .join("\n"));
+                }
+                Err(TryLockError::Error(err)) => {
+                    return Err(get_string_error(
+                        "failed to lock lock file.",
+                        err,
+                        &path_buf,
+                    ));
+                }
+            }
+            if !is_file_at_path(&file, &path_buf) {
+                continue;
+            }
+            let bool_was_stale = get_pid_from_file(&mut file).is_some();
+            let result_written = file
+                .set_len(0)
+                .and_then(|_| file.seek(SeekFrom::Start(0)))
+                .and_then(|_| file.write_all(format!("{}\n", std::process::id(),).as_bytes()))
+                .and_then(|_| file.sync_all());
+            if let Err(err) = result_written {
+                return Err(get_string_error("failed to write PID.", err, &path_buf));
+            }
+            return Ok(PidLockFile {
+                file,
+                path_buf,
+                bool_was_stale,
+            });
+        }
+        Err([
+            "Error: lock file kept being replaced while locking it.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n"))
+    }
+
+    /// Returns the PID of the live process holding arg_string_path, or None if nobody holds it
+    /// In case of a failure ( ie the file can't be read ), this returns an error explaining what happened
+    /// # Arguments
+    /// * arg_string_path: string-like path to the lock file
+    pub fn get_pid_of_holder<T: Debug + Display>(
+        arg_string_path: &T,
+    ) -> Result<Option<u32>, String> {
+        let path_buf = PathBuf::from(format!("{}", arg_string_path,));
+        let mut file = match File::open(&path_buf) {
+            Ok(file) => file,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(get_string_error(
+                    "failed to open lock file.",
+                    err,
+                    &path_buf,
+                ))
+            }
+        };
+        match file.try_lock_shared() {
+            Ok(()) => Ok(None),
+            Err(TryLockError::WouldBlock) => Ok(get_pid_from_file(&mut file)),
+            Err(TryLockError::Error(err)) => Err(get_string_error(
+                "failed to check lock file.",
+                err,
+                &path_buf,
+            )),
+        }
+    }
+
+    /// Returns the path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path_buf
+    }
+
+    /// Returns true if a process that's gone had left the lock file behind
+    pub fn was_stale(&self) -> bool {
+        self.bool_was_stale
+    }
+}
+
+impl Drop for PidLockFile {
+    fn drop(&mut self) {
+        // Remove the file while it's still locked, so nobody can lock it in between. std opens files
+        // with FILE_SHARE_DELETE on Windows, so this works there too.
+        let _ = std::fs::remove_file(&self.path_buf);
+        let _ = self.file.unlock();
+    }
+}
+//
+// Public - lock
+//
+/// Returns an exclusive lock on arg_string_path, waiting for as long as it's held elsewhere
+/// The file is created if it doesn't exist, and is never truncated.
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let file_lock_guard = match lock_file_exclusive( &"cache/index.lock" ) {
+///     Ok( file_lock_guard ) => { file_lock_guard }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // The lock is released when file_lock_guard goes out of scope
+pub fn lock_file_exclusive<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<FileLockGuard, String> {
+    lock_file_blocking(arg_string_path, FileLockKind::Exclusive)
+}
+
+/// Returns a shared lock on arg_string_path, waiting for as long as an exclusive lock is held elsewhere
+/// The file is created if it doesn't exist, and opened read-only if it can't be opened for writing.
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path
+pub fn lock_file_shared<T: Debug + Display>(arg_string_path: &T) -> Result<FileLockGuard, String> {
+    lock_file_blocking(arg_string_path, FileLockKind::Shared)
+}
+
+/// Returns an exclusive lock on arg_string_path, or None without waiting if it's held elsewhere
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// match try_lock_file_exclusive( &"cache/index.lock" ) {
+///     Ok( Some( file_lock_guard ) ) => { /* rebuild the cache */ }
+///     Ok( None ) => { /* another process is already rebuilding it */ }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn try_lock_file_exclusive<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<Option<FileLockGuard>, String> {
+    try_lock_file(arg_string_path, FileLockKind::Exclusive)
+}
+
+/// Returns a shared lock on arg_string_path, or None without waiting if an exclusive lock is held elsewhere
+/// # Arguments
+/// * arg_string_path: string-like path
+pub fn try_lock_file_shared<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<Option<FileLockGuard>, String> {
+    try_lock_file(arg_string_path, FileLockKind::Shared)
+}
+
+/// Returns an exclusive lock on arg_string_path, or an error if it's still held elsewhere after arg_duration_timeout
+/// # Arguments
+/// * arg_string_path: string-like path
+/// * arg_duration_timeout: how long to keep trying
+pub fn lock_file_exclusive_with_timeout<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_duration_timeout: Duration,
+) -> Result<FileLockGuard, String> {
+    lock_file_with_timeout(
+        arg_string_path,
+        FileLockKind::Exclusive,
+        arg_duration_timeout,
+    )
+}
+
+/// Returns a shared lock on arg_string_path, or an error if an exclusive lock is still held elsewhere after arg_duration_timeout
+/// # Arguments
+/// * arg_string_path: string-like path
+/// * arg_duration_timeout: how long to keep trying
+pub fn lock_file_shared_with_timeout<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_duration_timeout: Duration,
+) -> Result<FileLockGuard, String> {
+    lock_file_with_timeout(arg_string_path, FileLockKind::Shared, arg_duration_timeout)
+}
+//
+// Private
+//
+fn get_file_opened_for_lock(
+    arg_path: &Path,
+    arg_file_lock_kind: FileLockKind,
+) -> std::io::Result<File> {
+    let result = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(arg_path);
+    match result {
+        Err(err)
+            if err.kind() == ErrorKind::PermissionDenied
+                && arg_file_lock_kind == FileLockKind::Shared =>
+        {
+            File::open(arg_path)
+        }
+        _ => result,
+    }
+}
+
+fn get_file_and_path_buf_for_lock<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_file_lock_kind: FileLockKind,
+) -> Result<(File, PathBuf), String> {
+    let path_buf = PathBuf::from(format!("{}", arg_string_path,));
+    match get_file_opened_for_lock(&path_buf, arg_file_lock_kind) {
+        Ok(file) => Ok((file, path_buf)),
+        Err(err) => Err(get_string_error(
+            "failed to open file to lock.",
+            err,
+            &path_buf,
+        )),
+    }
+}
+
+/// Returns the PID in the file, if it holds one
+fn get_pid_from_file(arg_file: &mut File) -> Option<u32> {
+    let mut string_contents = String::new();
+    arg_file.seek(SeekFrom::Start(0)).ok()?;
+    arg_file.read_to_string(&mut string_contents).ok()?;
+    string_contents.trim().parse::<u32>().ok()
+}
+
+fn get_string_error(arg_str_message: &str, arg_err: std::io::Error, arg_path: &Path) -> String {
+    [
+        format!("Error: {}", arg_str_message,),
+        format!("err = {}", arg_err,),
+        format!("path = {}", arg_path.display(),),
+    ]
+    .join("\n")
+}
+
+/// Returns true if arg_path still names the open file
+#[cfg(unix)]
+fn is_file_at_path(arg_file: &File, arg_path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (arg_file.metadata(), std::fs::metadata(arg_path)) {
+        (Ok(metadata_file), Ok(metadata_path)) => {
+            metadata_file.dev() == metadata_path.dev() && metadata_file.ino() == metadata_path.ino()
+        }
+        _ => false,
+    }
+}
+
+/// Without inode numbers to compare, trust that the path wasn't replaced
+#[cfg(not(unix))]
+fn is_file_at_path(_arg_file: &File, _arg_path: &Path) -> bool {
+    true
+}
+
+fn lock_file_blocking<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_file_lock_kind: FileLockKind,
+) -> Result<FileLockGuard, String> {
+    let (file, path_buf) = get_file_and_path_buf_for_lock(arg_string_path, arg_file_lock_kind)?;
+    let result = match arg_file_lock_kind {
+        FileLockKind::Exclusive => file.lock(),
+        FileLockKind::Shared => file.lock_shared(),
+    };
+    match result {
+        Ok(()) => Ok(FileLockGuard {
+            file,
+            path_buf,
+            file_lock_kind: arg_file_lock_kind,
+        }),
+        Err(err) => Err(get_string_error("failed to lock file.", err, &path_buf)),
+    }
+}
+
+fn lock_file_with_timeout<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_file_lock_kind: FileLockKind,
+    arg_duration_timeout: Duration,
+) -> Result<FileLockGuard, String> {
+    const DURATION_SLEEP_MAX: Duration = Duration::from_millis(50);
+    let instant_deadline = Instant::now() + arg_duration_timeout;
+    let mut duration_sleep = Duration::from_millis(1);
+    loop {
+        if let Some(file_lock_guard) = try_lock_file(arg_string_path, arg_file_lock_kind)? {
+            return Ok(file_lock_guard);
+        }
+        let instant_now = Instant::now();
+        if instant_now >= instant_deadline {
+            return Err([
+                "Error: timed out waiting for file lock.".to_string(),
+                format!("arg_duration_timeout = {:?}", arg_duration_timeout,),
+                format!("arg_string_path = {}", arg_string_path,),
+            ]
+            .join("\n"));
+        }
+        std::thread::sleep(duration_sleep.min(instant_deadline - instant_now));
+        duration_sleep = (duration_sleep * 2).min(DURATION_SLEEP_MAX);
+    }
+}
+
+fn try_lock_file<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_file_lock_kind: FileLockKind,
+) -> Result<Option<FileLockGuard>, String> {
+    let (file, path_buf) = get_file_and_path_buf_for_lock(arg_string_path, arg_file_lock_kind)?;
+    let result = match arg_file_lock_kind {
+        FileLockKind::Exclusive => file.try_lock(),
+        FileLockKind::Shared => file.try_lock_shared(),
+    };
+    match result {
+        Ok(()) => Ok(Some(FileLockGuard {
+            file,
+            path_buf,
+            file_lock_kind: arg_file_lock_kind,
+        })),
+        Err(TryLockError::WouldBlock) => Ok(None),
+        Err(TryLockError::Error(err)) => {
+            Err(get_string_error("failed to lock file.", err, &path_buf))
+        }
+    }
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lock_file() {
+        let string_dir = crate::get_dir_temp_for_tests("lock_file");
+        let string_path = format!("{}/cache.lock", string_dir,);
+        {
+            let file_lock_guard = lock_file_exclusive(&string_path).unwrap();
+            assert_eq!(file_lock_guard.kind(), FileLockKind::Exclusive);
+            assert!(try_lock_file_exclusive(&string_path).unwrap().is_none());
+            assert!(try_lock_file_shared(&string_path).unwrap().is_none());
+            assert!(
+                lock_file_shared_with_timeout(&string_path, Duration::from_millis(20)).is_err()
+            );
+        }
+        {
+            let _file_lock_guard_first = lock_file_shared(&string_path).unwrap();
+            let _file_lock_guard_second = try_lock_file_shared(&string_path).unwrap().unwrap();
+            assert!(try_lock_file_exclusive(&string_path).unwrap().is_none());
+        }
+        // Released on drop, so this doesn't have to wait
+        let file_lock_guard =
+            lock_file_exclusive_with_timeout(&string_path, Duration::from_millis(20)).unwrap();
+        assert_eq!(file_lock_guard.path(), Path::new(&string_path));
+        assert!(try_lock_file_exclusive(&format!("{}/missing/a.lock", string_dir,)).is_err());
+    }
+
+    #[test]
+    fn test_pid_lock_file() {
+        let string_dir = crate::get_dir_temp_for_tests("pid_lock_file");
+        let string_path = format!("{}/daemon.pid", string_dir,);
+        assert_eq!(PidLockFile::get_pid_of_holder(&string_path), Ok(None));
+        {
+            let pid_lock_file = PidLockFile::acquire(&string_path).unwrap();
+            assert!(!pid_lock_file.was_stale());
+            assert_eq!(
+                std::fs::read_to_string(&string_path).unwrap(),
+                format!("{}\n", std::process::id(),)
+            );
+            assert_eq!(
+                PidLockFile::get_pid_of_holder(&string_path),
+                Ok(Some(std::process::id()))
+            );
+            let string_err = PidLockFile::acquire(&string_path).unwrap_err();
+            assert!(string_err.contains(&format!("pid = {}", std::process::id(),)));
+        }
+        assert!(!Path::new(&string_path).exists());
+        // Left behind by a process that's gone, so nothing holds the lock
+        std::fs::write(&string_path, "4194304\n").unwrap();
+        assert_eq!(PidLockFile::get_pid_of_holder(&string_path), Ok(None));
+        let pid_lock_file = PidLockFile::acquire(&string_path).unwrap();
+        assert!(pid_lock_file.was_stale());
+        assert_eq!(
+            std::fs::read_to_string(pid_lock_file.path()).unwrap(),
+            format!("{}\n", std::process::id(),)
+        );
+    }
+}
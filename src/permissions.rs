@@ -0,0 +1,281 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::fs::Permissions;
+use std::os::unix::fs::{MetadataExt, PermissionsExt};
+use std::path::Path;
+//
+// Libraries - local
+//
+use crate::find;
+//
+// Public - structs
+//
+/// Paths in a tree whose permissions or ownership are worth a second look, from get_permission_report()
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PermissionReport {
+    /// Files and directories anyone can write to; symlinks are left out, since their own mode is meaningless
+    pub world_writable: Vec<String>,
+    /// Files, directories and symlinks whose owner isn't the effective user of this process
+    pub not_owned_by_current_user: Vec<String>,
+}
+
+impl PermissionReport {
+    /// Returns true if nothing was found
+    pub fn is_empty(&self) -> bool {
+        self.world_writable.is_empty() && self.not_owned_by_current_user.is_empty()
+    }
+}
+//
+// Public - get
+//
+/// Returns the world-writable paths and the paths not owned by the current user in a tree, like find -perm -o+w and find ! -user
+/// The directory itself is included. Symlinks are reported but not followed.
+/// In case of a failure ( ie part of the tree can't be read ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_dir: string-like path to a directory
+/// # Examples
+/// let permission_report = match get_permission_report( &"/srv/app" ) {
+///     Ok( permission_report ) => { permission_report }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// for item_string_path in &permission_report.world_writable { println!( "{}", item_string_path ) }
+pub fn get_permission_report<T: Debug + Display>(
+    arg_string_path_dir: &T,
+) -> Result<PermissionReport, String> {
+    // SAFETY: geteuid() has no preconditions and can't fail
+    let int_uid_current = unsafe { libc::geteuid() };
+    let mut permission_report = PermissionReport::default();
+    for item_string_path in get_paths_in_tree(arg_string_path_dir)? {
+        let metadata = get_metadata(&item_string_path)?;
+        if !metadata.file_type().is_symlink() && metadata.mode() & 0o002 != 0 {
+            permission_report
+                .world_writable
+                .push(item_string_path.clone());
+        }
+        if metadata.uid() != int_uid_current {
+            permission_report
+                .not_owned_by_current_user
+                .push(item_string_path);
+        }
+    }
+    Ok(permission_report)
+}
+//
+// Public - set
+//
+/// Sets arg_string_path's permission bits to arg_int_mode, following symlinks, like chmod
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path
+/// * arg_int_mode: mode bits, ie 0o640
+/// # Examples
+/// match set_permissions_mode( &"config/secrets.toml", 0o600 ) {
+///     Ok( () ) => {}
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn set_permissions_mode<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_int_mode: u32,
+) -> Result<(), String> {
+    match std::fs::set_permissions(
+        format!("{}", arg_string_path,),
+        Permissions::from_mode(arg_int_mode),
+    ) {
+        Ok(()) => Ok(()),
+        Err(err) => Err([
+            "Error: failed to set permissions.".to_string(),
+            format!("err = {}", err,),
+            format!("arg_int_mode = {:o}", arg_int_mode,),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Sets arg_int_mode_file on every file and arg_int_mode_dir on every directory in a tree, including the directory itself
+/// Symlinks are skipped rather than followed, so a link can't carry the change outside the tree.
+/// The whole tree is listed first, and directories are changed last, deepest first, so a mode
+/// without read or execute bits doesn't lock the walk out part-way.
+/// Returns the number of paths changed
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_dir: string-like path to a directory
+/// * arg_int_mode_file: mode bits for files, ie 0o640
+/// * arg_int_mode_dir: mode bits for directories, ie 0o750
+/// # Examples
+/// let int_count = match set_permissions_recursive( &"/srv/app", 0o640, 0o750 ) {
+///     Ok( int_result ) => { int_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn set_permissions_recursive<T: Debug + Display>(
+    arg_string_path_dir: &T,
+    arg_int_mode_file: u32,
+    arg_int_mode_dir: u32,
+) -> Result<usize, String> {
+    let mut vec_of_string_paths_dirs = vec![];
+    let mut int_count = 0;
+    for item_string_path in get_paths_in_tree(arg_string_path_dir)? {
+        let metadata = get_metadata(&item_string_path)?;
+        if metadata.file_type().is_symlink() {
+            continue;
+        }
+        if metadata.is_dir() {
+            vec_of_string_paths_dirs.push(item_string_path);
+            continue;
+        }
+        set_permissions_mode(&item_string_path, arg_int_mode_file)?;
+        int_count += 1;
+    }
+    vec_of_string_paths_dirs.sort_by_key(|item_string_path| {
+        std::cmp::Reverse(Path::new(item_string_path).components().count())
+    });
+    for item_string_path in vec_of_string_paths_dirs {
+        set_permissions_mode(&item_string_path, arg_int_mode_dir)?;
+        int_count += 1;
+    }
+    Ok(int_count)
+}
+
+/// Sets the owner and group of everything in a tree, including the directory itself, like chown -R
+/// Symlinks are changed themselves and never followed. None leaves that id as it is.
+/// Changing the owner usually needs root; changing the group needs membership in it.
+/// Returns the number of paths changed
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_dir: string-like path to a directory
+/// * arg_option_uid: Option<u32>
+/// * arg_option_gid: Option<u32>
+/// # Examples
+/// let int_count = match chown_recursive( &"/srv/app", Some( 1000 ), Some( 1000 ) ) {
+///     Ok( int_result ) => { int_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn chown_recursive<T: Debug + Display>(
+    arg_string_path_dir: &T,
+    arg_option_uid: Option<u32>,
+    arg_option_gid: Option<u32>,
+) -> Result<usize, String> {
+    let vec_of_string_paths = get_paths_in_tree(arg_string_path_dir)?;
+    for item_string_path in &vec_of_string_paths {
+        if let Err(err) =
+            std::os::unix::fs::lchown(item_string_path, arg_option_uid, arg_option_gid)
+        {
+            return Err([
+                "Error: failed to change owner.".to_string(),
+                format!("err = {}", err,),
+                format!("arg_option_uid = {:?}", arg_option_uid,),
+                format!("arg_option_gid = {:?}", arg_option_gid,),
+                format!("item_string_path = {}", item_string_path,),
+            ]
+            .join("\n"));
+        }
+    }
+    Ok(vec_of_string_paths.len())
+}
+
+/// Adds execute bits wherever arg_string_path has read bits, like chmod +x without a umask
+/// A file readable by its owner and group, but not others, becomes executable by its owner and group.
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// match make_executable( &"scripts/deploy.sh" ) {
+///     Ok( () ) => {}
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // 0o644 becomes 0o755, and 0o640 becomes 0o750
+pub fn make_executable<T: Debug + Display>(arg_string_path: &T) -> Result<(), String> {
+    let int_mode = match std::fs::metadata(format!("{}", arg_string_path,)) {
+        Ok(metadata) => metadata.mode() & 0o7777,
+        Err(err) => {
+            return Err([
+                "Error: failed to get metadata.".to_string(),
+                format!("err = {}", err,),
+                format!("arg_string_path = {}", arg_string_path,),
+            ]
+            .join("\n"))
+        }
+    };
+    set_permissions_mode(arg_string_path, int_mode | ((int_mode & 0o444) >> 2))
+}
+//
+// Private
+//
+fn get_metadata(arg_str_path: &str) -> Result<std::fs::Metadata, String> {
+    std::fs::symlink_metadata(arg_str_path).map_err(|err| {
+        [
+            "Error: failed to get metadata.".to_string(),
+            format!("err = {}", err,),
+            format!("arg_str_path = {}", arg_str_path,),
+        ]
+        .join("\n")
+    })
+}
+
+/// Returns the directory and everything under it, without following symlinks
+fn get_paths_in_tree<T: Debug + Display>(arg_string_path_dir: &T) -> Result<Vec<String>, String> {
+    find(arg_string_path_dir)
+        .min_depth(0)
+        .into_iter()
+        .collect::<Result<Vec<String>, String>>()
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_int_mode(arg_str_path: &str) -> u32 {
+        std::fs::symlink_metadata(arg_str_path).unwrap().mode() & 0o7777
+    }
+
+    #[test]
+    fn test_set_permissions() {
+        let string_dir = crate::get_dir_temp_for_tests("set_permissions");
+        let string_path_dir_sub = format!("{}/sub", string_dir,);
+        let string_path_file = format!("{}/sub/a.sh", string_dir,);
+        let string_path_outside = format!("{}_outside.txt", string_dir,);
+        std::fs::create_dir(&string_path_dir_sub).unwrap();
+        std::fs::write(&string_path_file, "").unwrap();
+        std::fs::write(&string_path_outside, "").unwrap();
+        set_permissions_mode(&string_path_outside, 0o600).unwrap();
+        std::os::unix::fs::symlink(&string_path_outside, format!("{}/link", string_dir,)).unwrap();
+        assert_eq!(
+            set_permissions_recursive(&string_dir, 0o640, 0o750).unwrap(),
+            3
+        );
+        assert_eq!(get_int_mode(&string_dir), 0o750);
+        assert_eq!(get_int_mode(&string_path_dir_sub), 0o750);
+        assert_eq!(get_int_mode(&string_path_file), 0o640);
+        // The link's target is outside the tree, so it's left alone
+        assert_eq!(get_int_mode(&string_path_outside), 0o600);
+        make_executable(&string_path_file).unwrap();
+        assert_eq!(get_int_mode(&string_path_file), 0o750);
+        assert!(set_permissions_mode(&format!("{}/missing", string_dir,), 0o600).is_err());
+        std::fs::remove_file(&string_path_outside).unwrap();
+    }
+
+    #[test]
+    fn test_get_permission_report() {
+        let string_dir = crate::get_dir_temp_for_tests("get_permission_report");
+        let string_path_file = format!("{}/shared.txt", string_dir,);
+        std::fs::write(&string_path_file, "").unwrap();
+        std::fs::write(format!("{}/private.txt", string_dir,), "").unwrap();
+        set_permissions_mode(&string_dir, 0o755).unwrap();
+        set_permissions_mode(&format!("{}/private.txt", string_dir,), 0o600).unwrap();
+        set_permissions_mode(&string_path_file, 0o666).unwrap();
+        let permission_report = get_permission_report(&string_dir).unwrap();
+        assert_eq!(permission_report.world_writable, vec![string_path_file]);
+        assert!(permission_report.not_owned_by_current_user.is_empty());
+        // Owner and group stay the same, which needs no privileges
+        let metadata = std::fs::metadata(&string_dir).unwrap();
+        assert_eq!(
+            chown_recursive(&string_dir, Some(metadata.uid()), Some(metadata.gid())).unwrap(),
+            3
+        );
+        assert_eq!(chown_recursive(&string_dir, None, None).unwrap(), 3);
+    }
+}
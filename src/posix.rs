@@ -0,0 +1,419 @@
+//! POSIX-style path handling that behaves the same on every host platform
+//!
+//! Paths are always split on '/', so remote linux paths and S3-style keys can be processed from code
+//! running anywhere, including Windows. Nothing here touches the disk.
+//!
+//! Empty components and '.' components are dropped while parsing, so 'a//./b/' is treated as 'a/b'.
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display, Formatter};
+//
+// Libraries - local
+//
+use crate::windows::{WindowsPath, WindowsPrefix};
+//
+// Private - structs
+//
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PosixPath {
+    bool_absolute: bool,
+    vec_of_components: Vec<String>,
+}
+
+impl PosixPath {
+    fn new<T: Display>(arg_string_path: &T) -> Self {
+        let string_path = format!("{}", arg_string_path,);
+        PosixPath {
+            bool_absolute: string_path.starts_with('/'),
+            vec_of_components: string_path
+                .split('/')
+                .filter(|item_str| !item_str.is_empty() && *item_str != ".")
+                .map(|item_str| item_str.to_string())
+                .collect::<Vec<String>>(),
+        }
+    }
+}
+
+impl Display for PosixPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.bool_absolute {
+            write!(f, "/")?;
+        }
+        write!(f, "{}", self.vec_of_components.join("/"),)
+    }
+}
+//
+// Public - get - paths
+//
+/// Returns a string consisting of only the filename
+/// Returns None if the path ends in '..' or has no components
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_base_name( &"/A/B/file.txt" );
+/// // result = Some( "file.txt" )
+pub fn get_base_name<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    match PosixPath::new(arg_string_path).vec_of_components.pop() {
+        Some(string_last) if string_last != ".." => Some(string_last),
+        _ => None,
+    }
+}
+
+/// Returns the path without its final component
+/// Returns None if there is nothing to remove ( ie '/' or '' )
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_dir_name( &"/A/B/C" );
+/// // result = Some( "/A/B" )
+pub fn get_dir_name<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    let mut posix_path = PosixPath::new(arg_string_path);
+    posix_path.vec_of_components.pop()?;
+    Some(posix_path.to_string())
+}
+
+/// Returns a string representing the file extension without the period
+/// Follows std's rules: '.bashrc' has no extension, 'file.' has an empty one.
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_extension( &"/A/file.txt" );
+/// // result = Some( "txt" )
+pub fn get_extension<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    let string_base_name = get_base_name(arg_string_path)?;
+    match string_base_name.rfind('.') {
+        Some(0) | None => None,
+        Some(int_index) => Some(string_base_name[int_index + 1..].to_string()),
+    }
+}
+
+/// Returns a string that's the longest run of leading components shared by every path
+/// In case of a failure ( empty slice, or a mix of absolute and relative paths ), this returns an error
+/// # Arguments
+/// * arg_slice_of_strings: slice of string-like POSIX paths
+/// # Examples
+/// let result = easy_paths::posix::get_common_prefix( &[ "/A/B/C", "/A/B/D", "/A/B" ] );
+/// // result = Ok( "/A/B" )
+pub fn get_common_prefix<T: Debug + Display>(arg_slice_of_strings: &[T]) -> Result<String, String> {
+    let vec_of_posix_paths = arg_slice_of_strings
+        .iter()
+        .map(PosixPath::new)
+        .collect::<Vec<PosixPath>>();
+    let mut posix_path_to_return = match vec_of_posix_paths.first() {
+        Some(posix_path_result) => posix_path_result.clone(),
+        None => {
+            return Err([
+                "Error: Failed to get value at index 0.".to_string(),
+                format!(
+                    "arg_slice_of_strings.len() = {}",
+                    arg_slice_of_strings.len()
+                ),
+            ]
+            .join("\n"))
+        }
+    };
+    for item_posix_path in &vec_of_posix_paths[1..] {
+        if item_posix_path.bool_absolute != posix_path_to_return.bool_absolute {
+            return Err([
+                "Error: cannot mix absolute and relative paths.".to_string(),
+                format!("arg_slice_of_strings = {:#?}", arg_slice_of_strings,),
+            ]
+            .join("\n"));
+        }
+        let int_count_shared = posix_path_to_return
+            .vec_of_components
+            .iter()
+            .zip(item_posix_path.vec_of_components.iter())
+            .take_while(|(item_left, item_right)| item_left == item_right)
+            .count();
+        posix_path_to_return
+            .vec_of_components
+            .truncate(int_count_shared);
+    }
+    Ok(posix_path_to_return.to_string())
+}
+
+/// Returns a POSIX path that is the result of combining a slice of string-like values
+/// As with PathBuf, an absolute element replaces everything before it.
+/// Returns None if the slice is empty
+/// # Arguments
+/// * arg_slice_of_strings: slice of string-like POSIX paths
+/// # Examples
+/// let result = easy_paths::posix::get_path_joined( &[ "/A", "B", "C" ] );
+/// // result = Some( "/A/B/C" )
+pub fn get_path_joined<T: Debug + Display>(arg_slice_of_strings: &[T]) -> Option<String> {
+    let mut iter_of_posix_paths = arg_slice_of_strings.iter().map(PosixPath::new);
+    let mut posix_path_to_return = iter_of_posix_paths.next()?;
+    for item_posix_path in iter_of_posix_paths {
+        if item_posix_path.bool_absolute {
+            posix_path_to_return = item_posix_path;
+        } else {
+            posix_path_to_return
+                .vec_of_components
+                .extend(item_posix_path.vec_of_components);
+        }
+    }
+    Some(posix_path_to_return.to_string())
+}
+
+/// Returns the path with '.' and '..' resolved lexically and duplicate separators removed
+/// '..' at the root is dropped ( '/..' is '/' ), while leading '..' in relative paths is kept.
+/// An empty relative result is returned as '.'
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_path_normalized( &"/A/./B/../C//" );
+/// // result = "/A/C"
+pub fn get_path_normalized<T: Debug + Display>(arg_string_path: &T) -> String {
+    let posix_path = PosixPath::new(arg_string_path);
+    let mut vec_of_components: Vec<String> = vec![];
+    for item_string in posix_path.vec_of_components {
+        if item_string == ".." {
+            match vec_of_components.last() {
+                Some(string_last) if string_last != ".." => {
+                    vec_of_components.pop();
+                }
+                _ => {
+                    if !posix_path.bool_absolute {
+                        vec_of_components.push(item_string);
+                    }
+                }
+            }
+        } else {
+            vec_of_components.push(item_string);
+        }
+    }
+    let posix_path_normalized = PosixPath {
+        bool_absolute: posix_path.bool_absolute,
+        vec_of_components,
+    };
+    if !posix_path_normalized.bool_absolute && posix_path_normalized.vec_of_components.is_empty() {
+        return ".".to_string();
+    }
+    posix_path_normalized.to_string()
+}
+
+/// Returns a string that's a relative path after the prefix is removed
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// * arg_string_path_root_prefix: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_relative_path( &"/A/B/C/D", &"/A/B" );
+/// // result = Ok( "C/D" )
+pub fn get_relative_path<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path: &T1,
+    arg_string_path_root_prefix: &T2,
+) -> Result<String, String> {
+    let posix_path = PosixPath::new(arg_string_path);
+    let posix_path_root_prefix = PosixPath::new(arg_string_path_root_prefix);
+    if posix_path.bool_absolute != posix_path_root_prefix.bool_absolute
+        || !posix_path
+            .vec_of_components
+            .starts_with(&posix_path_root_prefix.vec_of_components)
+    {
+        return Err([
+            "Error: strip_prefix() failed.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+            format!(
+                "arg_string_path_root_prefix = {}",
+                arg_string_path_root_prefix,
+            ),
+        ]
+        .join("\n"));
+    }
+    Ok(posix_path.vec_of_components[posix_path_root_prefix.vec_of_components.len()..].join("/"))
+}
+
+/// Returns a vec resulting from splitting the path into substrings
+/// Absolute paths start with "/", the same as std's Path::iter()
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_vec_by_splitting_path( &"/A/B/C" );
+/// // result = [ "/", "A", "B", "C" ]
+pub fn get_vec_by_splitting_path<T: Debug + Display>(arg_string_path: &T) -> Vec<String> {
+    let posix_path = PosixPath::new(arg_string_path);
+    let mut vec_to_return = vec![];
+    if posix_path.bool_absolute {
+        vec_to_return.push("/".to_string());
+    }
+    vec_to_return.extend(posix_path.vec_of_components);
+    vec_to_return
+}
+//
+// Public - get - conversions
+//
+/// Returns the POSIX path rewritten in Windows form
+/// Absolute paths become rooted paths on the current drive ( '/A/B' becomes '\A\B' ).
+/// In case of a failure ( a component containing '\' or a character Windows doesn't allow in names ),
+/// this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like POSIX path
+/// # Examples
+/// let result = easy_paths::posix::get_path_converted_to_windows( &"A/B/file.txt" );
+/// // result = Ok( "A\\B\\file.txt" )
+pub fn get_path_converted_to_windows<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<String, String> {
+    let posix_path = PosixPath::new(arg_string_path);
+    for item_string in &posix_path.vec_of_components {
+        if let Some(char_invalid) = item_string
+            .chars()
+            .find(|item_char| is_char_invalid_on_windows(*item_char))
+        {
+            return Err([
+                "Error: path component can't be represented on Windows.".to_string(),
+                format!("invalid character = {:?}", char_invalid,),
+                format!("component = {}", item_string,),
+                format!("arg_string_path = {}", arg_string_path,),
+            ]
+            .join("\n"));
+        }
+    }
+    let mut string_to_return = String::new();
+    if posix_path.bool_absolute {
+        string_to_return.push('\\');
+    }
+    string_to_return.push_str(&posix_path.vec_of_components.join("\\"));
+    Ok(string_to_return)
+}
+
+/// Returns the Windows path rewritten in POSIX form
+/// In case of a failure ( drive letters, UNC shares and other prefixes have no POSIX equivalent ),
+/// this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like Windows path
+/// # Examples
+/// let result = easy_paths::posix::get_path_converted_from_windows( &"\\A\\B\\file.txt" );
+/// // result = Ok( "/A/B/file.txt" )
+pub fn get_path_converted_from_windows<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<String, String> {
+    let windows_path = WindowsPath::new(arg_string_path);
+    if !matches!(windows_path.prefix(), WindowsPrefix::None) {
+        return Err([
+            "Error: Windows path prefix can't be represented as a POSIX path.".to_string(),
+            format!("prefix = {}", windows_path.prefix(),),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n"));
+    }
+    Ok(PosixPath {
+        bool_absolute: windows_path.has_root(),
+        vec_of_components: windows_path.components().to_vec(),
+    }
+    .to_string())
+}
+//
+// Private
+//
+fn is_char_invalid_on_windows(arg_char: char) -> bool {
+    matches!(arg_char, '<' | '>' | ':' | '"' | '|' | '?' | '*' | '\\') || arg_char.is_control()
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_base_name() {
+        assert_eq!(
+            get_base_name(&"/A/B/file.txt"),
+            Some("file.txt".to_string())
+        );
+        assert_eq!(get_base_name(&"bucket/key/"), Some("key".to_string()));
+        assert_eq!(get_base_name(&"/"), None);
+    }
+
+    #[test]
+    fn test_get_dir_name() {
+        assert_eq!(get_dir_name(&"/A/B/C"), Some("/A/B".to_string()));
+        assert_eq!(get_dir_name(&"/A"), Some("/".to_string()));
+        assert_eq!(get_dir_name(&"/"), None);
+    }
+
+    #[test]
+    fn test_get_extension() {
+        assert_eq!(get_extension(&"/A/file.tar.gz"), Some("gz".to_string()));
+        assert_eq!(get_extension(&"/A/.bashrc"), None);
+    }
+
+    #[test]
+    fn test_get_common_prefix() {
+        assert_eq!(
+            get_common_prefix(&["/A/B/C", "/A/B/D", "/A/B"]),
+            Ok("/A/B".to_string())
+        );
+        assert_eq!(get_common_prefix(&["A/B", "C"]), Ok("".to_string()));
+        match get_common_prefix(&["/A", "A"]) {
+            Ok(string_result) => panic!("Mixed absolute and relative: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+
+    #[test]
+    fn test_get_path_joined() {
+        assert_eq!(
+            get_path_joined(&["/A", "B", "C"]),
+            Some("/A/B/C".to_string())
+        );
+        assert_eq!(get_path_joined(&["/A", "/B"]), Some("/B".to_string()));
+    }
+
+    #[test]
+    fn test_get_path_normalized() {
+        assert_eq!(get_path_normalized(&"/A/./B/../C//"), "/A/C".to_string());
+        assert_eq!(get_path_normalized(&"/.."), "/".to_string());
+        assert_eq!(get_path_normalized(&"../A/../../B"), "../../B".to_string());
+        assert_eq!(get_path_normalized(&"A/.."), ".".to_string());
+    }
+
+    #[test]
+    fn test_get_relative_path() {
+        assert_eq!(
+            get_relative_path(&"/A/B/C/D", &"/A/B"),
+            Ok("C/D".to_string())
+        );
+        match get_relative_path(&"/A/BC", &"/A/B") {
+            Ok(string_result) => panic!("Matched partial component: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+
+    #[test]
+    fn test_get_vec_by_splitting_path() {
+        assert_eq!(
+            get_vec_by_splitting_path(&"/A/B"),
+            vec!["/".to_string(), "A".to_string(), "B".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_get_path_converted_to_windows() {
+        assert_eq!(
+            get_path_converted_to_windows(&"A/B/file.txt"),
+            Ok("A\\B\\file.txt".to_string())
+        );
+        assert_eq!(get_path_converted_to_windows(&"/A"), Ok("\\A".to_string()));
+        match get_path_converted_to_windows(&"A/what?.txt") {
+            Ok(string_result) => panic!("Converted invalid name: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+
+    #[test]
+    fn test_get_path_converted_from_windows() {
+        assert_eq!(
+            get_path_converted_from_windows(&"\\A\\B/file.txt"),
+            Ok("/A/B/file.txt".to_string())
+        );
+        match get_path_converted_from_windows(&"C:\\A") {
+            Ok(string_result) => panic!("Converted drive path: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+}
@@ -0,0 +1,760 @@
+//
+// Libraries - native
+//
+use std::collections::BTreeSet;
+use std::ffi::{OsStr, OsString};
+use std::fmt::Display;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+//
+// Libraries - local
+//
+use crate::file_system::{get_path_buf_resolved_with, get_path_buf_without_prefix};
+use crate::{FileSystem, FsMetadata, OsFs};
+//
+// Public - structs
+//
+/// A view of a directory that every path stays inside, like openat2() with RESOLVE_IN_ROOT
+/// Paths are relative to the root, whether or not they start with '/'. '..' at the root stays at the
+/// root, and symlinks are resolved by RootedFs itself, so an absolute target like '/etc/passwd' means
+/// '<root>/etc/passwd'. read_dir() and canonicalize() return paths in the same rooted form.
+/// Symlinks are checked and then used in separate steps, so a process that can change the tree
+/// while RootedFs uses it can still race it; the tree's own contents can never lead outside.
+/// # Examples
+/// let rooted_fs = match RootedFs::new( &"/srv/project" ) {
+///     Ok( rooted_fs ) => { rooted_fs }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// let vec_of_bytes = rooted_fs.read( Path::new( "/../../etc/passwd" ) );   // reads /srv/project/etc/passwd
+#[derive(Clone, Debug)]
+pub struct RootedFs<F: FileSystem = OsFs> {
+    file_system: F,
+    path_buf_root: PathBuf,
+}
+
+impl RootedFs<OsFs> {
+    /// Returns a view of arg_string_path_root on the real disk
+    /// In case of a failure ( ie the root isn't a directory ), this returns an error explaining what happened
+    /// # Arguments
+    /// * arg_string_path_root: string-like path to an existing directory
+    pub fn new<T: Display>(arg_string_path_root: &T) -> Result<Self, String> {
+        Self::with_fs(OsFs, arg_string_path_root)
+    }
+}
+
+impl<F: FileSystem> RootedFs<F> {
+    /// Returns a view of arg_string_path_root in arg_file_system
+    /// # Arguments
+    /// * arg_file_system: any FileSystem
+    /// * arg_string_path_root: string-like path to an existing directory
+    pub fn with_fs<T: Display>(
+        arg_file_system: F,
+        arg_string_path_root: &T,
+    ) -> Result<Self, String> {
+        let path_buf_root = get_path_buf_dir_canonical(&arg_file_system, arg_string_path_root)?;
+        Ok(RootedFs {
+            file_system: arg_file_system,
+            path_buf_root,
+        })
+    }
+
+    /// Returns the root directory, as a path in the underlying filesystem
+    pub fn root(&self) -> &Path {
+        &self.path_buf_root
+    }
+
+    /// Returns where arg_path really is in the underlying filesystem, with every symlink resolved
+    /// The last component needn't exist.
+    /// # Arguments
+    /// * arg_path: path inside the root
+    pub fn get_path_real(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        Ok(self.get_path_buf_real(&path_buf_virtual))
+    }
+
+    fn get_path_buf_real(&self, arg_path_virtual: &Path) -> PathBuf {
+        self.path_buf_root.join(get_path_relative(arg_path_virtual))
+    }
+
+    fn get_path_buf_virtual(
+        &self,
+        arg_path: &Path,
+        arg_bool_follow_last: bool,
+    ) -> std::io::Result<PathBuf> {
+        get_path_buf_resolved_in_root(
+            arg_path,
+            arg_bool_follow_last,
+            |path_virtual| {
+                self.file_system
+                    .symlink_metadata(&self.get_path_buf_real(path_virtual))
+            },
+            |path_virtual| {
+                self.file_system
+                    .read_link(&self.get_path_buf_real(path_virtual))
+            },
+        )
+    }
+
+    fn get_path_buf_real_resolved(
+        &self,
+        arg_path: &Path,
+        arg_bool_follow_last: bool,
+    ) -> std::io::Result<PathBuf> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, arg_bool_follow_last)?;
+        Ok(self.get_path_buf_real(&path_buf_virtual))
+    }
+}
+
+impl<F: FileSystem> FileSystem for RootedFs<F> {
+    fn metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        // Every symlink is already resolved, so the underlying filesystem mustn't follow any more
+        self.file_system
+            .symlink_metadata(&self.get_path_buf_real_resolved(arg_path, true)?)
+    }
+
+    fn symlink_metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        self.file_system
+            .symlink_metadata(&self.get_path_buf_real_resolved(arg_path, false)?)
+    }
+
+    fn read_dir(&self, arg_path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let vec_of_path_bufs = self
+            .file_system
+            .read_dir(&self.get_path_buf_real_resolved(arg_path, true)?)?;
+        Ok(vec_of_path_bufs
+            .iter()
+            .filter_map(|item_path_buf| item_path_buf.file_name())
+            .map(|item_os_str_name| arg_path.join(item_os_str_name))
+            .collect())
+    }
+
+    fn read(&self, arg_path: &Path) -> std::io::Result<Vec<u8>> {
+        self.file_system
+            .read(&self.get_path_buf_real_resolved(arg_path, true)?)
+    }
+
+    fn write(&self, arg_path: &Path, arg_slice_of_bytes: &[u8]) -> std::io::Result<()> {
+        self.file_system.write(
+            &self.get_path_buf_real_resolved(arg_path, true)?,
+            arg_slice_of_bytes,
+        )
+    }
+
+    fn create_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        self.file_system
+            .create_dir(&self.get_path_buf_real_resolved(arg_path, false)?)
+    }
+
+    fn remove_file(&self, arg_path: &Path) -> std::io::Result<()> {
+        self.file_system
+            .remove_file(&self.get_path_buf_real_resolved(arg_path, false)?)
+    }
+
+    fn remove_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        let path_buf_real = self.get_path_buf_real_resolved(arg_path, false)?;
+        if path_buf_real == self.path_buf_root {
+            return Err(Error::from(ErrorKind::PermissionDenied));
+        }
+        self.file_system.remove_dir(&path_buf_real)
+    }
+
+    fn read_link(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        self.file_system
+            .read_link(&self.get_path_buf_real_resolved(arg_path, false)?)
+    }
+
+    fn symlink(&self, arg_path_target: &Path, arg_path_link: &Path) -> std::io::Result<()> {
+        // The target is stored as given; it's only ever resolved inside the root
+        self.file_system.symlink(
+            arg_path_target,
+            &self.get_path_buf_real_resolved(arg_path_link, false)?,
+        )
+    }
+
+    fn canonicalize(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        self.file_system
+            .symlink_metadata(&self.get_path_buf_real(&path_buf_virtual))?;
+        Ok(path_buf_virtual)
+    }
+}
+
+/// A copy-on-write view that reads from a lower directory and writes only to an upper directory
+/// Paths are rooted like RootedFs. Writing a file puts the new contents in the upper directory, creating
+/// its parents there as needed; the lower directory is never changed. Removing something that exists
+/// in the lower directory leaves a whiteout file named '.wh.<name>' in the upper directory, and
+/// re-creating a removed directory marks it opaque with '.wh..wh..opq', as aufs does. Names starting
+/// with '.wh.' are therefore reserved and can't be used through the overlay.
+/// # Examples
+/// let overlay_fs = match OverlayFs::new( &"/srv/project", &"/tmp/plugin_changes" ) {
+///     Ok( overlay_fs ) => { overlay_fs }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// overlay_fs.write( Path::new( "src/main.rs" ), b"fn main() {}" ).unwrap();   // only changes /tmp/plugin_changes
+#[derive(Clone, Debug)]
+pub struct OverlayFs<F: FileSystem = OsFs> {
+    file_system: F,
+    path_buf_lower: PathBuf,
+    path_buf_upper: PathBuf,
+}
+
+impl OverlayFs<OsFs> {
+    /// Returns an overlay of two directories on the real disk
+    /// In case of a failure ( ie either isn't a directory ), this returns an error explaining what happened
+    /// # Arguments
+    /// * arg_string_path_lower: string-like path to the directory to read from
+    /// * arg_string_path_upper: string-like path to the directory to write to
+    pub fn new<T1: Display, T2: Display>(
+        arg_string_path_lower: &T1,
+        arg_string_path_upper: &T2,
+    ) -> Result<Self, String> {
+        Self::with_fs(OsFs, arg_string_path_lower, arg_string_path_upper)
+    }
+}
+
+impl<F: FileSystem> OverlayFs<F> {
+    /// Returns an overlay of two directories in arg_file_system
+    /// # Arguments
+    /// * arg_file_system: any FileSystem
+    /// * arg_string_path_lower: string-like path to the directory to read from
+    /// * arg_string_path_upper: string-like path to the directory to write to
+    pub fn with_fs<T1: Display, T2: Display>(
+        arg_file_system: F,
+        arg_string_path_lower: &T1,
+        arg_string_path_upper: &T2,
+    ) -> Result<Self, String> {
+        let path_buf_lower = get_path_buf_dir_canonical(&arg_file_system, arg_string_path_lower)?;
+        let path_buf_upper = get_path_buf_dir_canonical(&arg_file_system, arg_string_path_upper)?;
+        if path_buf_lower.starts_with(&path_buf_upper)
+            || path_buf_upper.starts_with(&path_buf_lower)
+        {
+            return Err([
+                "Error: the lower and upper directories must not contain each other.".to_string(),
+                format!("arg_string_path_lower = {}", arg_string_path_lower,),
+                format!("arg_string_path_upper = {}", arg_string_path_upper,),
+            ]
+            .join("\n"));
+        }
+        Ok(OverlayFs {
+            file_system: arg_file_system,
+            path_buf_lower,
+            path_buf_upper,
+        })
+    }
+
+    /// Returns the directory that's read from
+    pub fn lower(&self) -> &Path {
+        &self.path_buf_lower
+    }
+
+    /// Returns the directory that's written to
+    pub fn upper(&self) -> &Path {
+        &self.path_buf_upper
+    }
+}
+
+impl<F: FileSystem> FileSystem for OverlayFs<F> {
+    fn metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        self.file_system
+            .symlink_metadata(&self.get_overlay_entry(&path_buf_virtual)?.get_path_real()?)
+    }
+
+    fn symlink_metadata(&self, arg_path: &Path) -> std::io::Result<FsMetadata> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, false)?;
+        self.file_system
+            .symlink_metadata(&self.get_overlay_entry(&path_buf_virtual)?.get_path_real()?)
+    }
+
+    fn read_dir(&self, arg_path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        Ok(self
+            .get_set_of_names(&path_buf_virtual)?
+            .into_iter()
+            .map(|item_os_string_name| arg_path.join(item_os_string_name))
+            .collect())
+    }
+
+    fn read(&self, arg_path: &Path) -> std::io::Result<Vec<u8>> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        self.file_system
+            .read(&self.get_overlay_entry(&path_buf_virtual)?.get_path_real()?)
+    }
+
+    fn write(&self, arg_path: &Path, arg_slice_of_bytes: &[u8]) -> std::io::Result<()> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        if let Ok(path_buf_real) = self.get_overlay_entry(&path_buf_virtual)?.get_path_real() {
+            if self.file_system.symlink_metadata(&path_buf_real)?.is_dir() {
+                return Err(Error::from(ErrorKind::IsADirectory));
+            }
+        }
+        self.prepare_upper_for_new_entry(&path_buf_virtual)?;
+        self.file_system.write(
+            &self.get_path_buf_upper(&path_buf_virtual),
+            arg_slice_of_bytes,
+        )
+    }
+
+    fn create_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, false)?;
+        if self
+            .get_overlay_entry(&path_buf_virtual)?
+            .get_path_real()
+            .is_ok()
+        {
+            return Err(Error::from(ErrorKind::AlreadyExists));
+        }
+        let bool_was_whited_out = self.prepare_upper_for_new_entry(&path_buf_virtual)?;
+        let path_buf_upper = self.get_path_buf_upper(&path_buf_virtual);
+        self.file_system.create_dir(&path_buf_upper)?;
+        // Without this, the removed lower directory's contents would reappear
+        if bool_was_whited_out {
+            self.file_system
+                .write(&path_buf_upper.join(STR_OPAQUE), b"")?;
+        }
+        Ok(())
+    }
+
+    fn remove_file(&self, arg_path: &Path) -> std::io::Result<()> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, false)?;
+        let overlay_entry = self.get_overlay_entry(&path_buf_virtual)?;
+        if self
+            .file_system
+            .symlink_metadata(&overlay_entry.get_path_real()?)?
+            .is_dir()
+        {
+            return Err(Error::from(ErrorKind::IsADirectory));
+        }
+        if let Some(path_buf_upper) = &overlay_entry.option_path_buf_upper {
+            self.file_system.remove_file(path_buf_upper)?;
+        }
+        if overlay_entry.option_path_buf_lower.is_some() {
+            self.write_whiteout(&path_buf_virtual)?;
+        }
+        Ok(())
+    }
+
+    fn remove_dir(&self, arg_path: &Path) -> std::io::Result<()> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, false)?;
+        if path_buf_virtual == Path::new("/") {
+            return Err(Error::from(ErrorKind::PermissionDenied));
+        }
+        let overlay_entry = self.get_overlay_entry(&path_buf_virtual)?;
+        if !self
+            .file_system
+            .symlink_metadata(&overlay_entry.get_path_real()?)?
+            .is_dir()
+        {
+            return Err(Error::from(ErrorKind::NotADirectory));
+        }
+        if !self.get_set_of_names(&path_buf_virtual)?.is_empty() {
+            return Err(Error::from(ErrorKind::DirectoryNotEmpty));
+        }
+        if let Some(path_buf_upper) = &overlay_entry.option_path_buf_upper {
+            // Only whiteouts and the opaque marker can be left in it
+            for item_path_buf in self.file_system.read_dir(path_buf_upper)? {
+                self.file_system.remove_file(&item_path_buf)?;
+            }
+            self.file_system.remove_dir(path_buf_upper)?;
+        }
+        if overlay_entry.option_path_buf_lower.is_some() {
+            self.write_whiteout(&path_buf_virtual)?;
+        }
+        Ok(())
+    }
+
+    fn read_link(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, false)?;
+        self.file_system
+            .read_link(&self.get_overlay_entry(&path_buf_virtual)?.get_path_real()?)
+    }
+
+    fn symlink(&self, arg_path_target: &Path, arg_path_link: &Path) -> std::io::Result<()> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path_link, false)?;
+        if self
+            .get_overlay_entry(&path_buf_virtual)?
+            .get_path_real()
+            .is_ok()
+        {
+            return Err(Error::from(ErrorKind::AlreadyExists));
+        }
+        self.prepare_upper_for_new_entry(&path_buf_virtual)?;
+        self.file_system
+            .symlink(arg_path_target, &self.get_path_buf_upper(&path_buf_virtual))
+    }
+
+    fn canonicalize(&self, arg_path: &Path) -> std::io::Result<PathBuf> {
+        let path_buf_virtual = self.get_path_buf_virtual(arg_path, true)?;
+        self.get_overlay_entry(&path_buf_virtual)?.get_path_real()?;
+        Ok(path_buf_virtual)
+    }
+}
+//
+// Private
+//
+const STR_OPAQUE: &str = ".wh..wh..opq";
+const STR_WHITEOUT_PREFIX: &str = ".wh.";
+
+/// Where a path in an OverlayFs exists; the lower path is only set if it's visible
+struct OverlayEntry {
+    option_path_buf_upper: Option<PathBuf>,
+    option_path_buf_lower: Option<PathBuf>,
+    /// False if this is an opaque directory in the upper layer, or below one
+    bool_lower_children_visible: bool,
+}
+
+impl OverlayEntry {
+    fn get_path_real(&self) -> std::io::Result<PathBuf> {
+        match (&self.option_path_buf_upper, &self.option_path_buf_lower) {
+            (Some(path_buf), _) | (None, Some(path_buf)) => Ok(path_buf.clone()),
+            (None, None) => Err(Error::from(ErrorKind::NotFound)),
+        }
+    }
+}
+
+impl<F: FileSystem> OverlayFs<F> {
+    /// Returns where arg_path_virtual exists in each layer, given that every component is already resolved
+    fn get_overlay_entry(&self, arg_path_virtual: &Path) -> std::io::Result<OverlayEntry> {
+        let mut path_buf_virtual = PathBuf::from("/");
+        let mut bool_lower_visible = true;
+        for item_os_str_name in get_path_relative(arg_path_virtual).iter() {
+            if self.is_whited_out(&path_buf_virtual, item_os_str_name) {
+                return Ok(OverlayEntry {
+                    option_path_buf_upper: None,
+                    option_path_buf_lower: None,
+                    bool_lower_children_visible: false,
+                });
+            }
+            if self
+                .file_system
+                .symlink_metadata(&self.get_path_buf_upper(&path_buf_virtual).join(STR_OPAQUE))
+                .is_ok()
+            {
+                bool_lower_visible = false;
+            }
+            path_buf_virtual.push(item_os_str_name);
+        }
+        let path_buf_upper = self.get_path_buf_upper(&path_buf_virtual);
+        let path_buf_lower = self
+            .path_buf_lower
+            .join(get_path_relative(&path_buf_virtual));
+        let bool_upper_exists = self.file_system.symlink_metadata(&path_buf_upper).is_ok();
+        let bool_lower_exists =
+            bool_lower_visible && self.file_system.symlink_metadata(&path_buf_lower).is_ok();
+        let bool_upper_opaque = self
+            .file_system
+            .symlink_metadata(&path_buf_upper.join(STR_OPAQUE))
+            .is_ok();
+        Ok(OverlayEntry {
+            option_path_buf_upper: bool_upper_exists.then_some(path_buf_upper),
+            option_path_buf_lower: bool_lower_exists.then_some(path_buf_lower),
+            bool_lower_children_visible: bool_lower_visible && !bool_upper_opaque,
+        })
+    }
+
+    fn get_path_buf_upper(&self, arg_path_virtual: &Path) -> PathBuf {
+        self.path_buf_upper
+            .join(get_path_relative(arg_path_virtual))
+    }
+
+    fn get_path_buf_virtual(
+        &self,
+        arg_path: &Path,
+        arg_bool_follow_last: bool,
+    ) -> std::io::Result<PathBuf> {
+        let closure_get_path_real = |path_virtual: &Path| {
+            if path_virtual.file_name().is_some_and(|os_str_name| {
+                os_str_name
+                    .to_string_lossy()
+                    .starts_with(STR_WHITEOUT_PREFIX)
+            }) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "names starting with '.wh.' are reserved by OverlayFs",
+                ));
+            }
+            self.get_overlay_entry(path_virtual)?.get_path_real()
+        };
+        get_path_buf_resolved_in_root(
+            arg_path,
+            arg_bool_follow_last,
+            |path_virtual| {
+                self.file_system
+                    .symlink_metadata(&closure_get_path_real(path_virtual)?)
+            },
+            |path_virtual| {
+                self.file_system
+                    .read_link(&closure_get_path_real(path_virtual)?)
+            },
+        )
+    }
+
+    /// Returns the merged names in a directory, without whiteouts or the names they hide
+    fn get_set_of_names(&self, arg_path_virtual: &Path) -> std::io::Result<BTreeSet<OsString>> {
+        let overlay_entry = self.get_overlay_entry(arg_path_virtual)?;
+        if !self
+            .file_system
+            .symlink_metadata(&overlay_entry.get_path_real()?)?
+            .is_dir()
+        {
+            return Err(Error::from(ErrorKind::NotADirectory));
+        }
+        let mut set_of_names = BTreeSet::new();
+        let mut set_of_whiteouts = BTreeSet::new();
+        if let Some(path_buf_upper) = &overlay_entry.option_path_buf_upper {
+            for item_path_buf in self.file_system.read_dir(path_buf_upper)? {
+                let os_string_name = item_path_buf.file_name().unwrap_or_default().to_os_string();
+                match os_string_name
+                    .to_string_lossy()
+                    .strip_prefix(STR_WHITEOUT_PREFIX)
+                {
+                    Some(str_name_hidden) => {
+                        set_of_whiteouts.insert(OsString::from(str_name_hidden));
+                    }
+                    None => {
+                        set_of_names.insert(os_string_name);
+                    }
+                }
+            }
+        }
+        if let (Some(path_buf_lower), true) = (
+            &overlay_entry.option_path_buf_lower,
+            overlay_entry.bool_lower_children_visible,
+        ) {
+            if self.file_system.symlink_metadata(path_buf_lower)?.is_dir() {
+                for item_path_buf in self.file_system.read_dir(path_buf_lower)? {
+                    let os_string_name =
+                        item_path_buf.file_name().unwrap_or_default().to_os_string();
+                    if !set_of_whiteouts.contains(&os_string_name) {
+                        set_of_names.insert(os_string_name);
+                    }
+                }
+            }
+        }
+        Ok(set_of_names)
+    }
+
+    fn is_whited_out(&self, arg_path_virtual_dir: &Path, arg_os_str_name: &OsStr) -> bool {
+        self.file_system
+            .symlink_metadata(&self.get_path_buf_whiteout(arg_path_virtual_dir, arg_os_str_name))
+            .is_ok()
+    }
+
+    fn get_path_buf_whiteout(
+        &self,
+        arg_path_virtual_dir: &Path,
+        arg_os_str_name: &OsStr,
+    ) -> PathBuf {
+        let mut os_string_name = OsString::from(STR_WHITEOUT_PREFIX);
+        os_string_name.push(arg_os_str_name);
+        self.get_path_buf_upper(arg_path_virtual_dir)
+            .join(os_string_name)
+    }
+
+    /// Copies the parent directories up into the upper layer and removes any whiteout for the entry
+    /// Returns true if there was a whiteout
+    fn prepare_upper_for_new_entry(&self, arg_path_virtual: &Path) -> std::io::Result<bool> {
+        let (path_virtual_parent, os_str_name) =
+            match (arg_path_virtual.parent(), arg_path_virtual.file_name()) {
+                (Some(path_parent), Some(os_str_name)) => (path_parent, os_str_name),
+                _ => return Err(Error::from(ErrorKind::AlreadyExists)),
+            };
+        if !self
+            .file_system
+            .symlink_metadata(
+                &self
+                    .get_overlay_entry(path_virtual_parent)?
+                    .get_path_real()?,
+            )?
+            .is_dir()
+        {
+            return Err(Error::from(ErrorKind::NotADirectory));
+        }
+        let mut path_buf_virtual = PathBuf::from("/");
+        for item_os_str_name in get_path_relative(path_virtual_parent).iter() {
+            path_buf_virtual.push(item_os_str_name);
+            let path_buf_upper = self.get_path_buf_upper(&path_buf_virtual);
+            if self.file_system.symlink_metadata(&path_buf_upper).is_err() {
+                self.file_system.create_dir(&path_buf_upper)?;
+            }
+        }
+        let path_buf_whiteout = self.get_path_buf_whiteout(path_virtual_parent, os_str_name);
+        if self
+            .file_system
+            .symlink_metadata(&path_buf_whiteout)
+            .is_ok()
+        {
+            self.file_system.remove_file(&path_buf_whiteout)?;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn write_whiteout(&self, arg_path_virtual: &Path) -> std::io::Result<()> {
+        let (path_virtual_parent, os_str_name) =
+            match (arg_path_virtual.parent(), arg_path_virtual.file_name()) {
+                (Some(path_parent), Some(os_str_name)) => (path_parent, os_str_name),
+                _ => return Err(Error::from(ErrorKind::PermissionDenied)),
+            };
+        self.prepare_upper_for_new_entry(arg_path_virtual)?;
+        self.file_system.write(
+            &self.get_path_buf_whiteout(path_virtual_parent, os_str_name),
+            b"",
+        )
+    }
+}
+
+/// Returns the canonical path of a directory, or an error if it isn't one
+fn get_path_buf_dir_canonical<F: FileSystem, T: Display>(
+    arg_file_system: &F,
+    arg_string_path: &T,
+) -> Result<PathBuf, String> {
+    let path_buf = match arg_file_system.canonicalize(Path::new(&format!("{}", arg_string_path,))) {
+        Ok(path_buf_result) => path_buf_result,
+        Err(err) => {
+            return Err([
+                "Error: failed to canonicalize directory.".to_string(),
+                format!("err = {}", err,),
+                format!("arg_string_path = {}", arg_string_path,),
+            ]
+            .join("\n"))
+        }
+    };
+    if !arg_file_system.is_dir(&path_buf) {
+        return Err([
+            "Error: path is not a directory.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n"));
+    }
+    Ok(path_buf)
+}
+
+/// Returns the rooted path with every symlink before the last component resolved, and the last one
+/// too if arg_bool_follow_last is set; the result always starts with '/'
+/// '..' at the root stays at the root, and absolute symlink targets start again from the root.
+fn get_path_buf_resolved_in_root<FM, FL>(
+    arg_path: &Path,
+    arg_bool_follow_last: bool,
+    arg_closure_symlink_metadata: FM,
+    arg_closure_read_link: FL,
+) -> std::io::Result<PathBuf>
+where
+    FM: Fn(&Path) -> std::io::Result<FsMetadata>,
+    FL: Fn(&Path) -> std::io::Result<PathBuf>,
+{
+    get_path_buf_resolved_with(
+        &get_path_buf_without_prefix(arg_path),
+        arg_bool_follow_last,
+        false,
+        arg_closure_symlink_metadata,
+        |path_virtual| {
+            arg_closure_read_link(path_virtual)
+                .map(|path_buf_target| get_path_buf_without_prefix(&path_buf_target))
+        },
+    )
+}
+
+/// Returns the path without its leading '/', so it can be joined onto a real directory
+fn get_path_relative(arg_path_virtual: &Path) -> &Path {
+    arg_path_virtual
+        .strip_prefix("/")
+        .unwrap_or(arg_path_virtual)
+}
+
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::InMemoryFs;
+
+    #[test]
+    fn test_rooted_fs() {
+        let in_memory_fs = InMemoryFs::new()
+            .with_file(&"/etc/passwd", "real")
+            .with_file(&"/jail/etc/passwd", "jailed")
+            .with_file(&"/jail/src/main.rs", "fn main() {}")
+            .with_symlink(&"/etc/passwd", &"/jail/absolute")
+            .with_symlink(&"../../../etc/passwd", &"/jail/src/relative")
+            .with_symlink(&"/", &"/jail/root");
+        let rooted_fs = RootedFs::with_fs(in_memory_fs, &"/jail").unwrap();
+        for item_str_path in [
+            "/etc/passwd",
+            "../../etc/passwd",
+            "absolute",
+            "src/relative",
+            "root/../root/etc/passwd",
+        ] {
+            assert_eq!(
+                rooted_fs.read(Path::new(item_str_path)).unwrap(),
+                b"jailed",
+                "{}",
+                item_str_path,
+            );
+        }
+        assert_eq!(
+            rooted_fs.canonicalize(Path::new("src/relative")).unwrap(),
+            PathBuf::from("/etc/passwd")
+        );
+        assert_eq!(
+            rooted_fs.get_path_real(Path::new("/../absolute")).unwrap(),
+            PathBuf::from("/jail/etc/passwd")
+        );
+        rooted_fs.write(Path::new("../../new.txt"), b"new").unwrap();
+        assert!(rooted_fs.exists(Path::new("/new.txt")));
+        assert_eq!(
+            rooted_fs.read_dir(Path::new("/src")).unwrap(),
+            vec![
+                PathBuf::from("/src/main.rs"),
+                PathBuf::from("/src/relative")
+            ]
+        );
+        assert!(rooted_fs.remove_dir(Path::new("/..")).is_err());
+        assert!(RootedFs::with_fs(InMemoryFs::new(), &"/missing").is_err());
+    }
+
+    #[test]
+    fn test_overlay_fs() {
+        let in_memory_fs = InMemoryFs::new()
+            .with_file(&"/lower/a.txt", "lower a")
+            .with_file(&"/lower/dir/b.txt", "lower b")
+            .with_symlink(&"/dir/b.txt", &"/lower/link")
+            .with_dir(&"/upper");
+        let overlay_fs = OverlayFs::with_fs(in_memory_fs, &"/lower", &"/upper").unwrap();
+        assert_eq!(overlay_fs.read(Path::new("link")).unwrap(), b"lower b");
+        overlay_fs.write(Path::new("/a.txt"), b"upper a").unwrap();
+        overlay_fs
+            .write(Path::new("/dir/c.txt"), b"upper c")
+            .unwrap();
+        assert_eq!(overlay_fs.read(Path::new("/a.txt")).unwrap(), b"upper a");
+        assert_eq!(
+            overlay_fs.read_dir(Path::new("/dir")).unwrap(),
+            vec![PathBuf::from("/dir/b.txt"), PathBuf::from("/dir/c.txt")]
+        );
+        overlay_fs.remove_file(Path::new("/dir/b.txt")).unwrap();
+        assert!(!overlay_fs.exists(Path::new("/dir/b.txt")));
+        assert!(!overlay_fs.exists(Path::new("/link")));
+        overlay_fs.remove_file(Path::new("/dir/c.txt")).unwrap();
+        overlay_fs.remove_dir(Path::new("/dir")).unwrap();
+        overlay_fs.create_dir(Path::new("/dir")).unwrap();
+        // The re-created directory is opaque, so the lower directory's contents stay hidden
+        assert_eq!(
+            overlay_fs.read_dir(Path::new("/dir")).unwrap(),
+            Vec::<PathBuf>::new()
+        );
+        assert!(overlay_fs.read(Path::new("/.wh.dir")).is_err());
+        // The lower directory is untouched
+        let in_memory_fs = &overlay_fs.file_system;
+        assert_eq!(
+            in_memory_fs.read(Path::new("/lower/a.txt")).unwrap(),
+            b"lower a"
+        );
+        assert_eq!(
+            in_memory_fs.read(Path::new("/lower/dir/b.txt")).unwrap(),
+            b"lower b"
+        );
+        assert!(OverlayFs::with_fs(InMemoryFs::new().with_dir(&"/a/b"), &"/a", &"/a/b").is_err());
+    }
+}
@@ -0,0 +1,525 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+//
+// Public - enums
+//
+/// A rule a file name can break, as reported by get_file_name_violations()
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileNameRule {
+    /// The name is empty
+    Empty,
+    /// The name is '.' or '..'
+    DotName,
+    /// The name contains '/' or '\'
+    Separator,
+    /// The name contains a NUL byte
+    Nul,
+    /// The name contains a control character other than NUL
+    ControlCharacter,
+    /// The name contains one of the characters Windows forbids: < > : " | ? *
+    InvalidCharacter,
+    /// The name is a Windows device name, ie CON, NUL, COM1 or LPT1, with or without an extension
+    ReservedName,
+    /// The name ends with '.' or ' ', which Windows silently strips
+    TrailingDotOrSpace,
+    /// The name is longer than the byte limit
+    TooLong,
+    /// The extension isn't lowercase, and the options ask for it to be
+    ExtensionNotNormalized,
+}
+
+impl Display for FileNameRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let str_description = match self {
+            FileNameRule::Empty => "name is empty",
+            FileNameRule::DotName => "name is '.' or '..'",
+            FileNameRule::Separator => "name contains a path separator",
+            FileNameRule::Nul => "name contains NUL",
+            FileNameRule::ControlCharacter => "name contains a control character",
+            FileNameRule::InvalidCharacter => "name contains one of < > : \" | ? *",
+            FileNameRule::ReservedName => "name is a reserved Windows device name",
+            FileNameRule::TrailingDotOrSpace => "name ends with '.' or ' '",
+            FileNameRule::TooLong => "name is longer than the byte limit",
+            FileNameRule::ExtensionNotNormalized => "extension isn't lowercase",
+        };
+        write!(f, "{}", str_description,)
+    }
+}
+//
+// Public - structs
+//
+/// Options for get_sanitized_file_name()
+/// By default each unsafe character is replaced with '_', names are limited to 255 bytes ( the limit on
+/// most filesystems ), the extension is kept as it is, and a name with nothing left becomes "file".
+#[derive(Clone, Debug)]
+pub struct SanitizeOptions {
+    string_replacement: String,
+    int_bytes_max: usize,
+    bool_lowercase_extension: bool,
+    string_fallback: String,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        SanitizeOptions {
+            string_replacement: "_".to_string(),
+            int_bytes_max: 255,
+            bool_lowercase_extension: false,
+            string_fallback: "file".to_string(),
+        }
+    }
+}
+
+impl SanitizeOptions {
+    /// Returns the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace each unsafe character with arg_string_replacement; an empty string strips them instead
+    /// Unsafe characters in the replacement itself are dropped.
+    /// # Arguments
+    /// * arg_string_replacement: string-like, ie "-"
+    pub fn replacement<T: Display>(mut self, arg_string_replacement: &T) -> Self {
+        self.string_replacement = format!("{}", arg_string_replacement,)
+            .chars()
+            .filter(|item_char| get_option_rule_for_char(*item_char).is_none())
+            .collect();
+        self
+    }
+
+    /// Limit names to arg_int_bytes bytes of UTF-8
+    /// # Arguments
+    /// * arg_int_bytes: byte limit, at least 1
+    pub fn max_bytes(mut self, arg_int_bytes: usize) -> Self {
+        self.int_bytes_max = arg_int_bytes.max(1);
+        self
+    }
+
+    /// Lowercase the extension, so "Photo.JPG" becomes "Photo.jpg"
+    pub fn lowercase_extension(mut self) -> Self {
+        self.bool_lowercase_extension = true;
+        self
+    }
+
+    /// Use arg_string_fallback when nothing is left of a name, ie for "" or "..."
+    /// # Arguments
+    /// * arg_string_fallback: string-like file name, sanitized like any other
+    pub fn fallback<T: Display>(mut self, arg_string_fallback: &T) -> Self {
+        self.string_fallback = format!("{}", arg_string_fallback,);
+        self
+    }
+}
+//
+// Public - get - file names
+//
+/// Returns arg_string_name made safe to use as a single file name on unix and Windows
+/// Separators, NUL, control characters and < > : " | ? * are replaced. Trailing dots and spaces are
+/// trimmed, and reserved Windows device names get an '_' added ( "CON.txt" becomes "CON_.txt" ).
+/// Names over the byte limit are shortened at a character boundary, keeping the extension.
+/// The result never breaks any rule checked by get_file_name_violations(). Shortening can expose a
+/// new extension ( "a.B.verylong" ), so the name is sanitized again until nothing changes.
+/// # Arguments
+/// * arg_string_name: string-like untrusted name, ie an upload's file name
+/// * arg_sanitize_options: SanitizeOptions
+/// # Examples
+/// let string_name = get_sanitized_file_name( &"../q3: report?.PDF", &SanitizeOptions::new().lowercase_extension() );
+/// // string_name = ".._q3_ report_.pdf"
+pub fn get_sanitized_file_name<T: Display>(
+    arg_string_name: &T,
+    arg_sanitize_options: &SanitizeOptions,
+) -> String {
+    const INT_PASSES_MAX: usize = 8;
+    let mut string_name =
+        get_sanitized_file_name_once(&format!("{}", arg_string_name,), arg_sanitize_options);
+    for _ in 0..INT_PASSES_MAX {
+        let string_name_next = get_sanitized_file_name_once(&string_name, arg_sanitize_options);
+        if string_name_next == string_name {
+            break;
+        }
+        string_name = string_name_next;
+    }
+    string_name
+}
+
+/// Returns the name after one pass of get_sanitized_file_name()'s rules
+fn get_sanitized_file_name_once(
+    arg_str_name: &str,
+    arg_sanitize_options: &SanitizeOptions,
+) -> String {
+    let string_name =
+        get_string_with_chars_replaced(arg_str_name, &arg_sanitize_options.string_replacement);
+    let mut string_name = string_name.trim_end_matches(['.', ' ']).to_string();
+    if string_name.is_empty() {
+        string_name = get_string_with_chars_replaced(
+            &arg_sanitize_options.string_fallback,
+            &arg_sanitize_options.string_replacement,
+        )
+        .trim_end_matches(['.', ' '])
+        .to_string();
+    }
+    if string_name.is_empty() {
+        string_name = "_".to_string();
+    }
+    let (mut string_stem, mut string_extension) = get_stem_and_extension(&string_name);
+    if arg_sanitize_options.bool_lowercase_extension {
+        string_extension = string_extension.to_lowercase();
+    }
+    // An extension that leaves no room for the stem is treated as part of the stem
+    if !string_extension.is_empty()
+        && string_extension.len() + 1 >= arg_sanitize_options.int_bytes_max
+    {
+        string_stem = format!("{}.{}", string_stem, string_extension,);
+        string_extension = String::new();
+    }
+    let int_bytes_stem_max = if string_extension.is_empty() {
+        arg_sanitize_options.int_bytes_max
+    } else {
+        arg_sanitize_options.int_bytes_max - string_extension.len() - 1
+    };
+    let mut string_stem = get_string_truncated(&string_stem, int_bytes_stem_max);
+    // Dots before an extension are fine, but with no extension they'd end the name
+    if string_extension.is_empty() {
+        string_stem = string_stem.trim_end_matches(['.', ' ']).to_string();
+    }
+    if string_stem.is_empty() {
+        string_stem = "_".to_string();
+    }
+    let string_stem = get_stem_with_reserved_name_fixed(string_stem, int_bytes_stem_max);
+    if string_extension.is_empty() {
+        string_stem
+    } else {
+        format!("{}.{}", string_stem, string_extension,)
+    }
+}
+
+/// Returns every rule arg_string_name breaks, in the order FileNameRule declares them
+/// An empty result means the name is safe, and get_sanitized_file_name() would return it unchanged.
+/// # Arguments
+/// * arg_string_name: string-like file name
+/// * arg_sanitize_options: SanitizeOptions, for the byte limit and extension rule
+/// # Examples
+/// let vec_of_rules = get_file_name_violations( &"NUL.txt", &SanitizeOptions::new() );
+/// // vec_of_rules = [ FileNameRule::ReservedName ]
+pub fn get_file_name_violations<T: Display>(
+    arg_string_name: &T,
+    arg_sanitize_options: &SanitizeOptions,
+) -> Vec<FileNameRule> {
+    let string_name = format!("{}", arg_string_name,);
+    let mut vec_to_return = vec![];
+    if string_name.is_empty() {
+        vec_to_return.push(FileNameRule::Empty);
+    }
+    if string_name == "." || string_name == ".." {
+        vec_to_return.push(FileNameRule::DotName);
+    }
+    for item_char in string_name.chars() {
+        if let Some(rule) = get_option_rule_for_char(item_char) {
+            if !vec_to_return.contains(&rule) {
+                vec_to_return.push(rule);
+            }
+        }
+    }
+    let (string_stem, string_extension) = get_stem_and_extension(&string_name);
+    if is_reserved_name(&string_stem) {
+        vec_to_return.push(FileNameRule::ReservedName);
+    }
+    if !vec_to_return.contains(&FileNameRule::DotName) && string_name.ends_with(['.', ' ']) {
+        vec_to_return.push(FileNameRule::TrailingDotOrSpace);
+    }
+    if string_name.len() > arg_sanitize_options.int_bytes_max {
+        vec_to_return.push(FileNameRule::TooLong);
+    }
+    if arg_sanitize_options.bool_lowercase_extension
+        && string_extension != string_extension.to_lowercase()
+    {
+        vec_to_return.push(FileNameRule::ExtensionNotNormalized);
+    }
+    vec_to_return.sort_by_key(|rule| *rule as u8);
+    vec_to_return
+}
+
+/// Returns an error naming each rule arg_string_name breaks, if any
+/// # Arguments
+/// * arg_string_name: string-like file name
+/// * arg_sanitize_options: SanitizeOptions
+/// # Examples
+/// match raise_error_if_file_name_is_unsafe( &"report?.pdf", &SanitizeOptions::new() ) {
+///     Ok( () ) => {}
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn raise_error_if_file_name_is_unsafe<T: Debug + Display>(
+    arg_string_name: &T,
+    arg_sanitize_options: &SanitizeOptions,
+) -> Result<(), String> {
+    let vec_of_rules = get_file_name_violations(arg_string_name, arg_sanitize_options);
+    if vec_of_rules.is_empty() {
+        return Ok(());
+    }
+    let mut vec_of_lines = vec!["Error: file name is unsafe.".to_string()];
+    vec_of_lines.extend(
+        vec_of_rules
+            .iter()
+            .map(|item_rule| format!("rule broken = {}", item_rule,)),
+    );
+    vec_of_lines.push(format!("arg_string_name = {:?}", arg_string_name,));
+    Err(vec_of_lines.join("\n"))
+}
+//
+// Private
+//
+fn get_option_rule_for_char(arg_char: char) -> Option<FileNameRule> {
+    match arg_char {
+        '/' | '\\' => Some(FileNameRule::Separator),
+        '\0' => Some(FileNameRule::Nul),
+        '<' | '>' | ':' | '"' | '|' | '?' | '*' => Some(FileNameRule::InvalidCharacter),
+        _ if arg_char.is_control() => Some(FileNameRule::ControlCharacter),
+        _ => None,
+    }
+}
+
+/// Returns the stem with an '_' after the device name if it's reserved, ie "CON.tar" becomes "CON_.tar"
+/// If there's no room for another byte, the device name's last character becomes the '_' instead.
+fn get_stem_with_reserved_name_fixed(arg_string_stem: String, arg_int_bytes_max: usize) -> String {
+    if !is_reserved_name(&arg_string_stem) {
+        return arg_string_stem;
+    }
+    let mut string_to_return = arg_string_stem;
+    let int_index_dot = string_to_return.find('.').unwrap_or(string_to_return.len());
+    let int_index_device_end = string_to_return[..int_index_dot]
+        .trim_end_matches(' ')
+        .len();
+    if string_to_return.len() < arg_int_bytes_max {
+        string_to_return.insert(int_index_device_end, '_');
+    } else {
+        let int_index_last_char = string_to_return[..int_index_device_end]
+            .char_indices()
+            .last()
+            .map(|(int_index, _)| int_index)
+            .unwrap_or_default();
+        string_to_return.replace_range(int_index_last_char..int_index_device_end, "_");
+    }
+    string_to_return
+}
+
+/// Splits at the last '.', so "a.tar.gz" is ( "a.tar", "gz" ); a leading '.' isn't an extension
+fn get_stem_and_extension(arg_str_name: &str) -> (String, String) {
+    match arg_str_name.rfind('.') {
+        Some(int_index) if int_index > 0 && int_index + 1 < arg_str_name.len() => (
+            arg_str_name[..int_index].to_string(),
+            arg_str_name[int_index + 1..].to_string(),
+        ),
+        _ => (arg_str_name.to_string(), String::new()),
+    }
+}
+
+fn get_string_truncated(arg_str: &str, arg_int_bytes_max: usize) -> String {
+    let mut int_index = arg_int_bytes_max.min(arg_str.len());
+    while !arg_str.is_char_boundary(int_index) {
+        int_index -= 1;
+    }
+    arg_str[..int_index].to_string()
+}
+
+fn get_string_with_chars_replaced(arg_str: &str, arg_str_replacement: &str) -> String {
+    let mut string_to_return = String::with_capacity(arg_str.len());
+    for item_char in arg_str.chars() {
+        if get_option_rule_for_char(item_char).is_some() {
+            string_to_return.push_str(arg_str_replacement);
+        } else {
+            string_to_return.push(item_char);
+        }
+    }
+    string_to_return
+}
+
+/// Returns true if Windows treats the name as a device, which it does whatever follows the first '.'
+/// and ignoring spaces before it, so "con", "CON.txt" and "Con .tar.gz" are all reserved
+fn is_reserved_name(arg_str_stem: &str) -> bool {
+    let str_device = arg_str_stem
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .trim_end_matches(' ')
+        .to_uppercase();
+    match str_device.as_str() {
+        "CON" | "PRN" | "AUX" | "NUL" | "CONIN$" | "CONOUT$" => true,
+        _ => {
+            let mut iter_of_chars = str_device.chars();
+            let str_prefix: String = iter_of_chars.by_ref().take(3).collect();
+            let vec_of_rest: Vec<char> = iter_of_chars.collect();
+            (str_prefix == "COM" || str_prefix == "LPT")
+                && vec_of_rest.len() == 1
+                && matches!(vec_of_rest[0], '0'..='9' | '\u{b9}' | '\u{b2}' | '\u{b3}')
+        }
+    }
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_sanitized_file_name() {
+        let sanitize_options = SanitizeOptions::new();
+        for (item_str_name, item_str_expected) in [
+            ("report.pdf", "report.pdf"),
+            ("../etc/passwd", ".._etc_passwd"),
+            ("a\\b\0c\u{7}d", "a_b_c_d"),
+            ("q3: report?.pdf", "q3_ report_.pdf"),
+            ("name. . ", "name"),
+            ("CON", "CON_"),
+            ("con.txt", "con_.txt"),
+            ("LPT9.tar.gz", "LPT9_.tar.gz"),
+            ("Aux .tar.gz", "Aux_ .tar.gz"),
+            ("COM10.txt", "COM10.txt"),
+            ("", "file"),
+            ("...", "file"),
+            (".bashrc", ".bashrc"),
+        ] {
+            assert_eq!(
+                get_sanitized_file_name(&item_str_name, &sanitize_options),
+                item_str_expected,
+            );
+        }
+        let sanitize_options = SanitizeOptions::new()
+            .replacement(&"")
+            .lowercase_extension()
+            .max_bytes(10);
+        assert_eq!(
+            get_sanitized_file_name(&"<<Photo>>.JPEG", &sanitize_options),
+            "Photo.jpeg"
+        );
+        assert_eq!(
+            get_sanitized_file_name(&"caf\u{e9}caf\u{e9}.txt", &sanitize_options),
+            "caf\u{e9}c.txt"
+        );
+        assert_eq!(
+            get_sanitized_file_name(&"CONSOLE.txt", &sanitize_options.clone().max_bytes(7)),
+            "CO_.txt"
+        );
+        assert_eq!(
+            get_sanitized_file_name(&"a.verylongextension", &sanitize_options),
+            "a.verylong"
+        );
+        // Shortening exposes "1PC" as the extension, which is then lowercased too
+        assert_eq!(
+            get_sanitized_file_name(&"C.1PC.\u{1F600}/", &sanitize_options.clone().max_bytes(5)),
+            "C.1pc"
+        );
+    }
+
+    #[test]
+    fn test_get_file_name_violations() {
+        let sanitize_options = SanitizeOptions::new().lowercase_extension().max_bytes(8);
+        assert_eq!(
+            get_file_name_violations(&"report.pdf", &sanitize_options),
+            vec![FileNameRule::TooLong]
+        );
+        assert_eq!(
+            get_file_name_violations(&"nul.TXT ", &sanitize_options),
+            vec![
+                FileNameRule::ReservedName,
+                FileNameRule::TrailingDotOrSpace,
+                FileNameRule::ExtensionNotNormalized,
+            ]
+        );
+        assert_eq!(
+            get_file_name_violations(&"a/b\0:", &sanitize_options),
+            vec![
+                FileNameRule::Separator,
+                FileNameRule::Nul,
+                FileNameRule::InvalidCharacter,
+            ]
+        );
+        assert_eq!(
+            get_file_name_violations(&"..", &sanitize_options),
+            vec![FileNameRule::DotName]
+        );
+        assert!(raise_error_if_file_name_is_unsafe(&"ok.txt", &sanitize_options).is_ok());
+        let err = raise_error_if_file_name_is_unsafe(&"", &sanitize_options).unwrap_err();
+        assert!(err.contains("name is empty"), "{}", err,);
+        // Whatever goes in, what comes out is safe
+        for item_str_name in [
+            "",
+            " . ",
+            "AUX.Tar.GZ",
+            "\u{1F600}\u{1F600}\u{1F600}",
+            "a?*<>",
+        ] {
+            let string_name = get_sanitized_file_name(&item_str_name, &sanitize_options);
+            assert_eq!(
+                get_file_name_violations(&string_name, &sanitize_options),
+                vec![],
+                "{:?} -> {:?}",
+                item_str_name,
+                string_name,
+            );
+        }
+    }
+
+    #[test]
+    fn test_get_sanitized_file_name_never_breaks_a_rule() {
+        // Names built from characters that hit every rule, with a fixed seed so failures repeat
+        let slice_of_chars = [
+            'a',
+            'B',
+            'c',
+            'P',
+            'C',
+            'O',
+            'N',
+            'L',
+            'T',
+            '1',
+            '.',
+            '.',
+            '.',
+            ' ',
+            '/',
+            '\\',
+            '\0',
+            '\u{7}',
+            ':',
+            '?',
+            '\u{e9}',
+            '\u{c9}',
+            '\u{130}',
+            '\u{1e9e}',
+            '\u{1F600}',
+            '_',
+        ];
+        let mut int_state: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut get_int_random = |arg_int_bound: usize| {
+            int_state ^= int_state << 13;
+            int_state ^= int_state >> 7;
+            int_state ^= int_state << 17;
+            (int_state % arg_int_bound as u64) as usize
+        };
+        for _ in 0..20_000 {
+            let string_name = (0..get_int_random(12))
+                .map(|_| slice_of_chars[get_int_random(slice_of_chars.len())])
+                .collect::<String>();
+            let mut sanitize_options = SanitizeOptions::new().max_bytes(1 + get_int_random(12));
+            if get_int_random(2) == 0 {
+                sanitize_options = sanitize_options.lowercase_extension();
+            }
+            if get_int_random(2) == 0 {
+                sanitize_options = sanitize_options.replacement(&"");
+            }
+            let string_name_sanitized = get_sanitized_file_name(&string_name, &sanitize_options);
+            assert_eq!(
+                get_file_name_violations(&string_name_sanitized, &sanitize_options),
+                vec![],
+                "{:?} -> {:?} with {:?}",
+                string_name,
+                string_name_sanitized,
+                sanitize_options,
+            );
+        }
+    }
+}
@@ -0,0 +1,313 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::io::Read;
+//
+// Libraries - downloaded
+//
+use regex::{Regex, RegexBuilder};
+//
+// Libraries - local
+//
+use crate::{find, get_extension};
+//
+// Public - structs
+//
+/// Options for search_in_files()
+/// By default the pattern is a literal, matching is case-sensitive, every extension is searched,
+/// binary files are skipped and there is no size limit.
+#[derive(Clone, Debug)]
+pub struct SearchOptions {
+    bool_regex: bool,
+    bool_case_insensitive: bool,
+    vec_of_extensions: Vec<String>,
+    bool_skip_binary: bool,
+    option_int_size_max: Option<u64>,
+}
+
+/// A single match returned by search_in_files()
+/// line_number and column are 1-based; column counts characters, not bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub path: String,
+    pub line_number: usize,
+    pub column: usize,
+    pub line: String,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        SearchOptions {
+            bool_regex: false,
+            bool_case_insensitive: false,
+            vec_of_extensions: vec![],
+            bool_skip_binary: true,
+            option_int_size_max: None,
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Returns the default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat the pattern as a regular expression instead of a literal
+    pub fn regex(mut self) -> Self {
+        self.bool_regex = true;
+        self
+    }
+
+    /// Ignore letter case when matching
+    pub fn case_insensitive(mut self) -> Self {
+        self.bool_case_insensitive = true;
+        self
+    }
+
+    /// Only search files with one of these extensions ( without the period )
+    /// # Arguments
+    /// * arg_slice_of_extensions: slice of string-likes, ie [ "rs", "toml" ]
+    pub fn extensions<T: Display>(mut self, arg_slice_of_extensions: &[T]) -> Self {
+        self.vec_of_extensions = arg_slice_of_extensions
+            .iter()
+            .map(|item| format!("{}", item,))
+            .collect::<Vec<String>>();
+        self
+    }
+
+    /// Also search files containing NUL bytes
+    pub fn include_binary(mut self) -> Self {
+        self.bool_skip_binary = false;
+        self
+    }
+
+    /// Skip files larger than arg_int_bytes
+    /// # Arguments
+    /// * arg_int_bytes: size in bytes
+    pub fn max_file_size(mut self, arg_int_bytes: u64) -> Self {
+        self.option_int_size_max = Some(arg_int_bytes);
+        self
+    }
+}
+//
+// Public - get - search
+//
+/// Returns every match of arg_string_pattern in the files under arg_string_path_root
+/// Each occurrence is its own match, so a line containing the pattern twice is returned twice.
+/// A file is treated as binary if a NUL byte appears in its first 8 KiB. Files that can't be read, ie
+/// because they vanished during the search, are skipped like grep -r skips them.
+/// In case of a failure ( bad regex, unreadable directory ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_root: string-like path to the directory to search
+/// * arg_string_pattern: string-like literal or regex
+/// * arg_search_options: SearchOptions
+/// # Examples
+/// let vec_of_matches = match search_in_files(
+///     &"src",
+///     &"todo",
+///     &SearchOptions::new().case_insensitive().extensions( &[ "rs" ] ),
+/// ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn search_in_files<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_root: &T1,
+    arg_string_pattern: &T2,
+    arg_search_options: &SearchOptions,
+) -> Result<Vec<SearchMatch>, String> {
+    let regex = get_regex_from_pattern(arg_string_pattern, arg_search_options)?;
+    let mut find_query = find(arg_string_path_root).type_file();
+    if let Some(int_size_max) = arg_search_options.option_int_size_max {
+        find_query = find_query.size_lt(int_size_max.saturating_add(1));
+    }
+    let mut vec_to_return = vec![];
+    for item_result in find_query {
+        let string_path = item_result?;
+        if !arg_search_options.vec_of_extensions.is_empty() {
+            let bool_extension_matches = match get_extension(&string_path) {
+                Some(string_extension) => arg_search_options
+                    .vec_of_extensions
+                    .contains(&string_extension),
+                None => false,
+            };
+            if !bool_extension_matches {
+                continue;
+            }
+        }
+        if let Ok(vec_of_matches) =
+            get_matches_in_file(&string_path, &regex, arg_search_options.bool_skip_binary)
+        {
+            vec_to_return.extend(vec_of_matches);
+        }
+    }
+    Ok(vec_to_return)
+}
+//
+// Private
+//
+/// Number of leading bytes checked for NUL when deciding if a file is binary
+const INT_BYTES_BINARY_CHECK: usize = 8192;
+
+fn get_regex_from_pattern<T: Debug + Display>(
+    arg_string_pattern: &T,
+    arg_search_options: &SearchOptions,
+) -> Result<Regex, String> {
+    let string_pattern = format!("{}", arg_string_pattern,);
+    let string_pattern = if arg_search_options.bool_regex {
+        string_pattern
+    } else {
+        regex::escape(&string_pattern)
+    };
+    match RegexBuilder::new(&string_pattern)
+        .case_insensitive(arg_search_options.bool_case_insensitive)
+        .build()
+    {
+        Ok(regex_result) => Ok(regex_result),
+        Err(err) => Err([
+            "Error: failed to build regex from pattern.".to_string(),
+            format!("err = {}", err,),
+            format!("arg_string_pattern = {}", arg_string_pattern,),
+        ]
+        .join("\n")),
+    }
+}
+
+fn get_matches_in_file(
+    arg_string_path: &str,
+    arg_regex: &Regex,
+    arg_bool_skip_binary: bool,
+) -> std::io::Result<Vec<SearchMatch>> {
+    let mut vec_of_bytes = vec![];
+    std::fs::File::open(arg_string_path)?.read_to_end(&mut vec_of_bytes)?;
+    if arg_bool_skip_binary
+        && vec_of_bytes
+            .iter()
+            .take(INT_BYTES_BINARY_CHECK)
+            .any(|item_byte| *item_byte == 0)
+    {
+        return Ok(vec![]);
+    }
+    let string_contents = String::from_utf8_lossy(&vec_of_bytes);
+    let mut vec_to_return = vec![];
+    for (item_int_index, item_str_line) in string_contents.lines().enumerate() {
+        for item_match in arg_regex.find_iter(item_str_line) {
+            vec_to_return.push(SearchMatch {
+                path: arg_string_path.to_string(),
+                line_number: item_int_index + 1,
+                column: item_str_line[..item_match.start()].chars().count() + 1,
+                line: item_str_line.to_string(),
+            });
+        }
+    }
+    Ok(vec_to_return)
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+
+    #[test]
+    fn test_search_in_files() {
+        let string_path_dir = get_dir_temp_for_tests("search_in_files");
+        std::fs::write(
+            format!("{}/a.rs", string_path_dir,),
+            "fn main() {}\n// TODO: one\n// todo: two todo\n",
+        )
+        .unwrap();
+        std::fs::write(format!("{}/b.txt", string_path_dir,), "TODO elsewhere\n").unwrap();
+        std::fs::write(format!("{}/c.rs", string_path_dir,), b"TODO\x00binary").unwrap();
+        let result = match search_in_files(
+            &string_path_dir,
+            &"todo",
+            &SearchOptions::new().case_insensitive().extensions(&["rs"]),
+        ) {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        let expected = [(2, 4), (3, 4), (3, 14)]
+            .iter()
+            .map(|(item_int_line, item_int_column)| SearchMatch {
+                path: format!("{}/a.rs", string_path_dir,),
+                line_number: *item_int_line,
+                column: *item_int_column,
+                line: if *item_int_line == 2 {
+                    "// TODO: one".to_string()
+                } else {
+                    "// todo: two todo".to_string()
+                },
+            })
+            .collect::<Vec<SearchMatch>>();
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn test_search_in_files_regex() {
+        let string_path_dir = get_dir_temp_for_tests("search_in_files_regex");
+        std::fs::write(format!("{}/small.rs", string_path_dir,), "fn alpha() {}\n").unwrap();
+        std::fs::write(
+            format!("{}/large.rs", string_path_dir,),
+            format!("fn beta() {{}}\n{}", "/".repeat(100),),
+        )
+        .unwrap();
+        let result = match search_in_files(
+            &string_path_dir,
+            &r"fn\s+(\w+)",
+            &SearchOptions::new().regex().max_file_size(50),
+        ) {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        let expected = vec![SearchMatch {
+            path: format!("{}/small.rs", string_path_dir,),
+            line_number: 1,
+            column: 1,
+            line: "fn alpha() {}".to_string(),
+        }];
+        assert_eq!(result, expected);
+        match search_in_files(&string_path_dir, &"(", &SearchOptions::new().regex()) {
+            Ok(_vec_result) => panic!("Accepted invalid regex."),
+            Err(_err) => {}
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_search_in_files_skips_unreadable_files() {
+        use std::os::unix::fs::PermissionsExt;
+        let string_path_dir = get_dir_temp_for_tests("search_in_files_unreadable");
+        std::fs::write(format!("{}/a.txt", string_path_dir,), "todo\n").unwrap();
+        let string_path_locked = format!("{}/b.txt", string_path_dir,);
+        std::fs::write(&string_path_locked, "todo\n").unwrap();
+        std::fs::set_permissions(&string_path_locked, std::fs::Permissions::from_mode(0o000))
+            .unwrap();
+        // Root can read the file anyway, and then it's searched like any other
+        let bool_is_readable = std::fs::read(&string_path_locked).is_ok();
+        let mut result = match search_in_files(&string_path_dir, &"todo", &SearchOptions::new()) {
+            Ok(vec_result) => vec_result,
+            Err(err) => panic!("{}", err,),
+        };
+        std::fs::set_permissions(&string_path_locked, std::fs::Permissions::from_mode(0o644))
+            .unwrap();
+        result.sort_by(|item_a, item_b| item_a.path.cmp(&item_b.path));
+        let slice_of_names: &[&str] = if bool_is_readable {
+            &["a.txt", "b.txt"]
+        } else {
+            &["a.txt"]
+        };
+        let expected = slice_of_names
+            .iter()
+            .map(|item_str_name| SearchMatch {
+                path: format!("{}/{}", string_path_dir, item_str_name,),
+                line_number: 1,
+                column: 1,
+                line: "todo".to_string(),
+            })
+            .collect::<Vec<SearchMatch>>();
+        assert_eq!(result, expected)
+    }
+}
@@ -0,0 +1,341 @@
+//! Serde adapters that expand and validate paths while a config is being deserialized
+//!
+//! Each adapter is used with `#[serde(with = "...")]` on a String, EasyPath or PathBuf field:
+//! * expanded: '~' and environment variables ( '$VAR', '${VAR}' ) are expanded
+//! * must_exist: like expanded, then the path must exist
+//! * absolute: like expanded, then the path is made absolute against the cwd
+//! * relative_to_config_file: like expanded, then a relative path is joined onto the directory of the
+//!   config file passed to with_config_file()
+//!
+//! Serializing writes the path back out unchanged.
+//!
+//! Serde's own errors don't say which field failed, so deserialize() wraps a deserializer and
+//! reports the field alongside the error.
+//
+// Libraries - native
+//
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::path::Path;
+//
+// Libraries - downloaded
+//
+use ::serde::de::Error as _;
+use ::serde::{Deserialize, Deserializer, Serializer};
+//
+// Libraries - local
+//
+use crate::{
+    get_absolute_path, get_dir_name, get_path_buf_absolute_with_normalization,
+    get_path_with_tilde_expanded_if_necessary, raise_error_if_path_does_not_exist,
+    PathNormalization,
+};
+//
+// Public - adapters
+//
+/// '~' and environment variables are expanded
+pub mod expanded {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<String>>(
+        arg_deserializer: D,
+    ) -> Result<T, D::Error> {
+        let string_path = String::deserialize(arg_deserializer)?;
+        Ok(T::from(
+            get_path_expanded(&string_path).map_err(D::Error::custom)?,
+        ))
+    }
+
+    pub fn serialize<S: Serializer, T: AsRef<Path>>(
+        arg_path: &T,
+        arg_serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_path(arg_path, arg_serializer)
+    }
+}
+
+/// '~' and environment variables are expanded, then the path must exist
+pub mod must_exist {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<String>>(
+        arg_deserializer: D,
+    ) -> Result<T, D::Error> {
+        let string_path = String::deserialize(arg_deserializer)?;
+        let string_path = get_path_expanded(&string_path).map_err(D::Error::custom)?;
+        raise_error_if_path_does_not_exist(&string_path).map_err(D::Error::custom)?;
+        Ok(T::from(string_path))
+    }
+
+    pub fn serialize<S: Serializer, T: AsRef<Path>>(
+        arg_path: &T,
+        arg_serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_path(arg_path, arg_serializer)
+    }
+}
+
+/// '~' and environment variables are expanded, then the path is made absolute against the cwd and normalized
+/// The path needn't exist, and symlinks aren't resolved.
+pub mod absolute {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<String>>(
+        arg_deserializer: D,
+    ) -> Result<T, D::Error> {
+        let string_path = String::deserialize(arg_deserializer)?;
+        let string_path = get_path_expanded(&string_path).map_err(D::Error::custom)?;
+        // Joined onto the cwd without touching the disk, so a path that doesn't exist yet still comes back absolute
+        let path_buf_absolute =
+            get_path_buf_absolute_with_normalization(&string_path, PathNormalization::Lexical)
+                .map_err(D::Error::custom)?;
+        Ok(T::from(path_buf_absolute.to_string_lossy().into_owned()))
+    }
+
+    pub fn serialize<S: Serializer, T: AsRef<Path>>(
+        arg_path: &T,
+        arg_serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_path(arg_path, arg_serializer)
+    }
+}
+
+/// '~' and environment variables are expanded, then a relative path is joined onto the directory
+/// holding the config file
+/// This must run inside with_config_file(), otherwise deserializing fails.
+pub mod relative_to_config_file {
+    use super::*;
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<String>>(
+        arg_deserializer: D,
+    ) -> Result<T, D::Error> {
+        let string_path = String::deserialize(arg_deserializer)?;
+        let string_path = get_path_expanded(&string_path).map_err(D::Error::custom)?;
+        if Path::new(&string_path).is_absolute() {
+            return Ok(T::from(string_path));
+        }
+        let string_path_dir_config =
+            match CELL_OPTION_STRING_PATH_DIR_CONFIG.with(|cell| cell.borrow().clone()) {
+                Some(string_result) => string_result,
+                None => return Err(D::Error::custom(
+                    [
+                        "Error: relative_to_config_file was used outside of with_config_file()."
+                            .to_string(),
+                        format!("path = {}", string_path,),
+                    ]
+                    .join("\n"),
+                )),
+            };
+        Ok(T::from(
+            Path::new(&string_path_dir_config)
+                .join(&string_path)
+                .to_string_lossy()
+                .into_owned(),
+        ))
+    }
+
+    pub fn serialize<S: Serializer, T: AsRef<Path>>(
+        arg_path: &T,
+        arg_serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serialize_path(arg_path, arg_serializer)
+    }
+}
+//
+// Public - deserialize
+//
+/// Returns the deserialized value, or an error naming the field that failed ( ie 'paths.cache_dir' )
+/// # Arguments
+/// * arg_deserializer: any serde deserializer
+/// # Examples
+/// let config: Config = match easy_paths::serde::deserialize(
+///     &mut serde_json::Deserializer::from_str( &string_json ),
+/// ) {
+///     Ok( config_result ) => { config_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>>(
+    arg_deserializer: D,
+) -> Result<T, String>
+where
+    D::Error: Display,
+{
+    match serde_path_to_error::deserialize(arg_deserializer) {
+        Ok(value_result) => Ok(value_result),
+        Err(err) => Err([
+            "Error: failed to deserialize.".to_string(),
+            format!("field = {}", err.path(),),
+            format!("err = {}", err.inner(),),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Returns the result of arg_closure, during which relative_to_config_file resolves relative paths
+/// against the directory holding arg_string_path_config_file
+/// # Arguments
+/// * arg_string_path_config_file: string-like path to the config file being parsed
+/// * arg_closure: closure that deserializes the config
+/// # Examples
+/// let string_path_config = "config/app.toml";
+/// let string_contents = std::fs::read_to_string( &string_path_config ).unwrap();
+/// let result: Result<Config, String> = easy_paths::serde::with_config_file( &string_path_config, || {
+///     easy_paths::serde::deserialize( toml::Deserializer::new( &string_contents ) )
+/// } );
+pub fn with_config_file<T: Display + ?Sized, R, F: FnOnce() -> R>(
+    arg_string_path_config_file: &T,
+    arg_closure: F,
+) -> R {
+    let string_path_dir_config = get_dir_name(&get_absolute_path(&format!(
+        "{}",
+        arg_string_path_config_file,
+    )))
+    .unwrap_or_default();
+    let option_string_previous =
+        CELL_OPTION_STRING_PATH_DIR_CONFIG.with(|cell| cell.replace(Some(string_path_dir_config)));
+    // Restores the previous value even if arg_closure panics, so nested calls behave
+    let _config_dir_restorer = ConfigDirRestorer {
+        option_string_previous,
+    };
+    arg_closure()
+}
+//
+// Private
+//
+thread_local! {
+    static CELL_OPTION_STRING_PATH_DIR_CONFIG: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+struct ConfigDirRestorer {
+    option_string_previous: Option<String>,
+}
+
+impl Drop for ConfigDirRestorer {
+    fn drop(&mut self) {
+        let option_string_previous = self.option_string_previous.take();
+        CELL_OPTION_STRING_PATH_DIR_CONFIG.with(|cell| cell.replace(option_string_previous));
+    }
+}
+
+/// Returns the path with environment variables, then '~', expanded
+fn get_path_expanded(arg_string_path: &str) -> Result<String, String> {
+    match shellexpand::env(arg_string_path) {
+        Ok(cow_result) => Ok(get_path_with_tilde_expanded_if_necessary(&cow_result)),
+        Err(err) => Err([
+            "Error: failed to expand environment variable in path.".to_string(),
+            format!("err = {}", err,),
+            format!("path = {}", arg_string_path,),
+        ]
+        .join("\n")),
+    }
+}
+
+fn serialize_path<S: Serializer, T: AsRef<Path>>(
+    arg_path: &T,
+    arg_serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match arg_path.as_ref().to_str() {
+        Some(str_result) => arg_serializer.serialize_str(str_result),
+        None => Err(::serde::ser::Error::custom(
+            [
+                "Error: path is not valid UTF-8.".to_string(),
+                format!("path = {:?}", arg_path.as_ref(),),
+            ]
+            .join("\n"),
+        )),
+    }
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EasyPath;
+    use ::serde::{Deserialize, Serialize};
+    use std::path::PathBuf;
+
+    #[derive(Debug, Deserialize, Serialize)]
+    struct Config {
+        #[serde(with = "crate::serde::expanded")]
+        path_expanded: String,
+        #[serde(with = "crate::serde::must_exist")]
+        path_must_exist: EasyPath,
+        #[serde(with = "crate::serde::absolute")]
+        path_absolute: String,
+        #[serde(with = "crate::serde::relative_to_config_file")]
+        path_relative: PathBuf,
+    }
+
+    #[test]
+    fn test_adapters() {
+        // Cargo sets CARGO_PKG_NAME for the test binary, so nothing here changes the environment
+        let string_json = r#"{
+            "path_expanded": "~/${CARGO_PKG_NAME}/a",
+            "path_must_exist": "test/test_a",
+            "path_absolute": "test/../src",
+            "path_relative": "data/b.txt"
+        }"#;
+        let config: Config = match with_config_file(&"/etc/app/config.json", || {
+            deserialize(&mut serde_json::Deserializer::from_str(string_json))
+        }) {
+            Ok(config_result) => config_result,
+            Err(err) => panic!("{}", err,),
+        };
+        assert!(!config.path_expanded.contains('~'));
+        assert!(config
+            .path_expanded
+            .ends_with(&format!("/{}/a", env!("CARGO_PKG_NAME"),)));
+        assert_eq!(config.path_must_exist, EasyPath::from("test/test_a"));
+        assert_eq!(
+            config.path_absolute,
+            format!("{}/src", env!("CARGO_MANIFEST_DIR"),)
+        );
+        assert_eq!(config.path_relative, PathBuf::from("/etc/app/data/b.txt"));
+        #[derive(Debug, Deserialize)]
+        struct ConfigAbsolute {
+            #[serde(with = "crate::serde::absolute")]
+            path_absolute: String,
+        }
+        let config_absolute: ConfigAbsolute = deserialize(&mut serde_json::Deserializer::from_str(
+            r#"{ "path_absolute": "config/missing.toml" }"#,
+        ))
+        .unwrap();
+        assert_eq!(
+            config_absolute.path_absolute,
+            format!("{}/config/missing.toml", env!("CARGO_MANIFEST_DIR"),)
+        );
+        let string_json_round_trip = serde_json::to_string(&config).unwrap();
+        assert!(string_json_round_trip.contains(r#""path_relative":"/etc/app/data/b.txt""#));
+    }
+
+    #[test]
+    fn test_errors_name_field_and_path() {
+        let string_json = r#"{
+            "path_expanded": "a",
+            "path_must_exist": "test/missing",
+            "path_absolute": "b",
+            "path_relative": "c"
+        }"#;
+        let err = match with_config_file(&"config.json", || {
+            deserialize::<_, Config>(&mut serde_json::Deserializer::from_str(string_json))
+        }) {
+            Ok(_config_result) => panic!("Accepted a path that doesn't exist."),
+            Err(err) => err,
+        };
+        assert!(err.contains("field = path_must_exist"), "{}", err,);
+        assert!(err.contains("test/missing"), "{}", err,);
+        let string_json = r#"{
+            "path_expanded": "a",
+            "path_must_exist": "test",
+            "path_absolute": "b",
+            "path_relative": "c"
+        }"#;
+        let err =
+            match deserialize::<_, Config>(&mut serde_json::Deserializer::from_str(string_json)) {
+                Ok(_config_result) => panic!("Resolved a relative path without a config file."),
+                Err(err) => err,
+            };
+        assert!(err.contains("field = path_relative"), "{}", err,);
+    }
+}
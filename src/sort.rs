@@ -0,0 +1,384 @@
+//
+// Libraries - native
+//
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display};
+//
+// Public - get - sorted
+//
+/// Returns the paths in natural order, so "file2" comes before "file10"
+/// Paths are compared component by component, so everything in "a" comes before "a-b". Runs of digits
+/// compare by value, and everything else compares by character.
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = get_paths_sorted_natural( &[ "img/file10.png", "img/file2.png", "img/file1.png" ] );
+/// // vec_of_paths = [ "img/file1.png", "img/file2.png", "img/file10.png" ]
+pub fn get_paths_sorted_natural<T: Display>(arg_slice_of_strings: &[T]) -> Vec<String> {
+    let mut vec_to_return = get_vec_of_strings(arg_slice_of_strings);
+    vec_to_return.sort_by(|left, right| compare_paths_natural(left, right, false));
+    vec_to_return
+}
+
+/// Returns the paths in natural order, ignoring letter case
+/// Paths that only differ by case keep a stable order, uppercase first.
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = get_paths_sorted_case_insensitive( &[ "b.txt", "A.txt", "a2.txt" ] );
+/// // vec_of_paths = [ "A.txt", "a2.txt", "b.txt" ]
+pub fn get_paths_sorted_case_insensitive<T: Display>(arg_slice_of_strings: &[T]) -> Vec<String> {
+    let mut vec_to_return = get_vec_of_strings(arg_slice_of_strings);
+    vec_to_return.sort_by(|left, right| compare_paths_natural(left, right, true));
+    vec_to_return
+}
+
+/// Returns the directories, then everything else, each group in natural order
+/// Whether a path is a directory is checked on disk, following symlinks; paths that don't exist sort
+/// with the files.
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = get_paths_sorted_dirs_first( &[ "Cargo.toml", "src", "README.md", "tests" ] );
+/// // vec_of_paths = [ "src", "tests", "Cargo.toml", "README.md" ]
+pub fn get_paths_sorted_dirs_first<T: Display>(arg_slice_of_strings: &[T]) -> Vec<String> {
+    let mut vec_of_tuples = get_vec_of_strings(arg_slice_of_strings)
+        .into_iter()
+        .map(|item_string| (!std::path::Path::new(&item_string).is_dir(), item_string))
+        .collect::<Vec<(bool, String)>>();
+    vec_of_tuples.sort_by(|(left_bool, left), (right_bool, right)| {
+        left_bool
+            .cmp(right_bool)
+            .then_with(|| compare_paths_natural(left, right, false))
+    });
+    vec_of_tuples
+        .into_iter()
+        .map(|(_, item_string)| item_string)
+        .collect()
+}
+
+/// Returns the paths in natural order of one component, ties broken by the whole path
+/// A negative index counts from the end, so -1 sorts by file name. Paths without that component come first.
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// * arg_int_index: component index, ie 0 for the first component or -1 for the last
+/// # Examples
+/// let vec_of_paths = get_paths_sorted_by_component( &[ "b/2.txt", "a/10.txt", "c/1.txt" ], -1 );
+/// // vec_of_paths = [ "c/1.txt", "b/2.txt", "a/10.txt" ]
+pub fn get_paths_sorted_by_component<T: Display>(
+    arg_slice_of_strings: &[T],
+    arg_int_index: isize,
+) -> Vec<String> {
+    let mut vec_to_return = get_vec_of_strings(arg_slice_of_strings);
+    vec_to_return.sort_by(|left, right| {
+        let option_left = get_option_component(left, arg_int_index);
+        let option_right = get_option_component(right, arg_int_index);
+        let ordering = match (option_left, option_right) {
+            (Some(str_left), Some(str_right)) => compare_strs_natural(str_left, str_right, false),
+            (left_option, right_option) => left_option.is_some().cmp(&right_option.is_some()),
+        };
+        ordering.then_with(|| compare_paths_natural(left, right, false))
+    });
+    vec_to_return
+}
+
+/// Returns the paths by modification time, oldest first, ties in natural order
+/// In case of a failure ( ie a path doesn't exist ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = match get_paths_sorted_by_modified( &[ "new.log", "old.log" ] ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // vec_of_paths = [ "old.log", "new.log" ]
+pub fn get_paths_sorted_by_modified<T: Debug + Display>(
+    arg_slice_of_strings: &[T],
+) -> Result<Vec<String>, String> {
+    get_paths_sorted_by_metadata(arg_slice_of_strings, |metadata| metadata.modified())
+}
+
+/// Returns the paths by size on disk, smallest first, ties in natural order
+/// Directories use the size their metadata reports, not the size of their contents.
+/// In case of a failure ( ie a path doesn't exist ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = match get_paths_sorted_by_file_size( &[ "big.bin", "small.txt" ] ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // vec_of_paths = [ "small.txt", "big.bin" ]
+pub fn get_paths_sorted_by_file_size<T: Debug + Display>(
+    arg_slice_of_strings: &[T],
+) -> Result<Vec<String>, String> {
+    get_paths_sorted_by_metadata(arg_slice_of_strings, |metadata| Ok(metadata.len()))
+}
+
+/// Returns the paths by length in characters, shortest first, ties in natural order
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = get_paths_sorted_by_length( &[ "/bb", "/a/b", "/c" ] );
+/// // vec_of_paths = [ "/c", "/bb", "/a/b" ]
+pub fn get_paths_sorted_by_length<T: Display>(arg_slice_of_strings: &[T]) -> Vec<String> {
+    let mut vec_to_return = get_vec_of_strings(arg_slice_of_strings);
+    vec_to_return.sort_by(|left, right| {
+        left.chars()
+            .count()
+            .cmp(&right.chars().count())
+            .then_with(|| compare_paths_natural(left, right, false))
+    });
+    vec_to_return
+}
+
+/// Returns the paths by number of components, shallowest first, ties in natural order
+/// This is the order get_paths_sorted_by_size_starting_with_shortest()'s name describes.
+/// # Arguments
+/// * arg_slice_of_strings: [] of string-likes
+/// # Examples
+/// let vec_of_paths = get_paths_sorted_by_depth( &[ "/A/B/C", "/Z", "/A/B" ] );
+/// // vec_of_paths = [ "/Z", "/A/B", "/A/B/C" ]
+pub fn get_paths_sorted_by_depth<T: Display>(arg_slice_of_strings: &[T]) -> Vec<String> {
+    let mut vec_to_return = get_vec_of_strings(arg_slice_of_strings);
+    vec_to_return.sort_by(|left, right| {
+        get_vec_of_components(left)
+            .len()
+            .cmp(&get_vec_of_components(right).len())
+            .then_with(|| compare_paths_natural(left, right, false))
+    });
+    vec_to_return
+}
+//
+// Private
+//
+fn compare_paths_natural(
+    arg_str_left: &str,
+    arg_str_right: &str,
+    arg_bool_case_insensitive: bool,
+) -> Ordering {
+    let vec_of_components_left = get_vec_of_components(arg_str_left);
+    let vec_of_components_right = get_vec_of_components(arg_str_right);
+    for (item_str_left, item_str_right) in
+        vec_of_components_left.iter().zip(&vec_of_components_right)
+    {
+        let ordering =
+            compare_strs_natural(item_str_left, item_str_right, arg_bool_case_insensitive);
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    vec_of_components_left
+        .len()
+        .cmp(&vec_of_components_right.len())
+        // Paths that only differ by case, leading zeros or separators still get a fixed order
+        .then_with(|| arg_str_left.cmp(arg_str_right))
+}
+
+/// Compares runs of digits by value and everything else by character
+/// Leading zeros only break ties, so "7" < "007" < "8".
+fn compare_strs_natural(
+    arg_str_left: &str,
+    arg_str_right: &str,
+    arg_bool_case_insensitive: bool,
+) -> Ordering {
+    let (string_left, string_right) = if arg_bool_case_insensitive {
+        (arg_str_left.to_lowercase(), arg_str_right.to_lowercase())
+    } else {
+        (arg_str_left.to_string(), arg_str_right.to_string())
+    };
+    let mut iter_left = string_left.chars().peekable();
+    let mut iter_right = string_right.chars().peekable();
+    let mut ordering_leading_zeros = Ordering::Equal;
+    loop {
+        match (iter_left.peek().copied(), iter_right.peek().copied()) {
+            (None, None) => return ordering_leading_zeros,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(char_left), Some(char_right))
+                if char_left.is_ascii_digit() && char_right.is_ascii_digit() =>
+            {
+                let string_digits_left = get_string_digits(&mut iter_left);
+                let string_digits_right = get_string_digits(&mut iter_right);
+                let str_value_left = string_digits_left.trim_start_matches('0');
+                let str_value_right = string_digits_right.trim_start_matches('0');
+                let ordering = str_value_left
+                    .len()
+                    .cmp(&str_value_right.len())
+                    .then_with(|| str_value_left.cmp(str_value_right));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                if ordering_leading_zeros == Ordering::Equal {
+                    ordering_leading_zeros =
+                        string_digits_left.len().cmp(&string_digits_right.len());
+                }
+            }
+            (Some(char_left), Some(char_right)) => {
+                if char_left != char_right {
+                    return char_left.cmp(&char_right);
+                }
+                iter_left.next();
+                iter_right.next();
+            }
+        }
+    }
+}
+
+fn get_option_component(arg_str_path: &str, arg_int_index: isize) -> Option<&str> {
+    let vec_of_components = get_vec_of_components(arg_str_path);
+    let int_index = if arg_int_index < 0 {
+        vec_of_components
+            .len()
+            .checked_sub(arg_int_index.unsigned_abs())?
+    } else {
+        arg_int_index as usize
+    };
+    vec_of_components.get(int_index).copied()
+}
+
+fn get_paths_sorted_by_metadata<T: Debug + Display, K: Ord, F>(
+    arg_slice_of_strings: &[T],
+    arg_closure_key: F,
+) -> Result<Vec<String>, String>
+where
+    F: Fn(&std::fs::Metadata) -> std::io::Result<K>,
+{
+    let mut vec_of_tuples = vec![];
+    for item_string_path in get_vec_of_strings(arg_slice_of_strings) {
+        let key = match std::fs::metadata(&item_string_path)
+            .and_then(|metadata| arg_closure_key(&metadata))
+        {
+            Ok(key) => key,
+            Err(err) => {
+                return Err([
+                    "Error: failed to read metadata.".to_string(),
+                    format!("err = {}", err,),
+                    format!("item_string_path = {}", item_string_path,),
+                ]
+                .join("\n"))
+            }
+        };
+        vec_of_tuples.push((key, item_string_path));
+    }
+    vec_of_tuples.sort_by(|(left_key, left), (right_key, right)| {
+        left_key
+            .cmp(right_key)
+            .then_with(|| compare_paths_natural(left, right, false))
+    });
+    Ok(vec_of_tuples
+        .into_iter()
+        .map(|(_, item_string_path)| item_string_path)
+        .collect())
+}
+
+fn get_string_digits(arg_iter: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut string_to_return = String::new();
+    while let Some(char_digit) = arg_iter.next_if(|item_char| item_char.is_ascii_digit()) {
+        string_to_return.push(char_digit);
+    }
+    string_to_return
+}
+
+/// Splits on '/' and the platform separator, dropping empty components, so "/a//b/" is [ "a", "b" ]
+fn get_vec_of_components(arg_str_path: &str) -> Vec<&str> {
+    arg_str_path
+        .split(['/', std::path::MAIN_SEPARATOR])
+        .filter(|item_str| !item_str.is_empty())
+        .collect()
+}
+
+fn get_vec_of_strings<T: Display>(arg_slice_of_strings: &[T]) -> Vec<String> {
+    arg_slice_of_strings
+        .iter()
+        .map(|item| format!("{}", item,))
+        .collect()
+}
+
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_get_paths_sorted_natural() {
+        assert_eq!(
+            get_paths_sorted_natural(&[
+                "img/file10.png",
+                "img/file2.png",
+                "img/file1.png",
+                "img-b/x",
+                "img/file02.png"
+            ]),
+            vec![
+                "img/file1.png",
+                "img/file2.png",
+                "img/file02.png",
+                "img/file10.png",
+                "img-b/x"
+            ]
+        );
+        assert_eq!(
+            get_paths_sorted_case_insensitive(&["b.txt", "a2.txt", "A.txt", "a.txt", "a10.txt"]),
+            vec!["A.txt", "a.txt", "a2.txt", "a10.txt", "b.txt"]
+        );
+        assert_eq!(
+            get_paths_sorted_by_component(&["b/2.txt", "a/10.txt", "c/1.txt", "top"], -1),
+            vec!["c/1.txt", "b/2.txt", "a/10.txt", "top"]
+        );
+        assert_eq!(
+            get_paths_sorted_by_component(&["b/2.txt", "a/10.txt", "top"], 1),
+            vec!["top", "b/2.txt", "a/10.txt"]
+        );
+    }
+
+    #[test]
+    fn test_get_paths_sorted_by_length_and_depth() {
+        assert_eq!(
+            get_paths_sorted_by_length(&["/bb", "/a/b", "/c", "/\u{e9}"]),
+            vec!["/c", "/\u{e9}", "/bb", "/a/b"]
+        );
+        assert_eq!(
+            get_paths_sorted_by_depth(&["/A/B/C", "/Z", "/A/B", "/A/B10", "/A/B9"]),
+            vec!["/Z", "/A/B", "/A/B9", "/A/B10", "/A/B/C"]
+        );
+    }
+
+    #[test]
+    fn test_get_paths_sorted_by_metadata() {
+        let string_path_dir = get_dir_temp_for_tests("get_paths_sorted_by_metadata");
+        let vec_of_paths = ["big", "small", "dir"]
+            .iter()
+            .map(|item_str| format!("{}/{}", string_path_dir, item_str,))
+            .collect::<Vec<String>>();
+        std::fs::write(&vec_of_paths[0], "0123456789").unwrap();
+        std::fs::write(&vec_of_paths[1], "0").unwrap();
+        std::fs::create_dir(&vec_of_paths[2]).unwrap();
+        let file = std::fs::File::options()
+            .write(true)
+            .open(&vec_of_paths[1])
+            .unwrap();
+        file.set_modified(SystemTime::UNIX_EPOCH).unwrap();
+        assert_eq!(
+            get_paths_sorted_dirs_first(&vec_of_paths),
+            vec![
+                vec_of_paths[2].clone(),
+                vec_of_paths[0].clone(),
+                vec_of_paths[1].clone()
+            ]
+        );
+        assert_eq!(
+            get_paths_sorted_by_file_size(&vec_of_paths[..2]),
+            Ok(vec![vec_of_paths[1].clone(), vec_of_paths[0].clone()])
+        );
+        assert_eq!(
+            get_paths_sorted_by_modified(&vec_of_paths[..2]),
+            Ok(vec![vec_of_paths[1].clone(), vec_of_paths[0].clone()])
+        );
+        assert!(get_paths_sorted_by_modified(&[format!("{}/missing", string_path_dir,)]).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//
+// Libraries - native
+//
+use std::collections::HashSet;
+use std::fmt::{Debug, Display};
+use std::path::{Component, Path, PathBuf};
+//
+// Libraries - local
+//
+use crate::join::get_path_buf_with_symlinks_resolved;
+use crate::{find, get_relative_path_between, PathNormalization};
+//
+// Public - get
+//
+/// Returns the target stored in the symlink at arg_string_path, exactly as written, without resolving it
+/// In case of a failure ( ie the path isn't a symlink ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path to a symlink
+/// # Examples
+/// let string_target = match get_symlink_target( &"/usr/bin/python3" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_target = "python3.12"
+pub fn get_symlink_target<T: Debug + Display>(arg_string_path: &T) -> Result<String, String> {
+    let path_buf_target = match std::fs::read_link(format!("{}", arg_string_path,)) {
+        Ok(path_buf_result) => path_buf_result,
+        Err(err) => {
+            return Err([
+                "Error: failed to read symlink.".to_string(),
+                format!("err = {}", err,),
+                format!("arg_string_path = {}", arg_string_path,),
+            ]
+            .join("\n"))
+        }
+    };
+    get_string_from_path_buf(path_buf_target, arg_string_path)
+}
+
+/// Returns each hop from arg_string_path through a chain of symlinks, ending at the first path that isn't a symlink
+/// The first element is arg_string_path as given. Each later one is where the previous link points,
+/// made absolute with every symlink before its last component resolved, so a '..' after a link
+/// climbs out of the link's target like the OS would. If the chain is broken, the last element is
+/// the missing path; is_broken_symlink() tells the two cases apart.
+/// In case of a failure ( ie a loop, or more than 40 hops ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let vec_of_hops = match get_symlink_chain( &"/usr/bin/python" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // vec_of_hops = [ "/usr/bin/python", "/usr/bin/python3", "/usr/bin/python3.12" ]
+pub fn get_symlink_chain<T: Debug + Display>(arg_string_path: &T) -> Result<Vec<String>, String> {
+    const INT_HOPS_MAX: usize = 40;
+    let mut vec_to_return = vec![format!("{}", arg_string_path,)];
+    let mut set_of_string_paths_visited = HashSet::new();
+    let mut path_buf_current = PathBuf::from(format!("{}", arg_string_path,));
+    loop {
+        match std::fs::symlink_metadata(&path_buf_current) {
+            Ok(metadata) if metadata.file_type().is_symlink() => {}
+            Ok(_) => break,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
+            Err(err) => {
+                return Err([
+                    "Error: failed to get metadata.".to_string(),
+                    format!("err = {}", err,),
+                    format!("path_buf_current = {}", path_buf_current.display(),),
+                    format!("arg_string_path = {}", arg_string_path,),
+                ]
+                .join("\n"))
+            }
+        }
+        if vec_to_return.len() > INT_HOPS_MAX {
+            return Err([
+                "Error: too many levels of symbolic links.".to_string(),
+                format!("vec_of_hops = {:#?}", vec_to_return,),
+            ]
+            .join("\n"));
+        }
+        let path_buf_target = PathBuf::from(get_symlink_target(&path_buf_current.display())?);
+        let path_buf_joined = match path_buf_current.parent() {
+            Some(path_parent) => path_parent.join(path_buf_target),
+            None => path_buf_target,
+        };
+        let string_path_next = match get_path_buf_of_hop(&path_buf_joined) {
+            Ok(path_buf_result) => path_buf_result.display().to_string(),
+            Err(err) => {
+                return Err([
+                    "Error: failed to resolve symlink target.".to_string(),
+                    format!("err = {}", err,),
+                    format!("path_buf_joined = {}", path_buf_joined.display(),),
+                    format!("vec_of_hops = {:#?}", vec_to_return,),
+                ]
+                .join("\n"))
+            }
+        };
+        if !set_of_string_paths_visited.insert(string_path_next.clone()) {
+            vec_to_return.push(string_path_next);
+            return Err([
+                "Error: symlinks form a loop.".to_string(),
+                format!("vec_of_hops = {:#?}", vec_to_return,),
+            ]
+            .join("\n"));
+        }
+        vec_to_return.push(string_path_next.clone());
+        path_buf_current = PathBuf::from(string_path_next);
+    }
+    Ok(vec_to_return)
+}
+//
+// Public - ( logic ) are / is
+//
+/// Returns true if arg_string_path is a symlink whose target can't be reached, ie missing or part of a loop
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let result = is_broken_symlink( &"/etc/alternatives/editor" );
+pub fn is_broken_symlink<T: Debug + Display>(arg_string_path: &T) -> bool {
+    let string_path = format!("{}", arg_string_path,);
+    is_symlink(&string_path) && std::fs::metadata(&string_path).is_err()
+}
+
+/// Returns true if arg_string_path is a symlink, without following it
+/// is_dir() and is_file() follow symlinks, so a link to a directory is also is_dir().
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let result = is_symlink( &"/usr/bin/python3" );
+pub fn is_symlink<T: Debug + Display>(arg_string_path: &T) -> bool {
+    std::fs::symlink_metadata(format!("{}", arg_string_path,))
+        .map(|metadata| metadata.file_type().is_symlink())
+        .unwrap_or(false)
+}
+//
+// Public - create / find
+//
+/// Creates a symlink at arg_string_path_link pointing to arg_string_path_target through a relative path, like ln -sr
+/// The relative path starts from the link's real parent directory, which must exist, so the pair can
+/// be moved together. A relative arg_string_path_target is taken from the cwd, not from the link.
+/// The target needn't exist; whatever part of it does is resolved first. On Windows, a directory
+/// symlink is created if the target is a directory.
+/// Returns the relative target that was written
+/// In case of a failure ( ie the link already exists ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_target: string-like path the link should point to
+/// * arg_string_path_link: string-like path of the link to create
+/// # Examples
+/// let string_target = match create_symlink_relative( &"/srv/app/releases/v2", &"/srv/app/current" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_target = "releases/v2"
+pub fn create_symlink_relative<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_target: &T1,
+    arg_string_path_link: &T2,
+) -> Result<String, String> {
+    let string_path_link = format!("{}", arg_string_path_link,);
+    let path_parent = match Path::new(&string_path_link).parent() {
+        Some(path_result) if !path_result.as_os_str().is_empty() => path_result,
+        _ => Path::new("."),
+    };
+    let string_path_parent = crate::get_absolute_path_or_error(&path_parent.display())?;
+    // Resolving what exists of the target keeps both sides comparable when it's under a symlink
+    let string_path_target = match get_path_buf_with_symlinks_resolved(Path::new(&format!(
+        "{}",
+        arg_string_path_target,
+    ))) {
+        Ok(path_buf_result) => path_buf_result.display().to_string(),
+        Err(err) => {
+            return Err([
+                "Error: failed to resolve target.".to_string(),
+                format!("err = {}", err,),
+                format!("arg_string_path_target = {}", arg_string_path_target,),
+            ]
+            .join("\n"))
+        }
+    };
+    let string_target = get_relative_path_between(
+        &string_path_parent,
+        &string_path_target,
+        PathNormalization::Lexical,
+    )?;
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(&string_target, &string_path_link);
+    #[cfg(windows)]
+    let result = if crate::is_dir(arg_string_path_target) {
+        std::os::windows::fs::symlink_dir(&string_target, &string_path_link)
+    } else {
+        std::os::windows::fs::symlink_file(&string_target, &string_path_link)
+    };
+    match result {
+        Ok(()) => Ok(string_target),
+        Err(err) => Err([
+            "Error: failed to create symlink.".to_string(),
+            format!("err = {}", err,),
+            format!("string_target = {}", string_target,),
+            format!("arg_string_path_link = {}", arg_string_path_link,),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Returns every broken symlink in a directory and its sub-directories, without following any links
+/// In case of a failure ( ie part of the tree can't be read ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_dir: string-like path to a directory
+/// # Examples
+/// let vec_of_broken_links = match find_broken_symlinks( &"/srv/app" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn find_broken_symlinks<T: Debug + Display>(
+    arg_string_path_dir: &T,
+) -> Result<Vec<String>, String> {
+    Ok(find(arg_string_path_dir)
+        .type_symlink()
+        .into_iter()
+        .collect::<Result<Vec<String>, String>>()?
+        .into_iter()
+        .filter(is_broken_symlink)
+        .collect())
+}
+//
+// Private
+//
+/// Returns arg_path with every symlink resolved except one in its last component, which is the next hop
+fn get_path_buf_of_hop(arg_path: &Path) -> std::io::Result<PathBuf> {
+    match (arg_path.parent(), arg_path.components().next_back()) {
+        (Some(path_parent), Some(Component::Normal(os_str_name))) => {
+            let path_parent = if path_parent.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                path_parent
+            };
+            Ok(get_path_buf_with_symlinks_resolved(path_parent)?.join(os_str_name))
+        }
+        // The last component is '..', '.' or the root, so it can't be a symlink itself
+        _ => get_path_buf_with_symlinks_resolved(arg_path),
+    }
+}
+
+fn get_string_from_path_buf<T: Display>(
+    arg_path_buf: PathBuf,
+    arg_string_path: &T,
+) -> Result<String, String> {
+    match arg_path_buf.into_os_string().into_string() {
+        Ok(string_result) => Ok(string_result),
+        Err(os_string) => Err([
+            "Error: symlink target is not valid UTF-8.".to_string(),
+            format!("os_string = {:?}", os_string,),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n")),
+    }
+}
+//
+// Tests
+//
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::symlink;
+
+    #[test]
+    fn test_symlink_chain() {
+        let string_dir = crate::get_dir_temp_for_tests("symlink_chain");
+        let string_dir_canonical = crate::get_absolute_path_or_error(&string_dir).unwrap();
+        std::fs::write(format!("{}/real.txt", string_dir,), "").unwrap();
+        symlink("real.txt", format!("{}/b", string_dir,)).unwrap();
+        symlink(
+            format!("{}/b", string_dir_canonical,),
+            format!("{}/a", string_dir,),
+        )
+        .unwrap();
+        symlink("missing.txt", format!("{}/broken", string_dir,)).unwrap();
+        symlink("loop_b", format!("{}/loop_a", string_dir,)).unwrap();
+        symlink("loop_a", format!("{}/loop_b", string_dir,)).unwrap();
+        let string_path_a = format!("{}/a", string_dir,);
+        assert!(is_symlink(&string_path_a));
+        assert!(!is_symlink(&format!("{}/real.txt", string_dir,)));
+        assert_eq!(
+            get_symlink_target(&format!("{}/b", string_dir,)).unwrap(),
+            "real.txt"
+        );
+        assert!(get_symlink_target(&format!("{}/real.txt", string_dir,)).is_err());
+        assert_eq!(
+            get_symlink_chain(&string_path_a).unwrap(),
+            vec![
+                string_path_a.clone(),
+                format!("{}/b", string_dir_canonical,),
+                format!("{}/real.txt", string_dir_canonical,),
+            ]
+        );
+        assert_eq!(
+            get_symlink_chain(&format!("{}/broken", string_dir,))
+                .unwrap()
+                .last()
+                .unwrap(),
+            &format!("{}/missing.txt", string_dir_canonical,)
+        );
+        assert!(get_symlink_chain(&format!("{}/loop_a", string_dir,)).is_err());
+        // The '..' applies to where s points, not to the directory holding s
+        let string_dir_other = format!("{}_other", string_dir_canonical,);
+        std::fs::create_dir_all(format!("{}/deep", string_dir_other,)).unwrap();
+        std::fs::write(format!("{}/real.txt", string_dir_other,), "").unwrap();
+        symlink(
+            format!("{}/deep", string_dir_other,),
+            format!("{}/s", string_dir,),
+        )
+        .unwrap();
+        symlink("s/../real.txt", format!("{}/dotdot", string_dir,)).unwrap();
+        assert_eq!(
+            get_symlink_chain(&format!("{}/dotdot", string_dir,)).unwrap(),
+            vec![
+                format!("{}/dotdot", string_dir,),
+                format!("{}/real.txt", string_dir_other,),
+            ]
+        );
+        assert!(!is_broken_symlink(&string_path_a));
+        assert!(is_broken_symlink(&format!("{}/loop_a", string_dir,)));
+        let mut vec_of_broken_links = find_broken_symlinks(&string_dir).unwrap();
+        vec_of_broken_links.sort();
+        assert_eq!(
+            vec_of_broken_links,
+            ["broken", "loop_a", "loop_b"]
+                .iter()
+                .map(|item_str| format!("{}/{}", string_dir, item_str,))
+                .collect::<Vec<String>>()
+        );
+    }
+
+    #[test]
+    fn test_create_symlink_relative() {
+        let string_dir = crate::get_dir_temp_for_tests("create_symlink_relative");
+        let string_path_target = format!("{}/releases/v2", string_dir,);
+        std::fs::create_dir_all(&string_path_target).unwrap();
+        std::fs::create_dir_all(format!("{}/links/nested", string_dir,)).unwrap();
+        assert_eq!(
+            create_symlink_relative(&string_path_target, &format!("{}/current", string_dir,))
+                .unwrap(),
+            "releases/v2"
+        );
+        assert_eq!(
+            create_symlink_relative(
+                &string_path_target,
+                &format!("{}/links/nested/v2", string_dir,)
+            )
+            .unwrap(),
+            "../../releases/v2"
+        );
+        assert!(crate::is_dir(&format!("{}/links/nested/v2", string_dir,)));
+        // The target needn't exist yet
+        assert_eq!(
+            create_symlink_relative(
+                &format!("{}/releases/v3", string_dir,),
+                &format!("{}/next", string_dir,)
+            )
+            .unwrap(),
+            "releases/v3"
+        );
+        assert!(is_broken_symlink(&format!("{}/next", string_dir,)));
+        assert!(
+            create_symlink_relative(&string_path_target, &format!("{}/current", string_dir,))
+                .is_err()
+        );
+    }
+}
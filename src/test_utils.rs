@@ -0,0 +1,19 @@
+//! Helpers shared by the library's tests and the easy-paths binary's tests
+//!
+//! The binary can't see the library's #[cfg(test)] items, so it includes this file with #[path].
+//
+// Public - get
+//
+/// Returns a fresh, empty directory under the system temp dir for tests that need to write to disk
+pub(crate) fn get_dir_temp_for_tests(arg_str_name: &str) -> String {
+    let path_buf = std::env::temp_dir().join("easy_paths_tests").join(format!(
+        "{}_{}",
+        arg_str_name,
+        std::process::id(),
+    ));
+    if path_buf.exists() {
+        std::fs::remove_dir_all(&path_buf).unwrap();
+    }
+    std::fs::create_dir_all(&path_buf).unwrap();
+    path_buf.to_str().unwrap().to_string()
+}
@@ -0,0 +1,477 @@
+//! Async versions of the functions that touch the disk, for use on a tokio runtime
+//!
+//! Everything goes through tokio::fs, so no runtime worker blocks on the disk. Results, including
+//! the order of walks and the text of errors, match the sync functions of the same name.
+//!
+//! Walks read directories and metadata up to a fixed number of operations at a time
+//! ( INT_CONCURRENCY_DEFAULT, or the limit passed to get_stream_of_paths_in_dir_and_sub_dirs() ).
+//! The futures and streams are cancellation safe: dropping one stops the work, and dropping a
+//! stream's next() future part-way loses no entries, since the unfinished step is resumed by the
+//! following call.
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::path::{Path, PathBuf};
+//
+// Libraries - downloaded
+//
+use futures_util::stream::{self, Stream, StreamExt};
+//
+// Libraries - local
+//
+use crate::{get_common_prefix, get_path_with_tilde_expanded_if_necessary};
+//
+// Public - constants
+//
+/// How many disk operations a walk has in flight at once, unless told otherwise
+pub const INT_CONCURRENCY_DEFAULT: usize = 16;
+//
+// Public - get - paths
+//
+/// Async version of get_absolute_path()
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let string_absolute_path = easy_paths::tokio::get_absolute_path( &string_path_relative ).await;
+pub async fn get_absolute_path<T: Debug + Display>(arg_string_path: &T) -> String {
+    let string_path = get_path_with_tilde_expanded_if_necessary(&arg_string_path);
+    match ::tokio::fs::canonicalize(PathBuf::from(&string_path)).await {
+        Ok(path_buf_result) => match path_buf_result.to_str() {
+            Some(str_result) => str_result.to_string(),
+            None => string_path,
+        },
+        Err(_err) => string_path,
+    }
+}
+
+/// Async version of get_absolute_path_or_error()
+/// # Arguments
+/// * arg_string_path: string-like
+/// # Examples
+/// let string_absolute_path = match easy_paths::tokio::get_absolute_path_or_error( &string_path_relative ).await {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err ) }
+/// };
+pub async fn get_absolute_path_or_error<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<String, String> {
+    let string_path = get_path_with_tilde_expanded_if_necessary(&arg_string_path);
+    match ::tokio::fs::canonicalize(PathBuf::from(&string_path)).await {
+        Ok(path_buf_result) => match path_buf_result.to_str() {
+            Some(str_result) => Ok(str_result.to_string()),
+            None => Err([
+                "Error: Failed to extract str from PathBuf.".to_string(),
+                format!("arg_string_path = {}", arg_string_path,),
+                format!("path built = {}", string_path,),
+            ]
+            .join("\n")),
+        },
+        Err(err) => Err([
+            "Error: Failed to 'canonicalize' string_path.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+            format!("err = {}", err,),
+            format!("path built = {}", string_path,),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Async version of get_common_path()
+/// # Arguments
+/// * arg_slice_of_strings: slice of string-like paths
+/// # Examples
+/// let result = match easy_paths::tokio::get_common_path( &[ "/A/B/C", "/A/B/D" ] ).await {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err ) }
+/// };
+pub async fn get_common_path<T: Debug + Display>(
+    arg_slice_of_strings: &[T],
+) -> Result<String, String> {
+    let string_prefix = get_common_prefix(arg_slice_of_strings)?;
+    //
+    // Keep getting parent dir until it exists
+    //
+    let mut path_prefix = Path::new(&string_prefix);
+    loop {
+        if ::tokio::fs::metadata(path_prefix).await.is_ok() {
+            break;
+        }
+        path_prefix = match path_prefix.parent() {
+            Some(path_result) => path_result,
+            None => {
+                return Err([
+                    "Error: Attempted to access non-existent parent.".to_string(),
+                    format!("path buf value at failure = {:?}", path_prefix),
+                    format!("arg_slice_of_strings = {:#?}", arg_slice_of_strings,),
+                ]
+                .join("\n"))
+            }
+        }
+    }
+    Ok(path_prefix.to_string_lossy().into_owned())
+}
+
+/// Async version of get_dir_ancestor_that_exists()
+/// # Arguments
+/// * arg_string_path: string-like path
+pub async fn get_dir_ancestor_that_exists<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Option<String> {
+    let string_path = format!("{}", arg_string_path,);
+    for item_path in Path::new(&string_path).ancestors() {
+        if ::tokio::fs::metadata(item_path).await.is_ok() {
+            return item_path.to_str().map(|str_result| str_result.to_string());
+        }
+    }
+    None
+}
+
+/// Async version of get_paths_in_dir()
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let result = match easy_paths::tokio::get_paths_in_dir( &"test" ).await {
+///     Ok( vec_result ) => vec_result,
+///     Err( err ) => panic!( "{}", err, )
+/// };
+pub async fn get_paths_in_dir<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<Vec<String>, String> {
+    let vec_of_path_bufs =
+        match get_vec_of_path_bufs_in_dir(Path::new(&format!("{}", arg_string_path,))).await {
+            Ok(vec_result) => vec_result,
+            Err(err) => {
+                return Err([
+                    "Error: failed to read directory.".to_string(),
+                    format!("err = {}", err,),
+                    format!("arg_string_path_dir = {}", &arg_string_path,),
+                ]
+                .join("\n"))
+            }
+        };
+    let mut vec_to_return = vec![];
+    for item_path_buf in vec_of_path_bufs {
+        match item_path_buf.to_str() {
+            Some(str_result) => vec_to_return.push(str_result.to_string()),
+            None => {
+                return Err([
+                    "Error: failed to extract str from arg_path_buf".to_string(),
+                    format!("item_path_buf = {:?}", &item_path_buf,),
+                ]
+                .join("\n"))
+            }
+        }
+    }
+    Ok(vec_to_return)
+}
+
+/// Async version of get_paths_in_dir_and_sub_dirs()
+/// # Arguments
+/// * arg_string_path: string-like path
+/// # Examples
+/// let result = match easy_paths::tokio::get_paths_in_dir_and_sub_dirs( &"test" ).await {
+///     Ok( vec_result ) => vec_result,
+///     Err( err ) => panic!( "{}", err )
+/// };
+pub async fn get_paths_in_dir_and_sub_dirs<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<Vec<String>, String> {
+    get_stream_of_paths_in_dir_and_sub_dirs(arg_string_path, INT_CONCURRENCY_DEFAULT)
+        .collect::<Vec<Result<String, String>>>()
+        .await
+        .into_iter()
+        .collect()
+}
+
+/// Async version of get_paths_to_only_dirs_in_dir_and_sub_dirs()
+/// # Arguments
+/// * arg_string_path_dir: string-like path
+pub async fn get_paths_to_only_dirs_in_dir_and_sub_dirs<T: Display>(
+    arg_string_path_dir: &T,
+) -> Result<Vec<String>, String> {
+    get_paths_filtered_by_metadata(arg_string_path_dir, |metadata| metadata.is_dir()).await
+}
+
+/// Async version of get_paths_to_only_files_in_dir_and_sub_dirs()
+/// # Arguments
+/// * arg_string_path_dir: string-like path
+pub async fn get_paths_to_only_files_in_dir_and_sub_dirs<T: Display>(
+    arg_string_path_dir: &T,
+) -> Result<Vec<String>, String> {
+    get_paths_filtered_by_metadata(arg_string_path_dir, |metadata| metadata.is_file()).await
+}
+
+/// Returns a stream of the paths get_paths_in_dir_and_sub_dirs() returns, in the same order
+/// Entries arrive as soon as they're found, so a caller can stop early. An error ends the stream
+/// at the point where the sync function would have returned it.
+/// # Arguments
+/// * arg_string_path: string-like path
+/// * arg_int_concurrency: how many disk operations may be in flight at once; 0 is treated as 1
+/// # Examples
+/// let mut stream = std::pin::pin!( easy_paths::tokio::get_stream_of_paths_in_dir_and_sub_dirs( &"test", 8 ) );
+/// while let Some( result ) = stream.next().await {
+///     let string_path = result?;
+/// }
+pub fn get_stream_of_paths_in_dir_and_sub_dirs<T: Display>(
+    arg_string_path: &T,
+    arg_int_concurrency: usize,
+) -> impl Stream<Item = Result<String, String>> + Send + 'static {
+    let walk_state = WalkState {
+        string_path_root: format!("{}", arg_string_path,),
+        stack_of_walk_entries: vec![],
+        int_concurrency: arg_int_concurrency.max(1),
+        bool_is_started: false,
+        bool_is_done: false,
+    };
+    stream::unfold(walk_state, |mut walk_state| async move {
+        if walk_state.bool_is_done {
+            return None;
+        }
+        if !walk_state.bool_is_started {
+            walk_state.bool_is_started = true;
+            let string_path_root = walk_state.string_path_root.clone();
+            if let Err(err) = walk_state.push_entries_in_dir(&string_path_root).await {
+                walk_state.bool_is_done = true;
+                return Some((Err(err), walk_state));
+            }
+        }
+        let walk_entry = walk_state.stack_of_walk_entries.pop()?;
+        let result = match walk_entry.result_is_dir {
+            Ok(true) => walk_state
+                .push_entries_in_dir(&walk_entry.string_path)
+                .await
+                .map(|_| walk_entry.string_path),
+            Ok(false) => Ok(walk_entry.string_path),
+            Err(err) => Err(err),
+        };
+        walk_state.bool_is_done = result.is_err();
+        Some((result, walk_state))
+    })
+}
+//
+// Public - ( logic ) are / is
+//
+/// Async version of is_dir()
+/// # Arguments
+/// * arg_string_path: string-like path
+pub async fn is_dir<T: Debug + Display>(arg_string_path: &T) -> bool {
+    ::tokio::fs::metadata(format!("{}", arg_string_path,))
+        .await
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false)
+}
+
+/// Async version of is_existing_path()
+/// # Arguments
+/// * arg_string_path: string-like path
+pub async fn is_existing_path<T: Debug + Display>(arg_string_path: &T) -> bool {
+    ::tokio::fs::metadata(format!("{}", arg_string_path,))
+        .await
+        .is_ok()
+}
+
+/// Async version of is_file()
+/// # Arguments
+/// * arg_string_path: string-like path
+pub async fn is_file<T: Debug + Display>(arg_string_path: &T) -> bool {
+    ::tokio::fs::metadata(format!("{}", arg_string_path,))
+        .await
+        .map(|metadata| metadata.is_file())
+        .unwrap_or(false)
+}
+//
+// Public - raise error
+//
+/// Async version of raise_error_if_path_does_not_exist()
+/// # Arguments
+/// * arg_string_path: string-like
+pub async fn raise_error_if_path_does_not_exist<T: Debug + Display>(
+    arg_string_path: &T,
+) -> Result<(), String> {
+    if !is_existing_path(arg_string_path).await {
+        return Err([
+            "Error: path does not exist.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+            match get_dir_ancestor_that_exists(arg_string_path).await {
+                Some(string_result) => {
+                    format!("ancestor that actually exists = {}", string_result,)
+                }
+                None => "No existing ancestor exists.".to_string(),
+            },
+        ]
+        .join("\n"));
+    }
+    Ok(())
+}
+//
+// Private
+//
+/// A path waiting on the walk's stack, with its metadata already looked up
+struct WalkEntry {
+    string_path: String,
+    result_is_dir: Result<bool, String>,
+}
+
+struct WalkState {
+    string_path_root: String,
+    stack_of_walk_entries: Vec<WalkEntry>,
+    int_concurrency: usize,
+    bool_is_started: bool,
+    bool_is_done: bool,
+}
+
+impl WalkState {
+    /// Lists a directory and looks up its entries' metadata concurrently, then pushes them in listing order
+    /// Errors from the lookups are kept on the entries, so they surface when the sync walk would reach them.
+    async fn push_entries_in_dir(&mut self, arg_str_path_dir: &str) -> Result<(), String> {
+        let vec_of_strings = get_paths_in_dir(&arg_str_path_dir).await?;
+        let string_path_root = self.string_path_root.clone();
+        let vec_of_walk_entries = stream::iter(vec_of_strings)
+            .map(|item_string_path| {
+                let string_path_root = string_path_root.clone();
+                async move {
+                    let result_is_dir = match ::tokio::fs::metadata(&item_string_path).await {
+                        Ok(metadata) => Ok(metadata.is_dir()),
+                        Err(err) => Err([
+                            "Error: failed to get meta data from arg_string_path.".to_string(),
+                            format!("err = {:?}", err,),
+                            format!("item_string_path_dir = {}", item_string_path,),
+                            format!("arg_string_path = {}", string_path_root,),
+                        ]
+                        .join("\n")),
+                    };
+                    WalkEntry {
+                        string_path: item_string_path,
+                        result_is_dir,
+                    }
+                }
+            })
+            .buffered(self.int_concurrency)
+            .collect::<Vec<WalkEntry>>()
+            .await;
+        self.stack_of_walk_entries.extend(vec_of_walk_entries);
+        Ok(())
+    }
+}
+
+/// Walks the directory, then keeps the paths whose metadata passes arg_closure_filter
+async fn get_paths_filtered_by_metadata<T: Display>(
+    arg_string_path_dir: &T,
+    arg_closure_filter: fn(&std::fs::Metadata) -> bool,
+) -> Result<Vec<String>, String> {
+    let vec_of_strings =
+        get_paths_in_dir_and_sub_dirs(&format!("{}", arg_string_path_dir,)).await?;
+    Ok(stream::iter(vec_of_strings)
+        .map(|item_string_path| async move {
+            let bool_keep = ::tokio::fs::metadata(&item_string_path)
+                .await
+                .map(|metadata| arg_closure_filter(&metadata))
+                .unwrap_or(false);
+            (item_string_path, bool_keep)
+        })
+        .buffered(INT_CONCURRENCY_DEFAULT)
+        .filter_map(
+            |(item_string_path, bool_keep)| async move { bool_keep.then_some(item_string_path) },
+        )
+        .collect::<Vec<String>>()
+        .await)
+}
+
+async fn get_vec_of_path_bufs_in_dir(arg_path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut read_dir = ::tokio::fs::read_dir(arg_path).await?;
+    let mut vec_to_return = vec![];
+    while let Some(dir_entry) = read_dir.next_entry().await? {
+        vec_to_return.push(dir_entry.path());
+    }
+    Ok(vec_to_return)
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[::tokio::test]
+    async fn test_walks_match_sync() {
+        let string_dir = crate::get_dir_temp_for_tests("tokio_walks_match_sync");
+        for item_str_path in ["a/b/c.txt", "a/d.txt", "e/f/g/h.txt", "i.txt"] {
+            let path_buf = Path::new(&string_dir).join(item_str_path);
+            std::fs::create_dir_all(path_buf.parent().unwrap()).unwrap();
+            std::fs::write(&path_buf, "").unwrap();
+        }
+        assert_eq!(
+            get_paths_in_dir_and_sub_dirs(&string_dir).await,
+            crate::get_paths_in_dir_and_sub_dirs(&string_dir)
+        );
+        assert_eq!(
+            get_paths_to_only_dirs_in_dir_and_sub_dirs(&string_dir).await,
+            crate::get_paths_to_only_dirs_in_dir_and_sub_dirs(&string_dir)
+        );
+        assert_eq!(
+            get_paths_to_only_files_in_dir_and_sub_dirs(&string_dir).await,
+            crate::get_paths_to_only_files_in_dir_and_sub_dirs(&string_dir)
+        );
+        let vec_of_results = get_stream_of_paths_in_dir_and_sub_dirs(&string_dir, 1)
+            .collect::<Vec<Result<String, String>>>()
+            .await;
+        assert_eq!(
+            vec_of_results
+                .into_iter()
+                .collect::<Result<Vec<String>, String>>(),
+            crate::get_paths_in_dir_and_sub_dirs(&string_dir)
+        );
+        let string_path_missing = format!("{}/missing", string_dir,);
+        assert_eq!(
+            get_paths_in_dir_and_sub_dirs(&string_path_missing).await,
+            crate::get_paths_in_dir_and_sub_dirs(&string_path_missing)
+        );
+        // Stopping early leaves nothing running
+        let vec_of_results = get_stream_of_paths_in_dir_and_sub_dirs(&string_dir, 4)
+            .take(2)
+            .collect::<Vec<Result<String, String>>>()
+            .await;
+        assert_eq!(vec_of_results.len(), 2);
+    }
+
+    #[::tokio::test]
+    async fn test_paths_match_sync() {
+        let string_dir = crate::get_dir_temp_for_tests("tokio_paths_match_sync");
+        let string_path_missing = format!("{}/x/y", string_dir,);
+        for item_string_path in [string_dir.clone(), string_path_missing.clone()] {
+            assert_eq!(
+                get_absolute_path_or_error(&item_string_path).await,
+                crate::get_absolute_path_or_error(&item_string_path)
+            );
+            assert_eq!(
+                get_absolute_path(&item_string_path).await,
+                crate::get_absolute_path(&item_string_path)
+            );
+            assert_eq!(
+                raise_error_if_path_does_not_exist(&item_string_path).await,
+                crate::raise_error_if_path_does_not_exist(&item_string_path)
+            );
+            assert_eq!(
+                is_dir(&item_string_path).await,
+                crate::is_dir(&item_string_path)
+            );
+            assert_eq!(
+                is_file(&item_string_path).await,
+                crate::is_file(&item_string_path)
+            );
+        }
+        let slice_of_strings = [
+            format!("{}/x/y", string_dir,),
+            format!("{}/x/z", string_dir,),
+        ];
+        assert_eq!(
+            get_common_path(&slice_of_strings).await,
+            crate::get_common_path(&slice_of_strings)
+        );
+        assert_eq!(
+            get_common_path(&slice_of_strings).await.unwrap(),
+            string_dir
+        );
+    }
+}
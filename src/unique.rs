@@ -0,0 +1,272 @@
+//
+// Libraries - native
+//
+use std::collections::hash_map::RandomState;
+use std::fmt::{Debug, Display};
+use std::fs::{File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+//
+// Libraries - local
+//
+use crate::extension::{get_stem_and_full_extension, SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT};
+use crate::get_base_name;
+//
+// Public - enums
+//
+/// How get_path_unique() changes a name that's taken
+/// The suffix goes between the stem and the extension, and compound extensions like '.tar.gz' stay whole.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UniquePathStyle {
+    /// "report (1).pdf", "report (2).pdf", ...
+    Parenthesized,
+    /// "report_1.pdf", "report_2.pdf", ...
+    Underscore,
+    /// "report_20261019-153012.pdf" in UTC, then "report_20261019-153012_1.pdf", ... within the same second
+    Timestamp,
+    /// "report_3f9a0c1e.pdf", with 8 new random hex digits on each attempt
+    Random,
+}
+//
+// Public - get - unique paths
+//
+/// Returns arg_string_path if nothing exists there, and otherwise the first free variant in arg_unique_path_style
+/// Broken symlinks count as taken. Another process can still claim the returned path before it's
+/// used; get_path_unique_created() closes that gap.
+/// In case of a failure ( ie the path has no file name ), this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like path the caller would like to use
+/// * arg_unique_path_style: UniquePathStyle
+/// # Examples
+/// let string_path = match get_path_unique( &"exports/report.tar.gz", UniquePathStyle::Parenthesized ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // string_path = "exports/report (1).tar.gz" if "exports/report.tar.gz" exists
+pub fn get_path_unique<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_unique_path_style: UniquePathStyle,
+) -> Result<String, String> {
+    let (string_path, _) =
+        get_path_unique_with_closure(arg_string_path, arg_unique_path_style, |str_path| {
+            match std::fs::symlink_metadata(str_path) {
+                Ok(_) => Ok(None),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Some(())),
+                Err(err) => Err(err),
+            }
+        })?;
+    Ok(string_path)
+}
+
+/// Same as get_path_unique(), except the file is created with create_new, so no other process can take
+/// the name between checking and creating it
+/// Returns the path and the new, empty file opened for writing.
+/// # Arguments
+/// * arg_string_path: string-like path the caller would like to use
+/// * arg_unique_path_style: UniquePathStyle
+/// # Examples
+/// let ( string_path, file ) = match get_path_unique_created( &"report.pdf", UniquePathStyle::Underscore ) {
+///     Ok( tuple_result ) => { tuple_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_path_unique_created<T: Debug + Display>(
+    arg_string_path: &T,
+    arg_unique_path_style: UniquePathStyle,
+) -> Result<(String, File), String> {
+    get_path_unique_with_closure(arg_string_path, arg_unique_path_style, |str_path| {
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(str_path)
+        {
+            Ok(file) => Ok(Some(file)),
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => Ok(None),
+            Err(err) => Err(err),
+        }
+    })
+}
+//
+// Private
+//
+/// Tries each candidate with arg_closure_try until it returns Some, giving up after INT_ATTEMPTS_MAX
+/// The closure returns None when the candidate is taken.
+fn get_path_unique_with_closure<T: Debug + Display, R, F>(
+    arg_string_path: &T,
+    arg_unique_path_style: UniquePathStyle,
+    mut arg_closure_try: F,
+) -> Result<(String, R), String>
+where
+    F: FnMut(&str) -> std::io::Result<Option<R>>,
+{
+    const INT_ATTEMPTS_MAX: u32 = 10_000;
+    let string_path = format!("{}", arg_string_path,);
+    let string_name = match get_base_name(&string_path) {
+        Some(string_name) => string_name,
+        None => {
+            return Err([
+                "Error: path has no file name.".to_string(),
+                format!("arg_string_path = {}", arg_string_path,),
+            ]
+            .join("\n"))
+        }
+    };
+    let (str_stem, str_extension) =
+        get_stem_and_full_extension(&string_name, SLICE_OF_EXTENSIONS_COMPOUND_DEFAULT);
+    let string_timestamp = get_string_timestamp_utc();
+    for item_int_attempt in 0..INT_ATTEMPTS_MAX {
+        let string_path_candidate = if item_int_attempt == 0 {
+            string_path.clone()
+        } else {
+            let string_suffix = match arg_unique_path_style {
+                UniquePathStyle::Parenthesized => format!(" ({})", item_int_attempt,),
+                UniquePathStyle::Underscore => format!("_{}", item_int_attempt,),
+                UniquePathStyle::Timestamp if item_int_attempt == 1 => {
+                    format!("_{}", string_timestamp,)
+                }
+                UniquePathStyle::Timestamp => {
+                    format!("_{}_{}", string_timestamp, item_int_attempt - 1,)
+                }
+                UniquePathStyle::Random => format!("_{:08x}", get_int_random() as u32,),
+            };
+            Path::new(&string_path)
+                .with_file_name(format!("{}{}{}", str_stem, string_suffix, str_extension,))
+                .to_string_lossy()
+                .into_owned()
+        };
+        match arg_closure_try(&string_path_candidate) {
+            Ok(Some(result)) => return Ok((string_path_candidate, result)),
+            Ok(None) => {}
+            Err(err) => {
+                return Err([
+                    "Error: failed to check whether path is free.".to_string(),
+                    format!("err = {}", err,),
+                    format!("string_path_candidate = {}", string_path_candidate,),
+                ]
+                .join("\n"))
+            }
+        }
+    }
+    Err([
+        format!("Error: no free path after {} attempts.", INT_ATTEMPTS_MAX,),
+        format!("arg_string_path = {}", arg_string_path,),
+    ]
+    .join("\n"))
+}
+
+fn get_int_random() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default(),
+    );
+    hasher.finish()
+}
+
+/// Returns the current UTC time as "YYYYMMDD-HHMMSS"
+fn get_string_timestamp_utc() -> String {
+    let int_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default() as i64;
+    let int_days = int_secs.div_euclid(86_400);
+    let int_secs_of_day = int_secs.rem_euclid(86_400);
+    // Civil date from days since 1970-01-01, from Howard Hinnant's date algorithms
+    let int_days_shifted = int_days + 719_468;
+    let int_era = int_days_shifted.div_euclid(146_097);
+    let int_day_of_era = int_days_shifted.rem_euclid(146_097);
+    let int_year_of_era = (int_day_of_era - int_day_of_era / 1_460 + int_day_of_era / 36_524
+        - int_day_of_era / 146_096)
+        / 365;
+    let int_day_of_year =
+        int_day_of_era - (365 * int_year_of_era + int_year_of_era / 4 - int_year_of_era / 100);
+    let int_month_shifted = (5 * int_day_of_year + 2) / 153;
+    let int_day = int_day_of_year - (153 * int_month_shifted + 2) / 5 + 1;
+    let int_month = if int_month_shifted < 10 {
+        int_month_shifted + 3
+    } else {
+        int_month_shifted - 9
+    };
+    let int_year = int_year_of_era + int_era * 400 + i64::from(int_month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        int_year,
+        int_month,
+        int_day,
+        int_secs_of_day / 3_600,
+        int_secs_of_day % 3_600 / 60,
+        int_secs_of_day % 60,
+    )
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+
+    #[test]
+    fn test_get_path_unique() {
+        let string_path_dir = get_dir_temp_for_tests("get_path_unique");
+        let string_path = format!("{}/report.tar.gz", string_path_dir,);
+        assert_eq!(
+            get_path_unique(&string_path, UniquePathStyle::Parenthesized),
+            Ok(string_path.clone())
+        );
+        for item_str_name in ["report.tar.gz", "report (1).tar.gz", "report_1.tar.gz"] {
+            std::fs::write(format!("{}/{}", string_path_dir, item_str_name,), "").unwrap();
+        }
+        assert_eq!(
+            get_path_unique(&string_path, UniquePathStyle::Parenthesized),
+            Ok(format!("{}/report (2).tar.gz", string_path_dir,))
+        );
+        assert_eq!(
+            get_path_unique(&string_path, UniquePathStyle::Underscore),
+            Ok(format!("{}/report_2.tar.gz", string_path_dir,))
+        );
+        let string_path_timestamp =
+            get_path_unique(&string_path, UniquePathStyle::Timestamp).unwrap();
+        assert!(string_path_timestamp.ends_with(".tar.gz"));
+        assert_eq!(
+            string_path_timestamp.len(),
+            string_path.len() + "_YYYYMMDD-HHMMSS".len()
+        );
+        let string_path_random = get_path_unique(&string_path, UniquePathStyle::Random).unwrap();
+        assert_eq!(
+            string_path_random.len(),
+            string_path.len() + "_0123abcd".len()
+        );
+        assert!(get_path_unique(&"/", UniquePathStyle::Underscore).is_err());
+        let string_path_accented = format!("{}/étar.gz", string_path_dir,);
+        std::fs::write(&string_path_accented, "").unwrap();
+        assert_eq!(
+            get_path_unique(&string_path_accented, UniquePathStyle::Underscore),
+            Ok(format!("{}/étar_1.gz", string_path_dir,))
+        );
+    }
+
+    #[test]
+    fn test_get_path_unique_created() {
+        let string_path_dir = get_dir_temp_for_tests("get_path_unique_created");
+        let string_path = format!("{}/.bashrc", string_path_dir,);
+        let vec_of_paths = (0..3)
+            .map(|_| {
+                get_path_unique_created(&string_path, UniquePathStyle::Parenthesized)
+                    .unwrap()
+                    .0
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(
+            vec_of_paths,
+            vec![
+                string_path.clone(),
+                format!("{}/.bashrc (1)", string_path_dir,),
+                format!("{}/.bashrc (2)", string_path_dir,),
+            ]
+        );
+        assert!(Path::new(&vec_of_paths[2]).is_file());
+    }
+}
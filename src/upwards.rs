@@ -0,0 +1,219 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::path::Path;
+//
+// Libraries - local
+//
+use crate::{
+    get_absolute_path, get_path_with_tilde_expanded_if_necessary,
+    raise_error_if_path_does_not_exist,
+};
+//
+// Public - enums
+//
+/// Where an upward search stops
+/// The boundary directory itself is still searched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UpwardsStop {
+    /// Keep going up to the filesystem root
+    Root,
+    /// Stop at the home directory; a start outside the home directory goes up to the root
+    HomeDir,
+    /// Stop at the first directory containing '.git'
+    GitRoot,
+    /// Stop before crossing onto a different device, ie a mount point. On non-unix platforms this is Root
+    FileSystem,
+}
+//
+// Public - get - upwards
+//
+/// Returns the nearest path named one of arg_slice_of_names, searching arg_string_path_start and then
+/// each of its parents
+/// Within a directory, names are checked in the order given. If arg_string_path_start is a file, the
+/// search starts in its directory.
+/// Returns None if nothing matches, and an error if arg_string_path_start doesn't exist
+/// # Arguments
+/// * arg_string_path_start: string-like path to start from
+/// * arg_slice_of_names: slice of string-like file or directory names, ie [ ".editorconfig" ]
+/// * arg_upwards_stop: UpwardsStop
+/// # Examples
+/// let option_string_path = match find_file_upwards( &"src/bin", &[ "Cargo.toml" ], UpwardsStop::GitRoot ) {
+///     Ok( option_result ) => { option_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // option_string_path = Some( "/<project dir>/Cargo.toml" )
+pub fn find_file_upwards<T1: Debug + Display, T2: Display>(
+    arg_string_path_start: &T1,
+    arg_slice_of_names: &[T2],
+    arg_upwards_stop: UpwardsStop,
+) -> Result<Option<String>, String> {
+    let mut option_to_return = None;
+    walk_upwards(
+        arg_string_path_start,
+        arg_slice_of_names,
+        arg_upwards_stop,
+        |string_path| {
+            option_to_return = Some(string_path);
+            false
+        },
+    )?;
+    Ok(option_to_return)
+}
+
+/// Returns every path named one of arg_slice_of_names from arg_string_path_start up to the stop, nearest first
+/// This suits layered config lookup, where nearer files override further ones ( ie .editorconfig ).
+/// # Arguments
+/// * arg_string_path_start: string-like path to start from
+/// * arg_slice_of_names: slice of string-like file or directory names
+/// * arg_upwards_stop: UpwardsStop
+/// # Examples
+/// let vec_of_paths = match find_all_files_upwards( &"src", &[ ".editorconfig" ], UpwardsStop::HomeDir ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn find_all_files_upwards<T1: Debug + Display, T2: Display>(
+    arg_string_path_start: &T1,
+    arg_slice_of_names: &[T2],
+    arg_upwards_stop: UpwardsStop,
+) -> Result<Vec<String>, String> {
+    let mut vec_to_return = vec![];
+    walk_upwards(
+        arg_string_path_start,
+        arg_slice_of_names,
+        arg_upwards_stop,
+        |string_path| {
+            vec_to_return.push(string_path);
+            true
+        },
+    )?;
+    Ok(vec_to_return)
+}
+//
+// Private
+//
+/// Calls arg_closure_on_match with each match, nearest first, until it returns false
+fn walk_upwards<T1: Debug + Display, T2: Display, F: FnMut(String) -> bool>(
+    arg_string_path_start: &T1,
+    arg_slice_of_names: &[T2],
+    arg_upwards_stop: UpwardsStop,
+    mut arg_closure_on_match: F,
+) -> Result<(), String> {
+    raise_error_if_path_does_not_exist(arg_string_path_start)?;
+    let string_path_start = get_absolute_path(arg_string_path_start);
+    let path_start = Path::new(&string_path_start);
+    let path_dir_start = if path_start.is_dir() {
+        path_start
+    } else {
+        path_start.parent().unwrap_or(path_start)
+    };
+    let vec_of_names = arg_slice_of_names
+        .iter()
+        .map(|item| format!("{}", item,))
+        .collect::<Vec<String>>();
+    let string_path_dir_home = get_path_with_tilde_expanded_if_necessary(&"~");
+    let option_int_device_start = get_option_int_device(path_dir_start);
+    for item_path_dir in path_dir_start.ancestors() {
+        if arg_upwards_stop == UpwardsStop::FileSystem
+            && get_option_int_device(item_path_dir) != option_int_device_start
+        {
+            break;
+        }
+        for item_string_name in &vec_of_names {
+            let path_buf_candidate = item_path_dir.join(item_string_name);
+            // symlink_metadata() so a broken symlink still counts as a match
+            if std::fs::symlink_metadata(&path_buf_candidate).is_err() {
+                continue;
+            }
+            if !arg_closure_on_match(path_buf_candidate.to_string_lossy().into_owned()) {
+                return Ok(());
+            }
+        }
+        let bool_is_stop = match arg_upwards_stop {
+            UpwardsStop::HomeDir => item_path_dir == Path::new(&string_path_dir_home),
+            UpwardsStop::GitRoot => item_path_dir.join(".git").exists(),
+            UpwardsStop::Root | UpwardsStop::FileSystem => false,
+        };
+        if bool_is_stop {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn get_option_int_device(arg_path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(arg_path)
+        .ok()
+        .map(|metadata| metadata.dev())
+}
+
+#[cfg(not(unix))]
+fn get_option_int_device(_arg_path: &Path) -> Option<u64> {
+    None
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+
+    #[test]
+    fn test_find_file_upwards() {
+        let string_path_dir = get_absolute_path(&get_dir_temp_for_tests("find_file_upwards"));
+        std::fs::create_dir_all(format!("{}/repo/.git", string_path_dir,)).unwrap();
+        std::fs::create_dir_all(format!("{}/repo/A/B", string_path_dir,)).unwrap();
+        for item_str_path in [
+            "conf.toml",
+            "repo/conf.toml",
+            "repo/A/conf.toml",
+            "repo/A/B/file.txt",
+        ] {
+            std::fs::write(format!("{}/{}", string_path_dir, item_str_path,), "").unwrap();
+        }
+        assert_eq!(
+            find_file_upwards(
+                &format!("{}/repo/A/B/file.txt", string_path_dir,),
+                &["conf.toml"],
+                UpwardsStop::Root,
+            ),
+            Ok(Some(format!("{}/repo/A/conf.toml", string_path_dir,)))
+        );
+        assert_eq!(
+            find_file_upwards(
+                &format!("{}/repo/A/B", string_path_dir,),
+                &["missing"],
+                UpwardsStop::Root
+            ),
+            Ok(None)
+        );
+        assert_eq!(
+            find_all_files_upwards(
+                &format!("{}/repo/A/B", string_path_dir,),
+                &["conf.toml"],
+                UpwardsStop::GitRoot
+            ),
+            Ok(vec![
+                format!("{}/repo/A/conf.toml", string_path_dir,),
+                format!("{}/repo/conf.toml", string_path_dir,),
+            ])
+        );
+        let vec_of_paths = find_all_files_upwards(
+            &format!("{}/repo/A/B", string_path_dir,),
+            &["conf.toml"],
+            UpwardsStop::Root,
+        )
+        .unwrap();
+        assert_eq!(vec_of_paths[2], format!("{}/conf.toml", string_path_dir,));
+        assert!(find_file_upwards(
+            &format!("{}/missing", string_path_dir,),
+            &["conf.toml"],
+            UpwardsStop::Root
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,704 @@
+//! Windows-style path handling that behaves the same on every host platform
+//!
+//! std::path::Path follows the rules of whatever platform the code was compiled for, so on mac and
+//! linux a string like 'C:\Users\me' is a single relative component. The functions here parse
+//! Windows paths by hand instead, so paths pulled from config files or logs can be processed anywhere.
+//!
+//! Supported forms:
+//! * drive letters: 'C:\a\b', 'C:a' ( drive-relative ), '\a' ( root of the current drive )
+//! * UNC shares: '\\server\share\a'
+//! * verbatim prefixes: '\\?\C:\a', '\\?\UNC\server\share\a', '\\?\Volume{...}\a'
+//! * device namespace: '\\.\COM1'
+//!
+//! Both '\' and '/' are accepted as separators, except after a verbatim prefix where only '\' is.
+//! Output always uses '\'. Comparisons ignore letter case.
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display, Formatter};
+//
+// Public - structs
+//
+/// The part of a Windows path that comes before the root separator
+#[derive(Clone, Debug)]
+pub enum WindowsPrefix {
+    /// No prefix, ie 'a\b' or '\a\b'
+    None,
+    /// 'C:'
+    Disk(char),
+    /// '\\server\share'
+    Unc(String, String),
+    /// '\\?\C:'
+    VerbatimDisk(char),
+    /// '\\?\UNC\server\share'
+    VerbatimUnc(String, String),
+    /// '\\?\<name>', ie '\\?\Volume{...}'
+    Verbatim(String),
+    /// '\\.\<device>', ie '\\.\COM1'
+    DeviceNs(String),
+}
+
+/// A parsed Windows path
+/// Equality ignores letter case and treats '\\?\C:' and 'C:' ( and the two UNC forms ) as the same prefix.
+#[derive(Clone, Debug)]
+pub struct WindowsPath {
+    prefix: WindowsPrefix,
+    bool_has_root: bool,
+    vec_of_components: Vec<String>,
+}
+
+impl WindowsPrefix {
+    /// Returns true if the prefix is one of the '\\?\' forms
+    pub fn is_verbatim(&self) -> bool {
+        matches!(
+            self,
+            WindowsPrefix::VerbatimDisk(_)
+                | WindowsPrefix::VerbatimUnc(_, _)
+                | WindowsPrefix::Verbatim(_)
+        )
+    }
+
+    /// Returns the prefix with verbatim forms mapped onto their plain equivalents, lowercased
+    fn get_key_for_comparison(&self) -> (u8, String) {
+        match self {
+            WindowsPrefix::None => (0, String::new()),
+            WindowsPrefix::Disk(char_drive) | WindowsPrefix::VerbatimDisk(char_drive) => {
+                (1, char_drive.to_lowercase().to_string())
+            }
+            WindowsPrefix::Unc(string_server, string_share)
+            | WindowsPrefix::VerbatimUnc(string_server, string_share) => (
+                2,
+                format!("{}\\{}", string_server, string_share,).to_lowercase(),
+            ),
+            WindowsPrefix::Verbatim(string_name) => (3, string_name.to_lowercase()),
+            WindowsPrefix::DeviceNs(string_name) => (4, string_name.to_lowercase()),
+        }
+    }
+}
+
+impl Display for WindowsPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowsPrefix::None => Ok(()),
+            WindowsPrefix::Disk(char_drive) => write!(f, "{}:", char_drive,),
+            WindowsPrefix::Unc(string_server, string_share) => {
+                write!(f, "\\\\{}\\{}", string_server, string_share,)
+            }
+            WindowsPrefix::VerbatimDisk(char_drive) => write!(f, "\\\\?\\{}:", char_drive,),
+            WindowsPrefix::VerbatimUnc(string_server, string_share) => {
+                write!(f, "\\\\?\\UNC\\{}\\{}", string_server, string_share,)
+            }
+            WindowsPrefix::Verbatim(string_name) => write!(f, "\\\\?\\{}", string_name,),
+            WindowsPrefix::DeviceNs(string_name) => write!(f, "\\\\.\\{}", string_name,),
+        }
+    }
+}
+
+impl PartialEq for WindowsPrefix {
+    fn eq(&self, arg_other: &Self) -> bool {
+        self.get_key_for_comparison() == arg_other.get_key_for_comparison()
+    }
+}
+
+impl Eq for WindowsPrefix {}
+
+impl WindowsPath {
+    /// Parses a string-like value as a Windows path
+    /// # Arguments
+    /// * arg_string_path: string-like
+    /// # Examples
+    /// let windows_path = WindowsPath::new( &"C:\\Users\\me\\file.txt" );
+    pub fn new<T: Display>(arg_string_path: &T) -> Self {
+        let string_path = format!("{}", arg_string_path,);
+        let (prefix, str_remainder) = get_prefix_and_remainder(&string_path);
+        let bool_verbatim = prefix.is_verbatim();
+        let is_separator = |arg_char: char| arg_char == '\\' || (!bool_verbatim && arg_char == '/');
+        let bool_has_root = match prefix {
+            //
+            // Reminder: UNC and verbatim paths are always rooted, like std's has_root()
+            //
+            WindowsPrefix::None | WindowsPrefix::Disk(_) => str_remainder.starts_with(is_separator),
+            _ => true,
+        };
+        let vec_of_components = str_remainder
+            .split(is_separator)
+            .filter(|item_str| !item_str.is_empty() && (bool_verbatim || *item_str != "."))
+            .map(|item_str| item_str.to_string())
+            .collect::<Vec<String>>();
+        WindowsPath {
+            prefix,
+            bool_has_root,
+            vec_of_components,
+        }
+    }
+
+    /// Returns the prefix ( drive, UNC share, verbatim, device )
+    pub fn prefix(&self) -> &WindowsPrefix {
+        &self.prefix
+    }
+
+    /// Returns true if the path has a root separator after its prefix
+    pub fn has_root(&self) -> bool {
+        self.bool_has_root
+    }
+
+    /// Returns the components after the prefix and root
+    pub fn components(&self) -> &[String] {
+        &self.vec_of_components
+    }
+
+    /// Returns true if the path doesn't depend on the current drive or directory
+    pub fn is_absolute(&self) -> bool {
+        match self.prefix {
+            WindowsPrefix::None => false,
+            WindowsPrefix::Disk(_) => self.bool_has_root,
+            _ => true,
+        }
+    }
+
+    /// Returns the final component, unless it's '..'
+    pub fn base_name(&self) -> Option<String> {
+        match self.vec_of_components.last() {
+            Some(string_last) if string_last != ".." => Some(string_last.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns the path without its final component
+    /// Returns None if there is no component to remove
+    pub fn dir_name(&self) -> Option<WindowsPath> {
+        if self.vec_of_components.is_empty() {
+            return None;
+        }
+        let mut windows_path_to_return = self.clone();
+        windows_path_to_return.vec_of_components.pop();
+        Some(windows_path_to_return)
+    }
+
+    /// Returns the text after the final period of the base name
+    /// Follows std's rules: '.bashrc' has no extension, 'file.' has an empty one.
+    pub fn extension(&self) -> Option<String> {
+        let string_base_name = self.base_name()?;
+        match string_base_name.rfind('.') {
+            Some(0) | None => None,
+            Some(int_index) => Some(string_base_name[int_index + 1..].to_string()),
+        }
+    }
+
+    /// Returns a new path with arg_windows_path appended, using the same rules as PathBuf::push() on Windows
+    /// * If arg_windows_path has a prefix, it replaces this path entirely
+    /// * If it only has a root ( '\a' ), it keeps this path's prefix and replaces everything else
+    /// * Otherwise its components are appended
+    pub fn join(&self, arg_windows_path: &WindowsPath) -> WindowsPath {
+        if !matches!(arg_windows_path.prefix, WindowsPrefix::None) {
+            return arg_windows_path.clone();
+        }
+        if arg_windows_path.bool_has_root {
+            return WindowsPath {
+                prefix: self.prefix.clone(),
+                bool_has_root: true,
+                vec_of_components: arg_windows_path.vec_of_components.clone(),
+            };
+        }
+        let mut windows_path_to_return = self.clone();
+        windows_path_to_return
+            .vec_of_components
+            .extend(arg_windows_path.vec_of_components.iter().cloned());
+        windows_path_to_return
+    }
+
+    /// Returns the remainder of this path after arg_windows_path_root_prefix is removed
+    /// Components are compared without regard to letter case.
+    /// Returns None if this path doesn't start with arg_windows_path_root_prefix
+    pub fn strip_prefix(&self, arg_windows_path_root_prefix: &WindowsPath) -> Option<WindowsPath> {
+        if self.prefix != arg_windows_path_root_prefix.prefix
+            || self.bool_has_root != arg_windows_path_root_prefix.bool_has_root
+            || self.vec_of_components.len() < arg_windows_path_root_prefix.vec_of_components.len()
+        {
+            return None;
+        }
+        let bool_components_match = self
+            .vec_of_components
+            .iter()
+            .zip(arg_windows_path_root_prefix.vec_of_components.iter())
+            .all(|(item_left, item_right)| is_same_component(item_left, item_right));
+        if !bool_components_match {
+            return None;
+        }
+        Some(WindowsPath {
+            prefix: WindowsPrefix::None,
+            bool_has_root: false,
+            vec_of_components: self.vec_of_components
+                [arg_windows_path_root_prefix.vec_of_components.len()..]
+                .to_vec(),
+        })
+    }
+
+    /// Returns the path with '..' resolved lexically
+    /// '..' directly under a root is dropped, while leading '..' in relative paths is kept.
+    /// Verbatim paths are returned unchanged, since Windows doesn't resolve '..' in them either.
+    pub fn normalized(&self) -> WindowsPath {
+        if self.prefix.is_verbatim() {
+            return self.clone();
+        }
+        let mut vec_of_components: Vec<String> = vec![];
+        for item_string in &self.vec_of_components {
+            if item_string != ".." {
+                vec_of_components.push(item_string.clone());
+                continue;
+            }
+            match vec_of_components.last() {
+                Some(string_last) if string_last != ".." => {
+                    vec_of_components.pop();
+                }
+                _ => {
+                    if !self.bool_has_root {
+                        vec_of_components.push(item_string.clone());
+                    }
+                }
+            }
+        }
+        WindowsPath {
+            prefix: self.prefix.clone(),
+            bool_has_root: self.bool_has_root,
+            vec_of_components,
+        }
+    }
+}
+
+impl Display for WindowsPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.prefix,)?;
+        let bool_prefix_is_disk_or_none = matches!(
+            self.prefix,
+            WindowsPrefix::None | WindowsPrefix::Disk(_) | WindowsPrefix::VerbatimDisk(_)
+        );
+        //
+        // Reminder: a bare share ( '\\server\share' ) is rooted, but is written without a trailing separator
+        //
+        if self.bool_has_root && (bool_prefix_is_disk_or_none || !self.vec_of_components.is_empty())
+        {
+            write!(f, "\\")?;
+        }
+        write!(f, "{}", self.vec_of_components.join("\\"),)
+    }
+}
+
+impl PartialEq for WindowsPath {
+    fn eq(&self, arg_other: &Self) -> bool {
+        self.prefix == arg_other.prefix
+            && self.bool_has_root == arg_other.bool_has_root
+            && self.vec_of_components.len() == arg_other.vec_of_components.len()
+            && self
+                .vec_of_components
+                .iter()
+                .zip(arg_other.vec_of_components.iter())
+                .all(|(item_left, item_right)| is_same_component(item_left, item_right))
+    }
+}
+
+impl Eq for WindowsPath {}
+
+impl From<&str> for WindowsPath {
+    fn from(arg_str_path: &str) -> Self {
+        WindowsPath::new(&arg_str_path)
+    }
+}
+//
+// Public - get - paths
+//
+/// Returns a string consisting of only the filename
+/// Returns None in case of failure
+/// # Arguments
+/// * arg_string_path: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::get_base_name( &"C:\\A\\B\\file.txt" );
+/// // result = Some( "file.txt" )
+pub fn get_base_name<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    WindowsPath::new(arg_string_path).base_name()
+}
+
+/// Returns the path without its final component
+/// Returns None if there is nothing to remove ( ie 'C:\' or '\\server\share' )
+/// # Arguments
+/// * arg_string_path: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::get_dir_name( &"C:/A/B/C" );
+/// // result = Some( "C:\\A\\B" )
+pub fn get_dir_name<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    WindowsPath::new(arg_string_path)
+        .dir_name()
+        .map(|windows_path| windows_path.to_string())
+}
+
+/// Returns a string representing the file extension without the period
+/// # Arguments
+/// * arg_string_path: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::get_extension( &"C:\\A\\file.txt" );
+/// // result = Some( "txt" )
+pub fn get_extension<T: Debug + Display>(arg_string_path: &T) -> Option<String> {
+    WindowsPath::new(arg_string_path).extension()
+}
+
+/// Returns a Windows path that is the result of combining a slice of string-like values
+/// Later elements with a drive or UNC prefix replace everything before them, as on Windows.
+/// Returns None if the slice is empty
+/// # Arguments
+/// * arg_slice_of_strings: slice of string-like Windows paths
+/// # Examples
+/// let result = easy_paths::windows::get_path_joined( &[ "C:\\A", "B", "C" ] );
+/// // result = Some( "C:\\A\\B\\C" )
+pub fn get_path_joined<T: Debug + Display>(arg_slice_of_strings: &[T]) -> Option<String> {
+    let mut iter_of_windows_paths = arg_slice_of_strings.iter().map(WindowsPath::new);
+    let windows_path_first = iter_of_windows_paths.next()?;
+    Some(
+        iter_of_windows_paths
+            .fold(windows_path_first, |windows_path_acc, item_windows_path| {
+                windows_path_acc.join(&item_windows_path)
+            })
+            .to_string(),
+    )
+}
+
+/// Returns a string that's a relative path after the prefix is removed
+/// Letter case is ignored when matching the prefix, and the remainder keeps its original case.
+/// In case of a failure, this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path: string-like Windows path
+/// * arg_string_path_root_prefix: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::get_relative_path( &"C:\\A\\B\\C\\D", &"c:/a/b" );
+/// // result = Ok( "C\\D" )
+pub fn get_relative_path<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path: &T1,
+    arg_string_path_root_prefix: &T2,
+) -> Result<String, String> {
+    match WindowsPath::new(arg_string_path)
+        .strip_prefix(&WindowsPath::new(arg_string_path_root_prefix))
+    {
+        Some(windows_path_result) => Ok(windows_path_result.to_string()),
+        None => Err([
+            "Error: strip_prefix() failed.".to_string(),
+            format!("arg_string_path = {}", arg_string_path,),
+            format!(
+                "arg_string_path_root_prefix = {}",
+                arg_string_path_root_prefix,
+            ),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Returns the path to arg_string_path_to relative to the directory arg_string_path_dir_from,
+/// climbing with '..' as needed
+/// Both paths are normalized lexically first, and components are compared without regard to case.
+/// If both paths are the same, this returns '.'
+/// In case of a failure ( different drives or UNC shares, or one path rooted and the other not ),
+/// this returns an error explaining what happened
+/// # Arguments
+/// * arg_string_path_dir_from: string-like Windows path
+/// * arg_string_path_to: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::get_relative_path_between( &"C:\\A\\X", &"c:\\a\\B\\C" );
+/// // result = Ok( "..\\B\\C" )
+pub fn get_relative_path_between<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_dir_from: &T1,
+    arg_string_path_to: &T2,
+) -> Result<String, String> {
+    let windows_path_from = WindowsPath::new(arg_string_path_dir_from).normalized();
+    let windows_path_to = WindowsPath::new(arg_string_path_to).normalized();
+    if windows_path_from.prefix != windows_path_to.prefix
+        || windows_path_from.bool_has_root != windows_path_to.bool_has_root
+    {
+        return Err([
+            "Error: paths have different drive or UNC roots, so no relative path exists."
+                .to_string(),
+            format!("arg_string_path_dir_from = {}", arg_string_path_dir_from,),
+            format!("arg_string_path_to = {}", arg_string_path_to,),
+        ]
+        .join("\n"));
+    }
+    let int_count_shared = windows_path_from
+        .vec_of_components
+        .iter()
+        .zip(windows_path_to.vec_of_components.iter())
+        .take_while(|(item_left, item_right)| is_same_component(item_left, item_right))
+        .count();
+    if windows_path_from.vec_of_components[int_count_shared..]
+        .iter()
+        .any(|item_string| item_string == "..")
+    {
+        return Err([
+            "Error: arg_string_path_dir_from climbs above its starting point, so no relative path exists."
+                .to_string(),
+            format!("arg_string_path_dir_from = {}", arg_string_path_dir_from,),
+        ]
+        .join("\n"));
+    }
+    let mut vec_of_components =
+        vec!["..".to_string(); windows_path_from.vec_of_components.len() - int_count_shared];
+    vec_of_components.extend(
+        windows_path_to.vec_of_components[int_count_shared..]
+            .iter()
+            .cloned(),
+    );
+    if vec_of_components.is_empty() {
+        return Ok(".".to_string());
+    }
+    Ok(vec_of_components.join("\\"))
+}
+//
+// Public - ( logic ) are / is
+//
+/// Returns true if both strings name the same Windows path, ignoring case and separator style
+/// This is purely textual; '..' is not resolved.
+/// # Arguments
+/// * arg_string_path_left: string-like Windows path
+/// * arg_string_path_right: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::are_paths_the_same( &"C:\\Users\\Me", &"c:/users/me/" );
+/// // result = true
+pub fn are_paths_the_same<T1: Debug + Display, T2: Debug + Display>(
+    arg_string_path_left: &T1,
+    arg_string_path_right: &T2,
+) -> bool {
+    WindowsPath::new(arg_string_path_left) == WindowsPath::new(arg_string_path_right)
+}
+
+/// Returns true if the string is an absolute Windows path
+/// # Arguments
+/// * arg_string_path: string-like Windows path
+/// # Examples
+/// let result = easy_paths::windows::is_absolute( &"C:\\A" );
+/// // result = true
+pub fn is_absolute<T: Debug + Display>(arg_string_path: &T) -> bool {
+    WindowsPath::new(arg_string_path).is_absolute()
+}
+//
+// Private
+//
+fn is_same_component(arg_str_left: &str, arg_str_right: &str) -> bool {
+    arg_str_left == arg_str_right || arg_str_left.to_lowercase() == arg_str_right.to_lowercase()
+}
+
+/// Splits the prefix off the front of a Windows path string
+fn get_prefix_and_remainder(arg_str_path: &str) -> (WindowsPrefix, &str) {
+    let is_separator = |arg_char: char| arg_char == '\\' || arg_char == '/';
+    if let Some(str_after_verbatim) = arg_str_path.strip_prefix("\\\\?\\") {
+        if let Some(str_after_unc) = str_after_verbatim
+            .strip_prefix("UNC\\")
+            .or_else(|| str_after_verbatim.strip_prefix("unc\\"))
+        {
+            let (string_server, string_share, str_remainder) =
+                get_server_share_and_remainder(str_after_unc, |arg_char| arg_char == '\\');
+            return (
+                WindowsPrefix::VerbatimUnc(string_server, string_share),
+                str_remainder,
+            );
+        }
+        if let Some(char_drive) = get_drive_letter(str_after_verbatim) {
+            return (
+                WindowsPrefix::VerbatimDisk(char_drive),
+                &str_after_verbatim[2..],
+            );
+        }
+        let int_index_end = str_after_verbatim
+            .find('\\')
+            .unwrap_or(str_after_verbatim.len());
+        return (
+            WindowsPrefix::Verbatim(str_after_verbatim[..int_index_end].to_string()),
+            &str_after_verbatim[int_index_end..],
+        );
+    }
+    let mut iter_of_chars = arg_str_path.chars();
+    if matches!(iter_of_chars.next(), Some(char_first) if is_separator(char_first))
+        && matches!(iter_of_chars.next(), Some(char_second) if is_separator(char_second))
+    {
+        let str_after_separators = &arg_str_path[2..];
+        let mut iter_of_chars_after = str_after_separators.chars();
+        if iter_of_chars_after.next() == Some('.')
+            && matches!(iter_of_chars_after.next(), Some(char_next) if is_separator(char_next))
+        {
+            let str_device = &str_after_separators[2..];
+            let int_index_end = str_device.find(is_separator).unwrap_or(str_device.len());
+            return (
+                WindowsPrefix::DeviceNs(str_device[..int_index_end].to_string()),
+                &str_device[int_index_end..],
+            );
+        }
+        let (string_server, string_share, str_remainder) =
+            get_server_share_and_remainder(str_after_separators, is_separator);
+        return (
+            WindowsPrefix::Unc(string_server, string_share),
+            str_remainder,
+        );
+    }
+    match get_drive_letter(arg_str_path) {
+        Some(char_drive) => (WindowsPrefix::Disk(char_drive), &arg_str_path[2..]),
+        None => (WindowsPrefix::None, arg_str_path),
+    }
+}
+
+fn get_drive_letter(arg_str: &str) -> Option<char> {
+    let mut iter_of_chars = arg_str.chars();
+    match (iter_of_chars.next(), iter_of_chars.next()) {
+        (Some(char_drive), Some(':')) if char_drive.is_ascii_alphabetic() => Some(char_drive),
+        _ => None,
+    }
+}
+
+fn get_server_share_and_remainder<F: Fn(char) -> bool + Copy>(
+    arg_str: &str,
+    arg_is_separator: F,
+) -> (String, String, &str) {
+    let int_index_server_end = arg_str.find(arg_is_separator).unwrap_or(arg_str.len());
+    let str_server = &arg_str[..int_index_server_end];
+    let str_after_server = match arg_str[int_index_server_end..].chars().next() {
+        Some(_char_separator) => &arg_str[int_index_server_end + 1..],
+        None => "",
+    };
+    let int_index_share_end = str_after_server
+        .find(arg_is_separator)
+        .unwrap_or(str_after_server.len());
+    (
+        str_server.to_string(),
+        str_after_server[..int_index_share_end].to_string(),
+        &str_after_server[int_index_share_end..],
+    )
+}
+//
+// Tests
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_windows_path_prefixes() {
+        assert_eq!(
+            WindowsPath::new(&"C:\\A\\B").prefix().to_string(),
+            "C:".to_string()
+        );
+        assert_eq!(
+            WindowsPath::new(&"\\\\server\\share\\A")
+                .prefix()
+                .to_string(),
+            "\\\\server\\share".to_string()
+        );
+        assert_eq!(
+            WindowsPath::new(&"//server/share/A").to_string(),
+            "\\\\server\\share\\A".to_string()
+        );
+        assert_eq!(
+            WindowsPath::new(&"\\\\?\\UNC\\server\\share\\A")
+                .prefix()
+                .to_string(),
+            "\\\\?\\UNC\\server\\share".to_string()
+        );
+        assert_eq!(
+            WindowsPath::new(&"\\\\?\\C:\\A/B").components(),
+            &["A/B".to_string()]
+        );
+        assert_eq!(
+            WindowsPath::new(&"\\\\.\\COM1").to_string(),
+            "\\\\.\\COM1".to_string()
+        );
+    }
+
+    #[test]
+    fn test_get_base_name() {
+        assert_eq!(
+            get_base_name(&"C:\\A\\B\\file.txt"),
+            Some("file.txt".to_string())
+        );
+        assert_eq!(get_base_name(&"C:\\"), None);
+        assert_eq!(get_base_name(&"A\\.."), None);
+    }
+
+    #[test]
+    fn test_get_dir_name() {
+        assert_eq!(get_dir_name(&"C:/A/B/C"), Some("C:\\A\\B".to_string()));
+        assert_eq!(get_dir_name(&"C:\\A"), Some("C:\\".to_string()));
+        assert_eq!(get_dir_name(&"C:\\"), None);
+        assert_eq!(
+            get_dir_name(&"\\\\server\\share\\A"),
+            Some("\\\\server\\share".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_extension() {
+        assert_eq!(get_extension(&"C:\\A\\file.txt"), Some("txt".to_string()));
+        assert_eq!(get_extension(&"C:\\A\\.bashrc"), None);
+        assert_eq!(get_extension(&"C:\\A.dir\\file"), None);
+    }
+
+    #[test]
+    fn test_get_path_joined() {
+        assert_eq!(
+            get_path_joined(&["C:\\A", "B", "C"]),
+            Some("C:\\A\\B\\C".to_string())
+        );
+        assert_eq!(
+            get_path_joined(&["C:\\A", "\\B"]),
+            Some("C:\\B".to_string())
+        );
+        assert_eq!(
+            get_path_joined(&["C:\\A", "D:\\B"]),
+            Some("D:\\B".to_string())
+        );
+    }
+
+    #[test]
+    fn test_get_relative_path() {
+        let result = match get_relative_path(&"C:\\A\\B\\C\\D", &"c:/a/b") {
+            Ok(string_result) => string_result,
+            Err(err) => panic!("{}", err,),
+        };
+        assert_eq!(result, "C\\D".to_string());
+        match get_relative_path(&"C:\\A\\B", &"D:\\A") {
+            Ok(string_result) => panic!("Stripped across drives: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+
+    #[test]
+    fn test_get_relative_path_between() {
+        assert_eq!(
+            get_relative_path_between(&"C:\\A\\X", &"c:\\a\\B\\C"),
+            Ok("..\\B\\C".to_string())
+        );
+        assert_eq!(
+            get_relative_path_between(&"\\\\server\\share\\A", &"//SERVER/share/A/./B/../C"),
+            Ok("C".to_string())
+        );
+        assert_eq!(
+            get_relative_path_between(&"C:\\A", &"C:\\A\\"),
+            Ok(".".to_string())
+        );
+        match get_relative_path_between(&"C:\\A", &"D:\\A") {
+            Ok(string_result) => panic!("Related paths across drives: {}", string_result),
+            Err(_err) => {}
+        }
+        match get_relative_path_between(&"\\\\server\\share\\A", &"C:\\A") {
+            Ok(string_result) => panic!("Related UNC path to drive: {}", string_result),
+            Err(_err) => {}
+        }
+    }
+
+    #[test]
+    fn test_are_paths_the_same() {
+        assert!(are_paths_the_same(&"C:\\Users\\Me", &"c:/users/me/"));
+        assert!(are_paths_the_same(&"\\\\?\\C:\\A", &"C:\\A"));
+        assert!(!are_paths_the_same(&"C:\\A", &"C:A"));
+    }
+
+    #[test]
+    fn test_is_absolute() {
+        assert!(is_absolute(&"C:\\A"));
+        assert!(is_absolute(&"\\\\server\\share"));
+        assert!(!is_absolute(&"C:A"));
+        assert!(!is_absolute(&"\\A"));
+    }
+}
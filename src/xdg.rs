@@ -0,0 +1,467 @@
+//
+// Libraries - native
+//
+use std::fmt::{Debug, Display};
+use std::path::Path;
+//
+// Libraries - local
+//
+use crate::get_path_with_tilde_expanded_if_necessary;
+//
+// Public - enums
+//
+/// The standard user directories from xdg-user-dirs ( ~/.config/user-dirs.dirs )
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UserDir {
+    Desktop,
+    Documents,
+    Download,
+    Music,
+    Pictures,
+    PublicShare,
+    Templates,
+    Videos,
+}
+//
+// Public - get - xdg
+//
+/// Returns the config directory for arg_string_app, ie "~/.config/my_app"
+/// This follows the XDG Base Directory spec: $XDG_CONFIG_HOME is used if it's set to an absolute path,
+/// otherwise this falls back to "~/.config". An empty app name returns the base directory itself.
+/// The directory isn't created, see get_dir_config_created()
+/// # Arguments
+/// * arg_string_app: string-like app name
+/// # Examples
+/// let string_path_dir_config = match get_dir_config( &"my_app" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_dir_config<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_xdg_for_app(
+        &get_option_string_from_env,
+        "XDG_CONFIG_HOME",
+        ".config",
+        arg_string_app,
+    )
+}
+
+/// Returns the cache directory for arg_string_app, ie "~/.cache/my_app"
+/// $XDG_CACHE_HOME is used if it's set to an absolute path, otherwise this falls back to "~/.cache".
+/// # Arguments
+/// * arg_string_app: string-like app name
+/// # Examples
+/// let string_path_dir_cache = match get_dir_cache( &"my_app" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_dir_cache<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_xdg_for_app(
+        &get_option_string_from_env,
+        "XDG_CACHE_HOME",
+        ".cache",
+        arg_string_app,
+    )
+}
+
+/// Returns the data directory for arg_string_app, ie "~/.local/share/my_app"
+/// $XDG_DATA_HOME is used if it's set to an absolute path, otherwise this falls back to "~/.local/share".
+/// # Arguments
+/// * arg_string_app: string-like app name
+/// # Examples
+/// let string_path_dir_data = match get_dir_data( &"my_app" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_dir_data<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_xdg_for_app(
+        &get_option_string_from_env,
+        "XDG_DATA_HOME",
+        ".local/share",
+        arg_string_app,
+    )
+}
+
+/// Returns the state directory for arg_string_app, ie "~/.local/state/my_app"
+/// $XDG_STATE_HOME is used if it's set to an absolute path, otherwise this falls back to "~/.local/state".
+/// # Arguments
+/// * arg_string_app: string-like app name
+/// # Examples
+/// let string_path_dir_state = match get_dir_state( &"my_app" ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_dir_state<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_xdg_for_app(
+        &get_option_string_from_env,
+        "XDG_STATE_HOME",
+        ".local/state",
+        arg_string_app,
+    )
+}
+
+/// Returns $XDG_RUNTIME_DIR
+/// The spec gives no fallback, so this returns an error if the variable is unset or relative.
+/// # Examples
+/// let string_path_dir_runtime = match get_dir_runtime() {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_dir_runtime() -> Result<String, String> {
+    get_dir_runtime_with_env(&get_option_string_from_env)
+}
+
+/// Same as get_dir_config(), except the directory is created if it doesn't exist
+/// On unix, directories created here get mode 0700, as the spec requires.
+/// # Arguments
+/// * arg_string_app: string-like app name
+pub fn get_dir_config_created<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_created(get_dir_config(arg_string_app)?)
+}
+
+/// Same as get_dir_cache(), except the directory is created with mode 0700 if it doesn't exist
+/// # Arguments
+/// * arg_string_app: string-like app name
+pub fn get_dir_cache_created<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_created(get_dir_cache(arg_string_app)?)
+}
+
+/// Same as get_dir_data(), except the directory is created with mode 0700 if it doesn't exist
+/// # Arguments
+/// * arg_string_app: string-like app name
+pub fn get_dir_data_created<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_created(get_dir_data(arg_string_app)?)
+}
+
+/// Same as get_dir_state(), except the directory is created with mode 0700 if it doesn't exist
+/// # Arguments
+/// * arg_string_app: string-like app name
+pub fn get_dir_state_created<T: Debug + Display>(arg_string_app: &T) -> Result<String, String> {
+    get_dir_created(get_dir_state(arg_string_app)?)
+}
+
+/// Returns every directory to search for arg_string_app's config files, most important first
+/// This is get_dir_config() followed by each entry of $XDG_CONFIG_DIRS ( default "/etc/xdg" ).
+/// Relative entries are skipped, as the spec requires. Nothing is checked for existence.
+/// # Arguments
+/// * arg_string_app: string-like app name
+/// # Examples
+/// let vec_of_dirs = match get_dirs_config_search( &"my_app" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // vec_of_dirs = [ "/home/me/.config/my_app", "/etc/xdg/my_app" ]
+pub fn get_dirs_config_search<T: Debug + Display>(
+    arg_string_app: &T,
+) -> Result<Vec<String>, String> {
+    get_dirs_xdg_search(
+        &get_option_string_from_env,
+        get_dir_config(arg_string_app)?,
+        "XDG_CONFIG_DIRS",
+        "/etc/xdg",
+        arg_string_app,
+    )
+}
+
+/// Returns every directory to search for arg_string_app's data files, most important first
+/// This is get_dir_data() followed by each entry of $XDG_DATA_DIRS ( default "/usr/local/share/:/usr/share/" ).
+/// # Arguments
+/// * arg_string_app: string-like app name
+/// # Examples
+/// let vec_of_dirs = match get_dirs_data_search( &"my_app" ) {
+///     Ok( vec_result ) => { vec_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+/// // vec_of_dirs = [ "/home/me/.local/share/my_app", "/usr/local/share/my_app", "/usr/share/my_app" ]
+pub fn get_dirs_data_search<T: Debug + Display>(arg_string_app: &T) -> Result<Vec<String>, String> {
+    get_dirs_xdg_search(
+        &get_option_string_from_env,
+        get_dir_data(arg_string_app)?,
+        "XDG_DATA_DIRS",
+        "/usr/local/share/:/usr/share/",
+        arg_string_app,
+    )
+}
+
+/// Returns a standard user directory, ie "~/Downloads"
+/// Entries are read from user-dirs.dirs in the config directory. If the file or the entry is missing,
+/// this falls back to "~/Desktop" for UserDir::Desktop and to "~" for everything else, as xdg-user-dir does.
+/// # Arguments
+/// * arg_user_dir: UserDir
+/// # Examples
+/// let string_path_dir_downloads = match get_dir_user( UserDir::Download ) {
+///     Ok( string_result ) => { string_result }
+///     Err( err ) => { panic!( "{}", err, ) }
+/// };
+pub fn get_dir_user(arg_user_dir: UserDir) -> Result<String, String> {
+    get_dir_user_with_env(&get_option_string_from_env, arg_user_dir)
+}
+//
+// Private
+//
+/// Creates arg_string_path, and any missing parents, then returns it
+fn get_dir_created(arg_string_path: String) -> Result<String, String> {
+    let mut dir_builder = std::fs::DirBuilder::new();
+    dir_builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        dir_builder.mode(0o700);
+    }
+    match dir_builder.create(&arg_string_path) {
+        Ok(()) => Ok(arg_string_path),
+        Err(err) => Err([
+            "Error: failed to create directory.".to_string(),
+            format!("err = {}", err,),
+            format!("arg_string_path = {}", arg_string_path,),
+        ]
+        .join("\n")),
+    }
+}
+
+/// Returns the home directory, looked up through arg_closure_get_env
+fn get_dir_home<F: Fn(&str) -> Option<String>>(arg_closure_get_env: &F) -> Result<String, String> {
+    match arg_closure_get_env("HOME") {
+        Some(string_path) if !string_path.is_empty() && string_path != "~" => Ok(string_path),
+        _ => Err("Error: failed to find the home directory.".to_string()),
+    }
+}
+
+fn get_dir_runtime_with_env<F: Fn(&str) -> Option<String>>(
+    arg_closure_get_env: &F,
+) -> Result<String, String> {
+    match get_option_string_path_from_env(arg_closure_get_env, "XDG_RUNTIME_DIR") {
+        Some(string_result) => Ok(string_result),
+        None => Err([
+            "Error: XDG_RUNTIME_DIR is not set to an absolute path.".to_string(),
+            format!(
+                "XDG_RUNTIME_DIR = {:?}",
+                arg_closure_get_env("XDG_RUNTIME_DIR"),
+            ),
+        ]
+        .join("\n")),
+    }
+}
+
+fn get_dir_user_with_env<F: Fn(&str) -> Option<String>>(
+    arg_closure_get_env: &F,
+    arg_user_dir: UserDir,
+) -> Result<String, String> {
+    let string_path_dir_home = get_dir_home(arg_closure_get_env)?;
+    let str_key = match arg_user_dir {
+        UserDir::Desktop => "XDG_DESKTOP_DIR",
+        UserDir::Documents => "XDG_DOCUMENTS_DIR",
+        UserDir::Download => "XDG_DOWNLOAD_DIR",
+        UserDir::Music => "XDG_MUSIC_DIR",
+        UserDir::Pictures => "XDG_PICTURES_DIR",
+        UserDir::PublicShare => "XDG_PUBLICSHARE_DIR",
+        UserDir::Templates => "XDG_TEMPLATES_DIR",
+        UserDir::Videos => "XDG_VIDEOS_DIR",
+    };
+    let string_path_file_user_dirs = format!(
+        "{}/user-dirs.dirs",
+        get_dir_xdg_for_app(arg_closure_get_env, "XDG_CONFIG_HOME", ".config", &"")?,
+    );
+    // A missing file just means the defaults apply
+    let string_contents = std::fs::read_to_string(&string_path_file_user_dirs).unwrap_or_default();
+    if let Some(string_result) =
+        get_option_string_path_from_user_dirs(&string_contents, str_key, &string_path_dir_home)
+    {
+        return Ok(string_result);
+    }
+    Ok(match arg_user_dir {
+        UserDir::Desktop => format!("{}/Desktop", string_path_dir_home,),
+        _ => string_path_dir_home,
+    })
+}
+
+/// Returns the app's directory under $arg_str_env_var, or under arg_str_path_default inside the home directory
+fn get_dir_xdg_for_app<F: Fn(&str) -> Option<String>, T: Debug + Display>(
+    arg_closure_get_env: &F,
+    arg_str_env_var: &str,
+    arg_str_path_default: &str,
+    arg_string_app: &T,
+) -> Result<String, String> {
+    let string_path_base =
+        match get_option_string_path_from_env(arg_closure_get_env, arg_str_env_var) {
+            Some(string_result) => string_result,
+            None => format!(
+                "{}/{}",
+                get_dir_home(arg_closure_get_env)?,
+                arg_str_path_default,
+            ),
+        };
+    Ok(get_string_path_joined_with_app(
+        &string_path_base,
+        arg_string_app,
+    ))
+}
+
+fn get_dirs_xdg_search<F: Fn(&str) -> Option<String>, T: Debug + Display>(
+    arg_closure_get_env: &F,
+    arg_string_path_dir_base: String,
+    arg_str_env_var: &str,
+    arg_str_dirs_default: &str,
+    arg_string_app: &T,
+) -> Result<Vec<String>, String> {
+    let string_dirs = match arg_closure_get_env(arg_str_env_var) {
+        Some(string_result) if !string_result.is_empty() => string_result,
+        _ => arg_str_dirs_default.to_string(),
+    };
+    let mut vec_to_return = vec![arg_string_path_dir_base];
+    for item_str_dir in string_dirs.split(':') {
+        if !Path::new(item_str_dir).is_absolute() {
+            continue;
+        }
+        let string_path = get_string_path_joined_with_app(item_str_dir, arg_string_app);
+        if !vec_to_return.contains(&string_path) {
+            vec_to_return.push(string_path);
+        }
+    }
+    Ok(vec_to_return)
+}
+
+/// Returns the variable from the real environment
+/// "HOME" is answered with the home directory '~' expands to, which also works where $HOME isn't set.
+fn get_option_string_from_env(arg_str_env_var: &str) -> Option<String> {
+    if arg_str_env_var == "HOME" {
+        return Some(get_path_with_tilde_expanded_if_necessary(&"~"));
+    }
+    std::env::var(arg_str_env_var).ok()
+}
+
+/// Returns the variable's value if it's set to an absolute path
+/// The spec says relative values are invalid and must be ignored.
+fn get_option_string_path_from_env<F: Fn(&str) -> Option<String>>(
+    arg_closure_get_env: &F,
+    arg_str_env_var: &str,
+) -> Option<String> {
+    match arg_closure_get_env(arg_str_env_var) {
+        Some(string_result) if Path::new(&string_result).is_absolute() => Some(string_result),
+        _ => None,
+    }
+}
+
+/// Returns the value of arg_str_key from the contents of a user-dirs.dirs file
+/// Lines look like 'XDG_DOWNLOAD_DIR="$HOME/Downloads"'; only "$HOME/..." and absolute values are valid.
+fn get_option_string_path_from_user_dirs(
+    arg_str_contents: &str,
+    arg_str_key: &str,
+    arg_str_path_dir_home: &str,
+) -> Option<String> {
+    for item_str_line in arg_str_contents.lines() {
+        let str_value = match item_str_line
+            .trim()
+            .strip_prefix(arg_str_key)
+            .and_then(|str_result| str_result.strip_prefix('='))
+        {
+            Some(str_result) => str_result.trim_matches('"'),
+            None => continue,
+        };
+        if let Some(str_remainder) = str_value.strip_prefix("$HOME") {
+            return Some(format!("{}{}", arg_str_path_dir_home, str_remainder,));
+        }
+        if Path::new(str_value).is_absolute() {
+            return Some(str_value.to_string());
+        }
+    }
+    None
+}
+
+fn get_string_path_joined_with_app<T: Debug + Display>(
+    arg_str_path_base: &str,
+    arg_string_app: &T,
+) -> String {
+    let string_app = format!("{}", arg_string_app,);
+    let str_path_base = arg_str_path_base.trim_end_matches('/');
+    if string_app.is_empty() {
+        return str_path_base.to_string();
+    }
+    format!("{}/{}", str_path_base, string_app,)
+}
+//
+// Tests
+//
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::get_dir_temp_for_tests;
+
+    #[test]
+    fn test_get_dirs_xdg() {
+        let string_path_dir = get_dir_temp_for_tests("get_dirs_xdg");
+        let string_path_dir_home = format!("{}/home", string_path_dir,);
+        let map_of_env_vars = std::collections::HashMap::from([
+            ("HOME", string_path_dir_home.clone()),
+            ("XDG_CONFIG_HOME", format!("{}/config/", string_path_dir,)),
+            ("XDG_CACHE_HOME", "relative/is/ignored".to_string()),
+            ("XDG_CONFIG_DIRS", "/etc/a:relative:/etc/b".to_string()),
+        ]);
+        let closure_get_env = |arg_str_env_var: &str| map_of_env_vars.get(arg_str_env_var).cloned();
+        assert_eq!(
+            get_dir_xdg_for_app(&closure_get_env, "XDG_CONFIG_HOME", ".config", &"app"),
+            Ok(format!("{}/config/app", string_path_dir,))
+        );
+        assert_eq!(
+            get_dir_xdg_for_app(&closure_get_env, "XDG_CACHE_HOME", ".cache", &"app"),
+            Ok(format!("{}/.cache/app", string_path_dir_home,))
+        );
+        assert_eq!(
+            get_dirs_xdg_search(
+                &closure_get_env,
+                format!("{}/config/app", string_path_dir,),
+                "XDG_CONFIG_DIRS",
+                "/etc/xdg",
+                &"app",
+            ),
+            Ok(vec![
+                format!("{}/config/app", string_path_dir,),
+                "/etc/a/app".to_string(),
+                "/etc/b/app".to_string(),
+            ])
+        );
+        assert!(get_dir_runtime_with_env(&closure_get_env).is_err());
+        assert!(
+            get_dir_xdg_for_app(&|_: &str| None, "XDG_CONFIG_HOME", ".config", &"app").is_err()
+        );
+        let string_path_dir_created = get_dir_created(
+            get_dir_xdg_for_app(
+                &closure_get_env,
+                "XDG_CONFIG_HOME",
+                ".config",
+                &"app/nested",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(Path::new(&string_path_dir_created).is_dir());
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let int_mode = std::fs::metadata(&string_path_dir_created)
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(int_mode & 0o077, 0);
+        }
+        std::fs::write(
+            format!("{}/config/user-dirs.dirs", string_path_dir,),
+            "# comment\nXDG_DOWNLOAD_DIR=\"$HOME/Fetched\"\nXDG_MUSIC_DIR=\"/srv/music\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            get_dir_user_with_env(&closure_get_env, UserDir::Download),
+            Ok(format!("{}/Fetched", string_path_dir_home,))
+        );
+        assert_eq!(
+            get_dir_user_with_env(&closure_get_env, UserDir::Music),
+            Ok("/srv/music".to_string())
+        );
+        assert_eq!(
+            get_dir_user_with_env(&closure_get_env, UserDir::Desktop),
+            Ok(format!("{}/Desktop", string_path_dir_home,))
+        );
+        assert_eq!(
+            get_dir_user_with_env(&closure_get_env, UserDir::Videos),
+            Ok(string_path_dir_home)
+        );
+    }
+}